@@ -0,0 +1,248 @@
+//! Table conversion CLI for the original fujidig/sfmt-rainbow layout
+//!
+//! Usage:
+//!   gen7seed_convert import <consumption> <FILE>... [options]
+//!   gen7seed_convert export <consumption> [options]
+//!
+//! Commands:
+//!   import  Convert original table files (in table ID order) into {consumption}.g7rt
+//!   export  Write the chains of {consumption}.g7rt as {consumption}_{table_id}.bin
+//!
+//! Options:
+//!   --chain-length <N>  Chain length of the original tables (import, default: 3000)
+//!   --table-dir <PATH>  Table directory (export, default: current directory)
+//!   --out-dir <PATH>    Output directory (default: current directory)
+//!   --help, -h          Show help
+//!
+//! Example:
+//!   gen7seed_convert import 417 417.bin
+//!   gen7seed_convert export 417 --table-dir .\tables --out-dir .\original
+//!
+//! Import recomputes a few chains of every table to check the reduction
+//! function. No table generated by the original implementation can be
+//! imported: its `.bin` files use a different reduction function than this
+//! crate and its `.sorted.bin` files hold truncated end hashes instead of end
+//! seeds. Both are refused with an explanation; import is meant for files
+//! written by `export`.
+
+use gen7seed_rainbow::domain::original_format::ORIGINAL_CHAIN_LENGTH;
+use gen7seed_rainbow::infra::original_io::{load_original_table, save_original_table};
+use gen7seed_rainbow::infra::table_io::{get_single_table_path, save_single_table_with_header};
+use gen7seed_rainbow::{
+    ImportOptions, MappedSingleTable, ValidationOptions, export_original_tables,
+    import_original_tables,
+};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+enum Command {
+    Import { inputs: Vec<PathBuf> },
+    Export,
+}
+
+struct Args {
+    command: Command,
+    consumption: i32,
+    chain_length: u32,
+    table_dir: Option<PathBuf>,
+    out_dir: Option<PathBuf>,
+}
+
+fn print_usage(program: &str) {
+    eprintln!(
+        "Usage: {} import <consumption> <FILE>... [options]",
+        program
+    );
+    eprintln!("       {} export <consumption> [options]", program);
+    eprintln!();
+    eprintln!("Commands:");
+    eprintln!("  import              Convert original table files (in table ID order) into");
+    eprintln!("                      {{consumption}}.g7rt");
+    eprintln!("  export              Write the chains of {{consumption}}.g7rt as");
+    eprintln!("                      {{consumption}}_{{table_id}}.bin");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  --chain-length <N>  Chain length of the original tables (import, default: {})",
+        ORIGINAL_CHAIN_LENGTH
+    );
+    eprintln!("  --table-dir <PATH>  Table directory (export, default: current directory)");
+    eprintln!("  --out-dir <PATH>    Output directory (default: current directory)");
+    eprintln!("  --help, -h          Show this help message");
+    eprintln!();
+    eprintln!("Tables generated by fujidig/sfmt-rainbow cannot be imported: .bin files use");
+    eprintln!("another reduction function and .sorted.bin files hold truncated end hashes.");
+    eprintln!("Import accepts files written by export (this crate's reduction function).");
+}
+
+fn parse_args() -> Result<Args, String> {
+    let args: Vec<String> = env::args().collect();
+
+    let mut command: Option<&str> = None;
+    let mut consumption: Option<i32> = None;
+    let mut inputs = Vec::new();
+    let mut chain_length = ORIGINAL_CHAIN_LENGTH;
+    let mut table_dir: Option<PathBuf> = None;
+    let mut out_dir: Option<PathBuf> = None;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--chain-length" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--chain-length requires a value".to_string());
+                }
+                chain_length = args[i]
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("Invalid value for --chain-length: {}", args[i]))?;
+            }
+            option @ ("--table-dir" | "--out-dir") => {
+                i += 1;
+                if i >= args.len() {
+                    return Err(format!("{} requires a value", option));
+                }
+                let dir = Some(PathBuf::from(&args[i]));
+                if option == "--table-dir" {
+                    table_dir = dir;
+                } else {
+                    out_dir = dir;
+                }
+            }
+            "--help" | "-h" => {
+                print_usage(&args[0]);
+                std::process::exit(0);
+            }
+            arg if !arg.starts_with('-') => {
+                if command.is_none() {
+                    command = Some(arg);
+                } else if consumption.is_none() {
+                    consumption = Some(
+                        arg.parse()
+                            .map_err(|_| format!("Invalid consumption value: {}", arg))?,
+                    );
+                } else {
+                    inputs.push(PathBuf::from(arg));
+                }
+            }
+            _ => return Err(format!("Unknown option: {}", args[i])),
+        }
+        i += 1;
+    }
+
+    let command = match command.ok_or("Missing command (import or export)")? {
+        "import" => {
+            if inputs.is_empty() {
+                return Err("import requires at least one table file".to_string());
+            }
+            Command::Import { inputs }
+        }
+        "export" => {
+            if let Some(input) = inputs.first() {
+                return Err(format!("Unexpected argument: {}", input.display()));
+            }
+            Command::Export
+        }
+        other => return Err(format!("Unknown command: {}", other)),
+    };
+    let consumption = consumption.ok_or("Missing consumption argument")?;
+
+    Ok(Args {
+        command,
+        consumption,
+        chain_length,
+        table_dir,
+        out_dir,
+    })
+}
+
+/// Convert original table files into a `.g7rt` file
+fn run_import(args: &Args, inputs: &[PathBuf], out_dir: &Path) -> Result<(), String> {
+    let mut tables = Vec::with_capacity(inputs.len());
+    for (table_id, path) in inputs.iter().enumerate() {
+        let table = load_original_table(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!(
+            "[Table {}] Loaded {} chains from {}",
+            table_id,
+            table.len(),
+            path.display()
+        );
+        tables.push(table);
+    }
+
+    println!("Checking the reduction function and sorting...");
+    let options = ImportOptions::new(args.consumption).with_chain_length(args.chain_length);
+    let (header, tables) = import_original_tables(tables, &options).map_err(|e| e.to_string())?;
+
+    let output_path = get_single_table_path(out_dir, args.consumption);
+    save_single_table_with_header(&output_path, &header, &tables, None)
+        .map_err(|e| format!("{}: {}", output_path.display(), e))?;
+    println!(
+        "Saved {} tables ({} chains each, chain length {}) to {}",
+        header.num_tables,
+        header.chains_per_table,
+        header.chain_length,
+        output_path.display()
+    );
+    Ok(())
+}
+
+/// Write the chains of a `.g7rt` file in the original layout
+fn run_export(args: &Args, out_dir: &Path) -> Result<(), String> {
+    let table_dir = args.table_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+    let table_path = get_single_table_path(&table_dir, args.consumption);
+    let options = ValidationOptions {
+        expected_consumption: Some(args.consumption),
+        ..ValidationOptions::default()
+    };
+    let table = MappedSingleTable::open(&table_path, &options)
+        .map_err(|e| format!("{}: {}", table_path.display(), e))?;
+
+    let tables = export_original_tables(&table).map_err(|e| e.to_string())?;
+    for (table_id, entries) in tables.iter().enumerate() {
+        let path = out_dir.join(format!("{}_{}.bin", args.consumption, table_id));
+        save_original_table(&path, entries).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!(
+            "[Table {}] Saved {} chains to {}",
+            table_id,
+            entries.len(),
+            path.display()
+        );
+    }
+
+    println!(
+        "Note: The chains use this crate's salted reduction function (chain length {}).",
+        table.header().chain_length
+    );
+    println!("      The original search cannot use them; import them again with gen7seed_convert.");
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!();
+            print_usage(&env::args().next().unwrap_or_default());
+            std::process::exit(1);
+        }
+    };
+
+    let out_dir = args.out_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+    let start = Instant::now();
+
+    let result = match &args.command {
+        Command::Import { inputs } => run_import(&args, inputs, &out_dir),
+        Command::Export => run_export(&args, &out_dir),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    println!("Done in {:.2} seconds.", start.elapsed().as_secs_f64());
+}
@@ -3,38 +3,86 @@
 //! Usage: gen7seed_create <consumption> [options]
 //!
 //! Options:
-//!   --no-sort        Skip sorting (generate unsorted table only)
-//!   --out-dir <PATH> Output directory (default: current directory)
-//!   --help, -h       Show help
+//!   --no-sort         Skip sorting (generate unsorted table only)
+//!   --end-hash-keys   Store the end hash of every chain (faster search, larger file)
+//!   --out-dir <PATH>  Output directory (default: current directory)
+//!   --work-dir <PATH> Checkpoint directory (default: <out-dir>/<consumption>_checkpoints)
+//!   --resume          Resume from the checkpoints of an interrupted run
+//!   --coverage-guided Start chains from seeds not covered yet (uses 512 MB more memory)
+//!   --perfect         Replace merged chains so that every end hash is distinct
+//!   --compact         Write the compact table format (bit-packed, about 45% smaller)
+//!   --created-at <SECS> Creation timestamp to record (default: SOURCE_DATE_EPOCH or now)
+//!   --chain-length <N> Chain length (default: 4096)
+//!   --chains <N>      Number of chains per table (default: 647168)
+//!   --tables <N>      Number of tables (default: 16)
+//!   --help, -h        Show help
 //!
 //! Example:
 //!   gen7seed_create 417
 
-use gen7seed_rainbow::constants::{NUM_TABLES, SUPPORTED_CONSUMPTIONS};
-use gen7seed_rainbow::infra::table_io::{get_single_table_path, save_single_table};
-use gen7seed_rainbow::infra::table_sort::sort_table_parallel;
-use gen7seed_rainbow::{GenerateOptions, generate_table};
+use gen7seed_rainbow::constants::{SOURCE_DATE_EPOCH, SUPPORTED_CONSUMPTIONS};
+use gen7seed_rainbow::infra::table_io::get_single_table_path;
+use gen7seed_rainbow::{
+    CancellationToken, CheckpointError, CheckpointEvent, CheckpointOptions, StartPoints,
+    TableParams, generate_tables_with_checkpoints,
+};
 use std::env;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 struct Args {
     consumption: i32,
     no_sort: bool,
+    end_hash_keys: bool,
     out_dir: Option<PathBuf>,
+    work_dir: Option<PathBuf>,
+    resume: bool,
+    start_points: StartPoints,
+    perfect: bool,
+    compact: bool,
+    created_at: Option<u64>,
+    params: TableParams,
 }
 
 fn print_usage(program: &str) {
     eprintln!("Usage: {} <consumption> [options]", program);
     eprintln!();
     eprintln!("Arguments:");
-    eprintln!("  <consumption>    Number of RNG consumptions (e.g., 417)");
+    eprintln!("  <consumption>     Number of RNG consumptions (e.g., 417)");
     eprintln!();
     eprintln!("Options:");
-    eprintln!("  --no-sort        Skip sorting (generate unsorted table only)");
-    eprintln!("  --out-dir <PATH> Output directory for table files (default: current directory)");
-    eprintln!("  --help, -h       Show this help message");
+    eprintln!("  --no-sort         Skip sorting (generate unsorted table only)");
+    eprintln!("  --end-hash-keys   Store the end hash of every chain (faster search, larger file)");
+    eprintln!("  --out-dir <PATH>  Output directory for table files (default: current directory)");
+    eprintln!(
+        "  --work-dir <PATH> Checkpoint directory (default: <out-dir>/<consumption>_checkpoints)"
+    );
+    eprintln!("  --resume          Resume from the checkpoints of an interrupted run");
+    eprintln!(
+        "  --coverage-guided Start chains from seeds not covered yet (uses 512 MB more memory)"
+    );
+    eprintln!("  --perfect         Replace merged chains so that every end hash is distinct");
+    eprintln!("  --compact         Write the compact table format (bit-packed, about 45% smaller)");
+    eprintln!(
+        "  --created-at <SECS> Creation timestamp to record (default: {} or now)",
+        SOURCE_DATE_EPOCH
+    );
+
+    let defaults = TableParams::default();
+    eprintln!(
+        "  --chain-length <N> Chain length (default: {})",
+        defaults.chain_length
+    );
+    eprintln!(
+        "  --chains <N>      Number of chains per table (default: {})",
+        defaults.chains_per_table
+    );
+    eprintln!(
+        "  --tables <N>      Number of tables (default: {})",
+        defaults.num_tables
+    );
+    eprintln!("  --help, -h        Show this help message");
     eprintln!();
     eprintln!("Supported consumption values: {:?}", SUPPORTED_CONSUMPTIONS);
 }
@@ -44,12 +92,25 @@ fn parse_args() -> Result<Args, String> {
 
     let mut consumption: Option<i32> = None;
     let mut no_sort = false;
+    let mut end_hash_keys = false;
     let mut out_dir: Option<PathBuf> = None;
+    let mut work_dir: Option<PathBuf> = None;
+    let mut resume = false;
+    let mut start_points = StartPoints::Sequential;
+    let mut perfect = false;
+    let mut compact = false;
+    let mut created_at: Option<u64> = None;
+    let mut params = TableParams::default();
 
     let mut i = 1;
     while i < args.len() {
         match args[i].as_str() {
             "--no-sort" => no_sort = true,
+            "--end-hash-keys" => end_hash_keys = true,
+            "--resume" => resume = true,
+            "--coverage-guided" => start_points = StartPoints::CoverageGuided,
+            "--perfect" => perfect = true,
+            "--compact" => compact = true,
             "--out-dir" => {
                 i += 1;
                 if i >= args.len() {
@@ -57,6 +118,40 @@ fn parse_args() -> Result<Args, String> {
                 }
                 out_dir = Some(PathBuf::from(&args[i]));
             }
+            "--created-at" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--created-at requires a value".to_string());
+                }
+                created_at = Some(
+                    args[i]
+                        .parse()
+                        .map_err(|_| format!("Invalid value for --created-at: {}", args[i]))?,
+                );
+            }
+            "--work-dir" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--work-dir requires a value".to_string());
+                }
+                work_dir = Some(PathBuf::from(&args[i]));
+            }
+            option @ ("--chain-length" | "--chains" | "--tables") => {
+                i += 1;
+                if i >= args.len() {
+                    return Err(format!("{} requires a value", option));
+                }
+                let value: u32 = args[i]
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("Invalid value for {}: {}", option, args[i]))?;
+                match option {
+                    "--chain-length" => params.chain_length = value,
+                    "--chains" => params.chains_per_table = value,
+                    _ => params.num_tables = value,
+                }
+            }
             "--help" | "-h" => {
                 print_usage(&args[0]);
                 std::process::exit(0);
@@ -77,68 +172,85 @@ fn parse_args() -> Result<Args, String> {
 
     let consumption = consumption.ok_or("Missing consumption argument")?;
 
+    if no_sort && end_hash_keys {
+        return Err("--end-hash-keys cannot be used with --no-sort".to_string());
+    }
+    if no_sort && perfect {
+        return Err("--perfect cannot be used with --no-sort".to_string());
+    }
+    if no_sort && compact {
+        return Err("--compact cannot be used with --no-sort".to_string());
+    }
+    if compact && end_hash_keys {
+        return Err("--compact already stores the end hashes; drop --end-hash-keys".to_string());
+    }
+
     Ok(Args {
         consumption,
         no_sort,
+        end_hash_keys,
         out_dir,
+        work_dir,
+        resume,
+        start_points,
+        perfect,
+        compact,
+        created_at,
+        params,
     })
 }
 
-fn generate_table_entries(
-    consumption: i32,
-    table_id: u32,
-    no_sort: bool,
-    total_tables: u32,
-) -> Vec<gen7seed_rainbow::ChainEntry> {
-    println!(
-        "Generating rainbow table {} for consumption {}...",
-        table_id, consumption
-    );
-
-    let start = Instant::now();
+/// Default checkpoint directory: `{out_dir}/{consumption}_checkpoints`
+fn default_work_dir(out_dir: &Path, consumption: i32) -> PathBuf {
+    out_dir.join(format!("{}_checkpoints", consumption))
+}
 
-    let progress_callback = |current: u32, total: u32| {
-        if current.is_multiple_of(100000) || current == total {
-            let progress = if total > 0 {
-                (current as f64 / total as f64) * 100.0
-            } else {
-                100.0
-            };
-            print!(
-                "\r[Table {}] Progress: {:.2}% ({}/{})",
-                table_id, progress, current, total
+/// Print a generation event
+fn print_event(event: &CheckpointEvent, num_tables: u32) {
+    match event {
+        CheckpointEvent::Resumed { table_id, range } => {
+            println!(
+                "[Table {}] Resumed chains {}..{} from checkpoint",
+                table_id, range.start, range.end
             );
-            io::stdout().flush().unwrap();
         }
-    };
-
-    let mut entries = generate_table(
-        consumption,
-        GenerateOptions::default()
-            .with_table_id(table_id)
-            .with_progress(progress_callback),
-    );
-
-    println!();
-
-    let gen_elapsed = start.elapsed();
-    println!(
-        "Generated {} entries in {:.2} seconds",
-        entries.len(),
-        gen_elapsed.as_secs_f64()
-    );
-
-    if !no_sort {
-        println!("Sorting...");
-        let sort_start = Instant::now();
-        sort_table_parallel(&mut entries, consumption);
-        let sort_elapsed = sort_start.elapsed();
-        println!("Sorted in {:.2} seconds.", sort_elapsed.as_secs_f64());
+        CheckpointEvent::Generating {
+            table_id,
+            done,
+            total,
+        } => {
+            if done.is_multiple_of(100000) || done == total {
+                let progress = if *total > 0 {
+                    (*done as f64 / *total as f64) * 100.0
+                } else {
+                    100.0
+                };
+                print!(
+                    "\r[Table {}] Progress: {:.2}% ({}/{})",
+                    table_id, progress, done, total
+                );
+                io::stdout().flush().unwrap();
+            }
+        }
+        CheckpointEvent::Sorting { table_id } => {
+            println!();
+            println!("[Table {}] Sorting...", table_id);
+        }
+        CheckpointEvent::Perfected { table_id, stats } => {
+            println!(
+                "[Table {}] Merge rate: {:.2}% ({} of {} chains merged, {} replacement rounds)",
+                table_id,
+                stats.merge_rate() * 100.0,
+                stats.merged,
+                stats.generated,
+                stats.rounds
+            );
+        }
+        CheckpointEvent::TableDone { table_id } => {
+            println!("[Table {}/{}] Done.\n", table_id + 1, num_tables);
+        }
+        CheckpointEvent::Writing => println!("Writing table file..."),
     }
-
-    println!("[Table {}/{}] Done.\n", table_id + 1, total_tables);
-
-    entries
 }
 
 fn main() {
@@ -159,34 +271,101 @@ fn main() {
         );
     }
 
+    let resolved_dir = args.out_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+    let work_dir = args
+        .work_dir
+        .clone()
+        .unwrap_or_else(|| default_work_dir(&resolved_dir, args.consumption));
+    let output_path = get_single_table_path(&resolved_dir, args.consumption);
+
+    // First Ctrl+C stops after the current chunk; a second one exits immediately
+    let token = CancellationToken::new();
+    let handler_token = token.clone();
+    if let Err(e) = ctrlc::set_handler(move || {
+        if handler_token.is_cancelled() {
+            std::process::exit(130);
+        }
+        handler_token.cancel();
+        eprintln!("\nStopping after the current chunk... (press Ctrl+C again to abort)");
+    }) {
+        eprintln!("Warning: Failed to set Ctrl+C handler: {}", e);
+    }
+
     #[cfg(feature = "multi-sfmt")]
     println!("Using Multi-SFMT (16-parallel SIMD) + rayon for maximum speed.");
     #[cfg(not(feature = "multi-sfmt"))]
     println!("Using parallel processing for faster generation.");
-    println!("This will take a long time. Press Ctrl+C to cancel.");
+    println!("This will take a long time. Press Ctrl+C to stop; resume later with --resume.");
+    println!("Checkpoints: {}", work_dir.display());
     println!();
 
     let start = Instant::now();
 
-    let resolved_dir = args.out_dir.clone().unwrap_or_else(|| PathBuf::from("."));
-
+    let params = args.params;
     println!(
         "Generating all {} tables for consumption {}...",
-        NUM_TABLES, args.consumption
+        params.num_tables, args.consumption
     );
+    if params != TableParams::default() {
+        println!(
+            "Table parameters: chain length {}, {} chains per table",
+            params.chain_length, params.chains_per_table
+        );
+    }
+    if args.start_points == StartPoints::CoverageGuided {
+        println!("Start points: coverage-guided (seeds not covered by earlier chains)");
+    }
+    if args.perfect {
+        println!("Perfect tables: merged chains are replaced after each table.");
+    }
+    if args.compact {
+        println!("Output format: compact (bit-packed start seeds and end hashes)");
+    }
     println!();
 
-    let mut tables = Vec::with_capacity(NUM_TABLES as usize);
-    for table_id in 0..NUM_TABLES {
-        let entries = generate_table_entries(args.consumption, table_id, args.no_sort, NUM_TABLES);
-        tables.push(entries);
+    let mut options = CheckpointOptions::new(&work_dir)
+        .with_params(params)
+        .with_start_points(args.start_points)
+        .with_perfect(args.perfect)
+        .with_resume(args.resume)
+        .with_sort(!args.no_sort)
+        .with_end_hashes(args.end_hash_keys)
+        .with_compact(args.compact)
+        .with_cancel(token)
+        .with_progress(move |event: &CheckpointEvent| print_event(event, params.num_tables));
+    if let Some(created_at) = args.created_at {
+        options = options.with_created_at(created_at);
     }
 
-    let output_path = get_single_table_path(&resolved_dir, args.consumption);
-    println!("Saving to {}...", output_path.display());
-    if let Err(e) = save_single_table(&output_path, args.consumption, &tables, !args.no_sort) {
-        eprintln!("Error saving table file: {}", e);
-        std::process::exit(1);
+    let summary = match generate_tables_with_checkpoints(args.consumption, &output_path, options) {
+        Ok(summary) => summary,
+        Err(CheckpointError::Cancelled) => {
+            eprintln!();
+            eprintln!(
+                "Cancelled. Finished chunks are kept in {}.",
+                work_dir.display()
+            );
+            eprintln!("Run again with --resume to continue.");
+            std::process::exit(130);
+        }
+        Err(e @ CheckpointError::ExistingCheckpoints(_)) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Use --resume to continue the previous run.");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!();
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    println!("Saved to {}", output_path.display());
+    if summary.tables_resumed > 0 || summary.chunks_resumed > 0 {
+        println!(
+            "Resumed {} tables and {} chunks from checkpoints.",
+            summary.tables_resumed, summary.chunks_resumed
+        );
     }
 
     let file_size = std::fs::metadata(&output_path)
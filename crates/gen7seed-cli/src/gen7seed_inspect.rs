@@ -0,0 +1,433 @@
+//! Table and missing seeds file inspector
+//!
+//! Usage: gen7seed_inspect <FILE> [options]
+//!
+//! Prints the decoded header of a table (`.g7rt`) or missing seeds (`.g7ms`)
+//! file and checks its size. For tables, the end keys of every table are
+//! summarized (min, max, duplicates) and chosen chains can be traced.
+//!
+//! Options:
+//!   --table <PATH>      Check that the missing seeds file is bound to this table
+//!   --missing <PATH>    Check that this missing seeds file is bound to the table
+//!   --chain <T>:<I>     Trace chain I of table T (repeatable)
+//!   --no-stats          Skip the end key statistics (recomputed if not stored)
+//!   --json              Print the report as JSON
+//!   --help, -h          Show help
+//!
+//! Example:
+//!   gen7seed_inspect 417.g7rt
+//!   gen7seed_inspect 417.g7rt --chain 0:12 --chain 3:0 --json
+//!   gen7seed_inspect 417.g7ms --table 417.g7rt
+
+use gen7seed_rainbow::constants::{
+    FILE_HEADER_SIZE, FLAG_COVERAGE_GUIDED, FLAG_END_HASH_KEYS, FLAG_FULL_HASH_SORT, FLAG_PERFECT,
+    FLAG_SORTED,
+};
+use gen7seed_rainbow::domain::missing_format::expected_missing_file_size;
+use gen7seed_rainbow::{
+    EndHashKey, MappedSingleTable, MissingFormatError, MissingSeeds, MissingSeedsHeader,
+    MissingSeedsView, StartPoints, TableFormatError, TableHeader, ValidationOptions, end_key_stats,
+    trace_chain,
+};
+use serde_json::{Map, Value, json};
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Seeds printed per line when tracing a chain
+const SEEDS_PER_LINE: usize = 8;
+
+struct Args {
+    path: PathBuf,
+    table: Option<PathBuf>,
+    missing: Option<PathBuf>,
+    chains: Vec<(u32, usize)>,
+    stats: bool,
+    json: bool,
+}
+
+fn print_usage(program: &str) {
+    eprintln!("Usage: {} <FILE> [options]", program);
+    eprintln!();
+    eprintln!("Prints the header of a table (.g7rt) or missing seeds (.g7ms) file.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --table <PATH>      Check that the missing seeds file is bound to this table");
+    eprintln!("  --missing <PATH>    Check that this missing seeds file is bound to the table");
+    eprintln!("  --chain <T>:<I>     Trace chain I of table T (repeatable)");
+    eprintln!("  --no-stats          Skip the end key statistics (recomputed if not stored)");
+    eprintln!("  --json              Print the report as JSON");
+    eprintln!("  --help, -h          Show this help message");
+}
+
+fn parse_chain(value: &str) -> Option<(u32, usize)> {
+    let (table_id, index) = value.split_once(':')?;
+    Some((table_id.parse().ok()?, index.parse().ok()?))
+}
+
+fn parse_args() -> Result<Args, String> {
+    let args: Vec<String> = env::args().collect();
+
+    let mut path: Option<PathBuf> = None;
+    let mut table: Option<PathBuf> = None;
+    let mut missing: Option<PathBuf> = None;
+    let mut chains = Vec::new();
+    let mut stats = true;
+    let mut json = false;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            option @ ("--table" | "--missing" | "--chain") => {
+                i += 1;
+                if i >= args.len() {
+                    return Err(format!("{} requires a value", option));
+                }
+                match option {
+                    "--table" => table = Some(PathBuf::from(&args[i])),
+                    "--missing" => missing = Some(PathBuf::from(&args[i])),
+                    _ => chains.push(
+                        parse_chain(&args[i])
+                            .ok_or_else(|| format!("Invalid value for --chain: {}", args[i]))?,
+                    ),
+                }
+            }
+            "--no-stats" => stats = false,
+            "--json" => json = true,
+            "--help" | "-h" => {
+                print_usage(&args[0]);
+                std::process::exit(0);
+            }
+            arg if !arg.starts_with('-') => {
+                if path.is_some() {
+                    return Err(format!("Unexpected argument: {}", arg));
+                }
+                path = Some(PathBuf::from(arg));
+            }
+            _ => return Err(format!("Unknown option: {}", args[i])),
+        }
+        i += 1;
+    }
+
+    Ok(Args {
+        path: path.ok_or("Missing file argument")?,
+        table,
+        missing,
+        chains,
+        stats,
+        json,
+    })
+}
+
+fn hex(value: u64) -> Value {
+    json!(format!("{:016x}", value))
+}
+
+fn flag_names(flags: u32) -> Vec<&'static str> {
+    [
+        (FLAG_SORTED, "sorted"),
+        (FLAG_FULL_HASH_SORT, "full_hash_sort"),
+        (FLAG_END_HASH_KEYS, "end_hash_keys"),
+        (FLAG_COVERAGE_GUIDED, "coverage_guided"),
+        (FLAG_PERFECT, "perfect"),
+    ]
+    .into_iter()
+    .filter(|&(flag, _)| flags & flag != 0)
+    .map(|(_, name)| name)
+    .collect()
+}
+
+fn table_header_report(header: &TableHeader) -> Value {
+    let format = if header.is_compact() {
+        "compact"
+    } else if header.is_sectioned() {
+        "sectioned"
+    } else {
+        "chain entries"
+    };
+    let sort_key = match header.end_hash_key() {
+        EndHashKey::Full => "full end hash",
+        EndHashKey::Truncated => "lower 32 bits of end hash",
+    };
+    let start_points = match header.start_points() {
+        StartPoints::Sequential => "sequential",
+        StartPoints::CoverageGuided => "coverage guided",
+    };
+
+    json!({
+        "version": header.version,
+        "format": format,
+        "consumption": header.consumption,
+        "chain_length": header.chain_length,
+        "chains_per_table": header.chains_per_table,
+        "num_tables": header.num_tables,
+        "flags": format!("{:#x}", header.flags),
+        "flag_names": flag_names(header.flags),
+        "sort_key": sort_key,
+        "start_points": start_points,
+        "created_at": header.created_at,
+        "checksum": header.checksum.map(hex),
+        "chains_checksum": header.chains_checksum.map(hex),
+    })
+}
+
+fn missing_header_report(header: &MissingSeedsHeader) -> Value {
+    json!({
+        "version": header.version,
+        "format": if header.is_compressed() { "compressed" } else { "raw" },
+        "consumption": header.consumption,
+        "chain_length": header.chain_length,
+        "chains_per_table": header.chains_per_table,
+        "num_tables": header.num_tables,
+        "missing_count": header.missing_count,
+        "source_checksum": hex(header.source_checksum),
+        "created_at": header.created_at,
+        "checksum": header.checksum.map(hex),
+    })
+}
+
+fn size_report(expected: Option<u64>, actual: u64) -> Value {
+    json!({
+        "expected": expected,
+        "actual": actual,
+        "ok": expected == Some(actual),
+    })
+}
+
+fn binding_report(path: &Path, result: Result<(), String>) -> Value {
+    json!({
+        "path": path.display().to_string(),
+        "bound": result.is_ok(),
+        "error": result.err(),
+    })
+}
+
+/// Check whether a missing seeds header is bound to the table at `table_path`
+fn check_binding(header: &MissingSeedsHeader, table_path: &Path) -> Result<(), String> {
+    let table = MappedSingleTable::open(table_path, &ValidationOptions::default())
+        .map_err(|e| e.to_string())?;
+    header
+        .verify_source(&table.bound_header())
+        .map_err(|e| e.to_string())
+}
+
+fn inspect_table(args: &Args, header: &TableHeader, file_size: u64) -> Result<Value, String> {
+    let mut report = Map::new();
+    report.insert("file".into(), json!("table"));
+    report.insert("header".into(), table_header_report(header));
+
+    let table = match MappedSingleTable::open(&args.path, &ValidationOptions::default()) {
+        Ok(table) => table,
+        Err(TableFormatError::InvalidFileSize { expected, found }) => {
+            report.insert("size".into(), size_report(Some(expected), found));
+            return Ok(Value::Object(report));
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    report.insert(
+        "size".into(),
+        size_report(Some(table.expected_size()), file_size),
+    );
+
+    if header.is_sectioned() {
+        let sections: Vec<Value> = table
+            .sections()
+            .iter()
+            .map(|entry| {
+                json!({
+                    "kind": entry.kind.to_string(),
+                    "version": entry.version,
+                    "offset": entry.offset,
+                    "length": entry.length,
+                    "checksum": hex(entry.checksum),
+                })
+            })
+            .collect();
+        report.insert("sections".into(), json!(sections));
+
+        let embedded = match MissingSeedsView::embedded(&table) {
+            Ok(Some(missing)) => json!({ "missing_count": missing.len(), "bound": true }),
+            Ok(None) => Value::Null,
+            Err(e) => json!({ "bound": false, "error": e.to_string() }),
+        };
+        report.insert("embedded_missing_seeds".into(), embedded);
+    }
+
+    if let Some(missing_path) = &args.missing {
+        let result = MissingSeeds::open(missing_path, None)
+            .map_err(|e| e.to_string())
+            .and_then(|missing| {
+                missing
+                    .verify_source(&table.bound_header())
+                    .map_err(|e| e.to_string())
+            });
+        report.insert("missing_seeds".into(), binding_report(missing_path, result));
+    }
+
+    if args.stats {
+        let stats: Vec<Value> = table
+            .table_refs()
+            .iter()
+            .enumerate()
+            .map(|(table_id, table_ref)| {
+                let stats = end_key_stats(table_ref, header.consumption);
+                json!({
+                    "table_id": table_id,
+                    "chains": stats.chains,
+                    "min_end_key": stats.min.map(hex),
+                    "max_end_key": stats.max.map(hex),
+                    "duplicate_end_keys": stats.duplicates,
+                    "sorted": stats.sorted,
+                })
+            })
+            .collect();
+        report.insert("tables".into(), json!(stats));
+    }
+
+    let mut traces = Vec::with_capacity(args.chains.len());
+    for &(table_id, index) in &args.chains {
+        let trace = table
+            .table_ref(table_id)
+            .and_then(|table_ref| trace_chain(&table_ref, header.consumption, table_id, index))
+            .ok_or_else(|| format!("Chain {}:{} does not exist", table_id, index))?;
+        traces.push(json!({
+            "table_id": trace.table_id,
+            "index": trace.index,
+            "start_seed": trace.start_seed(),
+            "end_seed": trace.end_seed(),
+            "end_hash": hex(trace.end_hash),
+            "verified": trace.verified,
+            "seeds": trace.seeds,
+        }));
+    }
+    if !traces.is_empty() {
+        report.insert("chains".into(), json!(traces));
+    }
+
+    Ok(Value::Object(report))
+}
+
+fn inspect_missing(
+    args: &Args,
+    header: &MissingSeedsHeader,
+    file_size: u64,
+) -> Result<Value, String> {
+    let mut report = Map::new();
+    report.insert("file".into(), json!("missing seeds"));
+    report.insert("header".into(), missing_header_report(header));
+
+    let expected = if header.is_compressed() {
+        // Compressed files are sized by their block directory
+        match MissingSeeds::open(&args.path, None) {
+            Ok(_) => Some(file_size),
+            Err(MissingFormatError::InvalidBlocks) => None,
+            Err(e) => return Err(e.to_string()),
+        }
+    } else {
+        Some(expected_missing_file_size(header))
+    };
+    report.insert("size".into(), size_report(expected, file_size));
+
+    if let Some(table_path) = &args.table {
+        report.insert(
+            "table".into(),
+            binding_report(table_path, check_binding(header, table_path)),
+        );
+    }
+
+    Ok(Value::Object(report))
+}
+
+fn inspect(args: &Args) -> Result<Value, String> {
+    let mut file = File::open(&args.path).map_err(|e| e.to_string())?;
+    let file_size = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut header_buf = [0u8; FILE_HEADER_SIZE];
+    file.read_exact(&mut header_buf)
+        .map_err(|_| format!("File is smaller than a {}-byte header", FILE_HEADER_SIZE))?;
+
+    match TableHeader::from_bytes(&header_buf) {
+        Ok(header) => inspect_table(args, &header, file_size),
+        Err(TableFormatError::InvalidMagic) => match MissingSeedsHeader::from_bytes(&header_buf) {
+            Ok(header) => inspect_missing(args, &header, file_size),
+            Err(MissingFormatError::InvalidMagic) => {
+                Err("Not a table or missing seeds file".to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        },
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Print a report as indented `key: value` lines
+fn print_human(value: &Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::Object(_) => {
+                        println!("{}{}:", pad, key);
+                        print_human(value, indent + 2);
+                    }
+                    Value::Array(items) if items.iter().any(Value::is_object) => {
+                        println!("{}{}:", pad, key);
+                        for item in items {
+                            println!("{}  -", pad);
+                            print_human(item, indent + 4);
+                        }
+                    }
+                    Value::Array(items) if items.len() > SEEDS_PER_LINE => {
+                        println!("{}{}:", pad, key);
+                        for (line, chunk) in items.chunks(SEEDS_PER_LINE).enumerate() {
+                            let values: Vec<String> = chunk.iter().map(scalar).collect();
+                            println!(
+                                "{}  [{:>5}] {}",
+                                pad,
+                                line * SEEDS_PER_LINE,
+                                values.join(" ")
+                            );
+                        }
+                    }
+                    _ => println!("{}{}: {}", pad, key, scalar(value)),
+                }
+            }
+        }
+        _ => println!("{}{}", pad, scalar(value)),
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(scalar).collect::<Vec<_>>().join(", "),
+        _ => value.to_string(),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!();
+            print_usage(&env::args().next().unwrap_or_default());
+            std::process::exit(1);
+        }
+    };
+
+    let report = match inspect(&args) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Error: {}: {}", args.path.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        print_human(&report, 0);
+    }
+}
@@ -0,0 +1,209 @@
+//! Missing seeds extraction CLI
+//!
+//! Usage:
+//!   gen7seed_missing <consumption> [options]
+//!
+//! Options:
+//!   --table-dir <PATH>  Table directory (default: current directory)
+//!   --out-dir <PATH>    Output directory (default: the table directory)
+//!   --uncompressed      Write the uncompressed format (plain seed list)
+//!   --help, -h          Show help
+//!
+//! Example:
+//!   gen7seed_missing 417 --table-dir .\tables
+//!
+//! Computes every chain of {consumption}.g7rt, marking the seeds they reach
+//! in a 512 MB bitmap, and saves the seeds no chain reaches to
+//! {consumption}.g7ms, bound to the table.
+
+use gen7seed_rainbow::infra::missing_seeds_io::{
+    get_missing_seeds_path, save_missing_seeds, save_missing_seeds_compressed,
+};
+use gen7seed_rainbow::infra::table_io::get_single_table_path;
+use gen7seed_rainbow::{
+    MappedSingleTable, TableFormatError, ValidationOptions,
+    extract_missing_seeds_multi_table_with_header,
+};
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Memory used by the seed bitmap (one bit per 2^32 seeds)
+const BITMAP_MB: u64 = (1u64 << 32) / 8 / (1024 * 1024);
+
+struct Args {
+    consumption: i32,
+    table_dir: PathBuf,
+    out_dir: Option<PathBuf>,
+    compressed: bool,
+}
+
+fn print_usage(program: &str) {
+    eprintln!("Usage: {} <consumption> [options]", program);
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --table-dir <PATH>  Table directory (default: current directory)");
+    eprintln!("  --out-dir <PATH>    Output directory (default: the table directory)");
+    eprintln!("  --uncompressed      Write the uncompressed format (plain seed list)");
+    eprintln!("  --help, -h          Show this help message");
+    eprintln!();
+    eprintln!(
+        "Extraction keeps a bitmap of all 2^32 seeds in memory ({} MB).",
+        BITMAP_MB
+    );
+}
+
+fn parse_args() -> Result<Args, String> {
+    let args: Vec<String> = env::args().collect();
+
+    let mut consumption: Option<i32> = None;
+    let mut table_dir = PathBuf::from(".");
+    let mut out_dir: Option<PathBuf> = None;
+    let mut compressed = true;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            option @ ("--table-dir" | "--out-dir") => {
+                i += 1;
+                if i >= args.len() {
+                    return Err(format!("{} requires a value", option));
+                }
+                let dir = PathBuf::from(&args[i]);
+                if option == "--table-dir" {
+                    table_dir = dir;
+                } else {
+                    out_dir = Some(dir);
+                }
+            }
+            "--uncompressed" => compressed = false,
+            "--help" | "-h" => {
+                print_usage(&args[0]);
+                std::process::exit(0);
+            }
+            arg if !arg.starts_with('-') && consumption.is_none() => {
+                consumption = Some(
+                    arg.parse()
+                        .map_err(|_| format!("Invalid consumption value: {}", arg))?,
+                );
+            }
+            _ => return Err(format!("Unknown option: {}", args[i])),
+        }
+        i += 1;
+    }
+
+    Ok(Args {
+        consumption: consumption.ok_or("Missing consumption argument")?,
+        table_dir,
+        out_dir,
+        compressed,
+    })
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let table_path = get_single_table_path(&args.table_dir, args.consumption);
+    println!("Loading table from {}...", table_path.display());
+    let options = ValidationOptions {
+        expected_consumption: Some(args.consumption),
+        ..ValidationOptions::default()
+    };
+    let table = MappedSingleTable::open(&table_path, &options).map_err(|e| {
+        format!(
+            "{}: {}\nGenerate it with: gen7seed_create {}",
+            table_path.display(),
+            e,
+            args.consumption
+        )
+    })?;
+    let header = table.bound_header();
+    if header.is_compact() {
+        return Err(format!(
+            "{}: {}",
+            table_path.display(),
+            TableFormatError::CompactNotSupported
+        ));
+    }
+
+    let tables: Vec<_> = (0..table.num_tables())
+        .filter_map(|table_id| Some((table.entries(table_id)?, table_id)))
+        .collect();
+    println!(
+        "Loaded {} tables ({} chains each, chain length {})",
+        tables.len(),
+        header.chains_per_table,
+        header.chain_length
+    );
+
+    println!(
+        "Note: Building the seed bitmap uses {} MB of memory.",
+        BITMAP_MB
+    );
+    println!("Building seed bitmap...");
+    let (_, result) = extract_missing_seeds_multi_table_with_header(
+        &tables,
+        &header,
+        |_, table_id, current, total| {
+            let progress = if total > 0 {
+                (current as f64 / total as f64) * 100.0
+            } else {
+                100.0
+            };
+            print!(
+                "\r[Table {}] Progress: {:.2}% ({}/{})",
+                table_id, progress, current, total
+            );
+            if current == total {
+                println!();
+            }
+            io::stdout().flush().unwrap();
+        },
+    );
+
+    println!(
+        "Reachable: {} ({:.4}%)",
+        result.reachable_count,
+        result.coverage * 100.0
+    );
+    println!(
+        "Missing:   {} ({:.4}%)",
+        result.missing_count,
+        (1.0 - result.coverage) * 100.0
+    );
+
+    let out_dir = args.out_dir.as_ref().unwrap_or(&args.table_dir);
+    let output_path = get_missing_seeds_path(out_dir, args.consumption);
+    let saved = if args.compressed {
+        save_missing_seeds_compressed(&output_path, &header, &result.missing_seeds)
+    } else {
+        save_missing_seeds(&output_path, &header, &result.missing_seeds)
+    };
+    saved.map_err(|e| format!("{}: {}", output_path.display(), e))?;
+    let file_size = std::fs::metadata(&output_path).map_or(0, |m| m.len());
+    println!(
+        "Saved {} missing seeds to {} ({} bytes)",
+        result.missing_count,
+        output_path.display(),
+        file_size
+    );
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!();
+            print_usage(&env::args().next().unwrap_or_default());
+            std::process::exit(1);
+        }
+    };
+
+    let start = Instant::now();
+    if let Err(e) = run(&args) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+    println!("Done in {:.2} seconds.", start.elapsed().as_secs_f64());
+}
@@ -1,39 +1,78 @@
 //! Initial seed search CLI
 //!
-//! Usage: gen7seed_search <consumption> [--table-dir <PATH>]
-//! Then enter 8 needle values (0-16) separated by spaces.
+//! Usage: gen7seed_search <consumption> [options]
+//! Then enter 8 needle values (0-16) separated by spaces, or pass them with
+//! `--needles` / `--batch` to search without prompting.
+//!
+//! Options:
+//!   --table-dir <PATH>     Table directory (default: current directory)
+//!   --verify               Check the table and missing seeds checksums before searching
+//!   --tolerant             Retry with corrected readings if nothing is found
+//!   --max-corrections <N>  Maximum number of misread needles (implies --tolerant)
+//!   --max-delta <D>        Maximum misreading per needle (implies --tolerant)
+//!   --brute-force          Scan all 2^32 seeds without a table (any consumption)
+//!   --stop-on-hit          Stop the brute-force scan at the first matching seed
+//!   --needles <VALUES>     Search a single reading and exit
+//!   --batch <FILE|->       Search one reading per line of a file or standard input
+//!   --format <FORMAT>      Output of --needles/--batch: text, json or csv
+//!   --help, -h             Show help
 //!
 //! Example:
 //!   gen7seed_search 417
 //!   gen7seed_search 417 --table-dir .\tables
-//!   Enter needle values (8 values, 0-16, space-separated): 5 12 3 8 14 1 9 6
+//!   gen7seed_search 417 --verify
+//!   gen7seed_search 417 --tolerant
+//!   gen7seed_search 420 --brute-force --stop-on-hit
+//!   Enter needle values (8+ values, 0-16, space-separated): 5 12 3 8 14 1 9 6
+//!   gen7seed_search 417 --needles "5 12 3 8 14 1 9 6"
+//!   gen7seed_search 417 --batch readings.txt --format csv > results.csv
+//!
+//! Values after the first 8 are the needles read next. They are used to rule out
+//! candidates when more than one initial seed matches the first 8.
+//!
+//! This tool searches across all tables in the file in parallel, walking 16
+//! tables at a time with multi-sfmt.
+//! If the table file stores its missing seeds (sectioned format) or
+//! `{consumption}.g7ms` exists in the table directory, seeds not covered by
+//! the tables are searched by brute force when the table search finds nothing.
 //!
-//! This tool searches across all 16 tables using multi-sfmt parallel search.
+//! With `--brute-force`, no table is loaded and every seed is checked instead.
+//! This takes much longer, but works for consumption values without a table.
+//! Press Ctrl+C to cancel a running scan and return to the prompt.
+//!
+//! With `--batch`, each non-empty line (lines starting with `#` are skipped)
+//! is searched and written as one JSON object or as CSV rows, with status
+//! messages on stderr. The exit code is 0 if a seed was found for every
+//! reading, 1 if one was not found, 2 for invalid arguments or readings and
+//! 3 if the table cannot be loaded.
 
 use gen7seed_rainbow::ValidationOptions;
-use gen7seed_rainbow::constants::{NEEDLE_COUNT, SUPPORTED_CONSUMPTIONS};
+use gen7seed_rainbow::constants::{NEEDLE_COUNT, SEED_SPACE, SUPPORTED_CONSUMPTIONS};
+use gen7seed_rainbow::domain::checksum::ChecksumStatus;
+use gen7seed_rainbow::domain::needle::{MAX_NEEDLE_CANDIDATES, MAX_NEEDLE_DELTA};
 use gen7seed_rainbow::domain::table_format::TableFormatError;
+use gen7seed_rainbow::infra::missing_seeds_io::{
+    MissingSeeds, MissingSeedsView, get_missing_seeds_path, verify_missing_seeds_file,
+};
 use gen7seed_rainbow::infra::table_io::get_single_table_path;
+use gen7seed_rainbow::{
+    BruteForceOptions, BruteForceProgress, BruteForceStatus, CancellationToken, NeedleCorrection,
+    NeedleTolerance, TableRef, TolerantHit, brute_force_search, filter_by_extra_needles,
+    search_missing_seeds, search_missing_seeds_tolerant, search_seeds_multi_table,
+    search_seeds_tolerant_multi_table,
+};
+use serde_json::{Value, json};
+use std::collections::HashSet;
 use std::env;
-use std::io::{self, Write};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use gen7seed_rainbow::MappedSingleTable;
 
-// Binary search imports
-#[cfg(feature = "multi-sfmt")]
-use gen7seed_rainbow::search_seeds_x16;
-
-#[cfg(feature = "multi-sfmt")]
-use gen7seed_rainbow::ChainEntry;
-
-#[cfg(not(feature = "multi-sfmt"))]
-use gen7seed_rainbow::search_seeds;
-
-#[cfg(not(feature = "multi-sfmt"))]
-use gen7seed_rainbow::constants::NUM_TABLES;
-
 fn format_table_error(path: &Path, err: TableFormatError) -> String {
     match err {
         TableFormatError::InvalidMagic => format!(
@@ -60,6 +99,10 @@ fn format_table_error(path: &Path, err: TableFormatError) -> String {
             "Incompatible table: table count mismatch (expected {}, found {}).\nPlease regenerate the table.",
             expected, found
         ),
+        TableFormatError::InvalidParams(params) => format!(
+            "Invalid table parameters (chain length {}, {} chains per table, {} tables).\nPlease regenerate the table.",
+            params.chain_length, params.chains_per_table, params.num_tables
+        ),
         TableFormatError::TableNotSorted => {
             "Table is not sorted. Search requires a sorted table.\nPlease regenerate the table (sorting is done automatically)."
                 .to_string()
@@ -68,21 +111,130 @@ fn format_table_error(path: &Path, err: TableFormatError) -> String {
             "Invalid file size: expected {} bytes, found {} bytes.",
             expected, found
         ),
+        TableFormatError::ChecksumMismatch { expected, found } => format!(
+            "Checksum mismatch: '{}' is corrupted (expected {:016x}, found {:016x}).\nPlease download or regenerate the table again.",
+            path.display(),
+            expected,
+            found
+        ),
+        TableFormatError::InvalidCompactTable { table_id } => format!(
+            "Invalid compact table file: table {} is corrupted.\nPlease regenerate the table.",
+            table_id
+        ),
+        TableFormatError::CompactNotSupported => {
+            "Compact tables cannot be loaded as chain entries.".to_string()
+        }
+        TableFormatError::InvalidSectionDirectory => format!(
+            "Invalid table file: the section directory of '{}' is corrupted.\nPlease download or regenerate the table again.",
+            path.display()
+        ),
+        TableFormatError::MissingSection(kind) => format!(
+            "Invalid table file: '{}' has no {} section.\nPlease regenerate the table.",
+            path.display(),
+            kind
+        ),
+        TableFormatError::UnsupportedSection { kind, version } => format!(
+            "Unsupported {} section (version {}).\nPlease regenerate the table.",
+            kind, version
+        ),
+        TableFormatError::SectionChecksumMismatch {
+            kind,
+            expected,
+            found,
+        } => format!(
+            "Checksum mismatch: the {} section of '{}' is corrupted (expected {:016x}, found {:016x}).\nPlease download or regenerate the table again.",
+            kind,
+            path.display(),
+            expected,
+            found
+        ),
         TableFormatError::Io(msg) => format!("I/O error: {}", msg),
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+/// Exit code when an initial seed is found (for every query in batch mode)
+const EXIT_FOUND: i32 = 0;
+/// Exit code when no initial seed is found (for any query in batch mode)
+const EXIT_NOT_FOUND: i32 = 1;
+/// Exit code for invalid arguments or needle values
+const EXIT_INVALID_INPUT: i32 = 2;
+/// Exit code when the table cannot be loaded or fails verification
+const EXIT_TABLE_ERROR: i32 = 3;
 
-    if args.len() < 2 || args.len() > 4 {
-        eprintln!("Usage: {} <consumption> [--table-dir <PATH>]", args[0]);
-        eprintln!("Supported consumption values: {:?}", SUPPORTED_CONSUMPTIONS);
-        std::process::exit(1);
-    }
+/// Non-interactive queries
+enum Queries {
+    /// A single reading given on the command line
+    Needles(String),
+    /// One reading per line of a file (`-` = standard input)
+    Batch(PathBuf),
+}
+
+/// Output format of non-interactive queries
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+struct Args {
+    consumption: i32,
+    table_dir: Option<PathBuf>,
+    verify: bool,
+    tolerance: Option<NeedleTolerance>,
+    brute_force: bool,
+    stop_on_hit: bool,
+    queries: Option<Queries>,
+    format: OutputFormat,
+}
+
+fn print_usage(program: &str) {
+    eprintln!("Usage: {} <consumption> [options]", program);
+    eprintln!();
+    eprintln!("Arguments:");
+    eprintln!("  <consumption>          Number of RNG consumptions (e.g., 417)");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --table-dir <PATH>     Table directory (default: current directory)");
+    eprintln!(
+        "  --verify               Check the table and missing seeds checksums before searching"
+    );
+    eprintln!("  --tolerant             Retry with corrected readings if nothing is found");
+    eprintln!("                         (default: one needle off by ±1)");
+    eprintln!("  --max-corrections <N>  Maximum number of misread needles (implies --tolerant)");
+    eprintln!(
+        "  --max-delta <D>        Maximum misreading per needle, {} = unknown (implies --tolerant)",
+        MAX_NEEDLE_DELTA
+    );
+    eprintln!("  --brute-force          Scan all 2^32 seeds without a table (any consumption)");
+    eprintln!("  --stop-on-hit          Stop the brute-force scan at the first matching seed");
+    eprintln!(
+        "  --needles <VALUES>     Search a single reading and exit (e.g. \"5 12 3 8 14 1 9 6\")"
+    );
+    eprintln!("  --batch <FILE|->       Search one reading per line of a file or standard input");
+    eprintln!("  --format <FORMAT>      Output of --needles/--batch: text, json or csv");
+    eprintln!("                         (default: text for --needles, json for --batch)");
+    eprintln!("  --help, -h             Show this help message");
+    eprintln!();
+    eprintln!(
+        "Exit codes: {} found, {} not found, {} invalid input, {} table error",
+        EXIT_FOUND, EXIT_NOT_FOUND, EXIT_INVALID_INPUT, EXIT_TABLE_ERROR
+    );
+    eprintln!();
+    eprintln!("Supported consumption values: {:?}", SUPPORTED_CONSUMPTIONS);
+}
+
+fn parse_args() -> Result<Args, String> {
+    let args: Vec<String> = env::args().collect();
 
     let mut consumption: Option<i32> = None;
     let mut table_dir: Option<PathBuf> = None;
+    let mut verify = false;
+    let mut tolerance: Option<NeedleTolerance> = None;
+    let mut brute_force = false;
+    let mut stop_on_hit = false;
+    let mut queries: Option<Queries> = None;
+    let mut format: Option<OutputFormat> = None;
 
     let mut i = 1;
     while i < args.len() {
@@ -90,39 +242,182 @@ fn main() {
             "--table-dir" => {
                 i += 1;
                 if i >= args.len() {
-                    eprintln!("--table-dir requires a value");
-                    std::process::exit(1);
+                    return Err("--table-dir requires a value".to_string());
                 }
                 table_dir = Some(PathBuf::from(&args[i]));
             }
+            "--verify" => {
+                verify = true;
+            }
+            "--tolerant" => {
+                tolerance.get_or_insert_with(NeedleTolerance::default);
+            }
+            "--max-corrections" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or("--max-corrections requires a value")?
+                    .parse()
+                    .map_err(|_| format!("Invalid --max-corrections value: {}", args[i]))?;
+                let current = tolerance.unwrap_or_default();
+                tolerance = Some(current.with_max_corrections(value));
+            }
+            "--max-delta" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or("--max-delta requires a value")?
+                    .parse()
+                    .map_err(|_| format!("Invalid --max-delta value: {}", args[i]))?;
+                let current = tolerance.unwrap_or_default();
+                tolerance = Some(current.with_max_delta(value));
+            }
+            "--brute-force" => {
+                brute_force = true;
+            }
+            "--stop-on-hit" => {
+                stop_on_hit = true;
+            }
+            option @ ("--needles" | "--batch") => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| format!("{} requires a value", option))?;
+                if queries.is_some() {
+                    return Err("--needles and --batch can only be given once".to_string());
+                }
+                queries = Some(if option == "--needles" {
+                    parse_needle_values(value)?;
+                    Queries::Needles(value.clone())
+                } else {
+                    Queries::Batch(PathBuf::from(value))
+                });
+            }
+            "--format" => {
+                i += 1;
+                format = Some(match args.get(i).map(String::as_str) {
+                    Some("text") => OutputFormat::Text,
+                    Some("json") => OutputFormat::Json,
+                    Some("csv") => OutputFormat::Csv,
+                    Some(other) => return Err(format!("Invalid --format value: {}", other)),
+                    None => return Err("--format requires a value".to_string()),
+                });
+            }
+            "--help" | "-h" => {
+                print_usage(&args[0]);
+                std::process::exit(0);
+            }
             value if !value.starts_with('-') => {
                 if consumption.is_some() {
-                    eprintln!("Error: duplicate consumption argument '{}'.", value);
-                    std::process::exit(1);
+                    return Err(format!("Duplicate consumption argument: {}", value));
                 }
-                consumption = value.parse().ok();
-            }
-            other => {
-                eprintln!("Unknown option: {}", other);
-                std::process::exit(1);
+                consumption = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid consumption value: {}", value))?,
+                );
             }
+            other => return Err(format!("Unknown option: {}", other)),
         }
         i += 1;
     }
 
-    let consumption = match consumption {
-        Some(v) => v,
-        None => {
-            eprintln!("Error: Missing or invalid consumption value.");
-            std::process::exit(1);
+    let consumption = consumption.ok_or("Missing consumption argument")?;
+
+    if brute_force && tolerance.is_some() {
+        return Err("--tolerant cannot be combined with --brute-force".to_string());
+    }
+    if let Some(tolerance) = tolerance
+        && !tolerance.is_within_limit()
+    {
+        return Err(format!(
+            "--max-corrections {} with --max-delta {} tries {} readings (limit: {}); \
+             lower one of them",
+            tolerance.max_corrections,
+            tolerance.max_delta,
+            tolerance.candidate_count(),
+            MAX_NEEDLE_CANDIDATES
+        ));
+    }
+    if brute_force && verify {
+        return Err("--verify cannot be combined with --brute-force".to_string());
+    }
+    if stop_on_hit && !brute_force {
+        return Err("--stop-on-hit requires --brute-force".to_string());
+    }
+    if brute_force && queries.is_some() {
+        return Err("--needles and --batch cannot be combined with --brute-force".to_string());
+    }
+    let format = match (&queries, format) {
+        (None, Some(_)) => return Err("--format requires --needles or --batch".to_string()),
+        (Some(Queries::Batch(_)), None) => OutputFormat::Json,
+        (_, format) => format.unwrap_or(OutputFormat::Text),
+    };
+
+    Ok(Args {
+        consumption,
+        table_dir,
+        verify,
+        tolerance,
+        brute_force,
+        stop_on_hit,
+        queries,
+        format,
+    })
+}
+
+/// Destination of status messages
+///
+/// Messages go to stderr when stdout carries JSON or CSV results.
+#[derive(Clone, Copy)]
+struct Status {
+    to_stderr: bool,
+}
+
+impl Status {
+    fn print(self, message: impl std::fmt::Display) {
+        if self.to_stderr {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!();
+            print_usage(&env::args().next().unwrap_or_default());
+            std::process::exit(EXIT_INVALID_INPUT);
         }
     };
+    let Args {
+        consumption,
+        table_dir,
+        verify,
+        tolerance,
+        brute_force,
+        stop_on_hit,
+        queries,
+        format,
+    } = args;
 
+    if brute_force {
+        run_brute_force(consumption, stop_on_hit);
+        return;
+    }
+
+    let status = Status {
+        to_stderr: format != OutputFormat::Text,
+    };
     let resolved_dir = table_dir.unwrap_or_else(|| PathBuf::from("."));
     let table_path = get_single_table_path(&resolved_dir, consumption);
 
-    println!("Loading table for consumption {}...", consumption);
-    println!("Table file: {}", table_path.display());
+    status.print(format!("Loading table for consumption {}...", consumption));
+    status.print(format!("Table file: {}", table_path.display()));
     let start_load = Instant::now();
 
     let options = ValidationOptions::for_search(consumption);
@@ -131,141 +426,783 @@ fn main() {
         Ok(t) => t,
         Err(e) => {
             eprintln!("Error: {}", format_table_error(&table_path, e));
-            std::process::exit(1);
+            std::process::exit(EXIT_TABLE_ERROR);
         }
     };
 
     let load_time = start_load.elapsed();
 
-    let table_count = table.num_tables();
+    let missing_path = get_missing_seeds_path(&resolved_dir, consumption);
+    if verify {
+        verify_files(status, &table_path, &table, &missing_path);
+    }
 
-    println!(
+    status.print(format!(
         "Loaded {} tables in {:.3} seconds",
-        table_count,
+        table.num_tables(),
         load_time.as_secs_f64()
+    ));
+
+    let embedded_seeds = embedded_missing_seeds(status, &table);
+    let missing_file = if embedded_seeds.is_none() {
+        load_missing_seeds_fallback(status, &missing_path, &table)
+    } else {
+        None
+    };
+    let missing_seeds = embedded_seeds.or_else(|| missing_file.as_ref().map(MissingSeeds::as_view));
+
+    let search = TableSearch {
+        consumption,
+        table: &table,
+        table_refs: table.table_refs(),
+        missing_seeds,
+        tolerance,
+        status,
+    };
+
+    let exit_code = match queries {
+        None => {
+            while let Some(values) = prompt_needle_values() {
+                let result = search.run(&values);
+                print_text_result(&search, &result);
+            }
+            EXIT_FOUND
+        }
+        Some(Queries::Needles(line)) => run_queries(&search, [Ok(line)], format),
+        Some(Queries::Batch(path)) if path.as_os_str() == "-" => {
+            run_queries(&search, io::stdin().lock().lines(), format)
+        }
+        Some(Queries::Batch(path)) => match File::open(&path) {
+            Ok(file) => run_queries(&search, BufReader::new(file).lines(), format),
+            Err(e) => {
+                eprintln!("Error: {}: {}", path.display(), e);
+                EXIT_INVALID_INPUT
+            }
+        },
+    };
+    std::process::exit(exit_code);
+}
+
+// =============================================================================
+// Table search
+// =============================================================================
+
+/// Loaded table and settings shared by all queries
+struct TableSearch<'a> {
+    consumption: i32,
+    table: &'a MappedSingleTable,
+    table_refs: Vec<TableRef<'a>>,
+    missing_seeds: Option<MissingSeedsView<&'a [u8]>>,
+    tolerance: Option<NeedleTolerance>,
+    status: Status,
+}
+
+/// Where a candidate seed was found
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum HitSource {
+    Table,
+    MissingSeeds,
+    Tolerant,
+}
+
+impl HitSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Table => "table",
+            Self::MissingSeeds => "missing_seeds",
+            Self::Tolerant => "tolerant",
+        }
+    }
+}
+
+/// Candidate initial seed of a query
+struct SeedHit {
+    seed: u32,
+    /// Table the seed was found in (None for missing seeds and corrected readings)
+    table_id: Option<u32>,
+    source: HitSource,
+    corrections: Vec<NeedleCorrection>,
+}
+
+/// Result of a single query
+struct QueryResult {
+    hits: Vec<SeedHit>,
+    /// Candidates were found but all were ruled out by the extra needles
+    filtered_out: bool,
+    elapsed: Duration,
+}
+
+impl TableSearch<'_> {
+    /// Search a reading of 8 or more needle values
+    fn run(&self, values: &[u64]) -> QueryResult {
+        let (head, extra_needles) = values.split_at(NEEDLE_COUNT);
+        let needle_values: [u64; NEEDLE_COUNT] = head.try_into().unwrap();
+
+        self.status.print(format!(
+            "Searching across {} tables...",
+            self.table_refs.len()
+        ));
+        let start = Instant::now();
+
+        let mut hits: Vec<SeedHit> =
+            search_seeds_multi_table(needle_values, self.consumption, &self.table_refs)
+                .into_iter()
+                .map(|(table_id, seed)| SeedHit {
+                    seed,
+                    table_id: Some(table_id),
+                    source: HitSource::Table,
+                    corrections: Vec::new(),
+                })
+                .collect();
+
+        if hits.is_empty()
+            && let Some(missing) = &self.missing_seeds
+        {
+            self.status.print(format!(
+                "Not found in tables. Searching {} missing seeds...",
+                missing.len()
+            ));
+            hits = search_missing_seeds(needle_values, self.consumption, missing)
+                .into_iter()
+                .map(|seed| SeedHit {
+                    seed,
+                    table_id: None,
+                    source: HitSource::MissingSeeds,
+                    corrections: Vec::new(),
+                })
+                .collect();
+        }
+
+        hits.sort_by_key(|hit| (hit.seed, hit.table_id));
+        keep_first_per_seed(&mut hits, |hit| hit.seed);
+
+        let found_before_filter = !hits.is_empty();
+        if found_before_filter && !extra_needles.is_empty() {
+            hits = self.filter_hits(hits, extra_needles);
+        }
+
+        if hits.is_empty()
+            && let Some(tolerance) = &self.tolerance
+        {
+            self.status.print(format!(
+                "No exact match. Trying readings with up to {} misread needle(s) (±{})...",
+                tolerance.max_corrections,
+                tolerance.max_delta.min(MAX_NEEDLE_DELTA)
+            ));
+            let tolerant_hits = search_tolerant(
+                needle_values,
+                self.consumption,
+                self.table,
+                self.missing_seeds.as_ref(),
+                tolerance,
+            );
+            hits = tolerant_hits
+                .into_iter()
+                .map(|hit| SeedHit {
+                    seed: hit.seed,
+                    table_id: None,
+                    source: HitSource::Tolerant,
+                    corrections: hit.corrections,
+                })
+                .collect();
+
+            if !hits.is_empty() && !extra_needles.is_empty() {
+                hits = self.filter_hits(hits, extra_needles);
+            }
+        }
+
+        QueryResult {
+            filtered_out: found_before_filter && hits.is_empty(),
+            hits,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    /// Keep the hits that match the extra needles
+    fn filter_hits(&self, mut hits: Vec<SeedHit>, extra_needles: &[u64]) -> Vec<SeedHit> {
+        let seeds: Vec<u32> = hits.iter().map(|hit| hit.seed).collect();
+        let remaining = apply_extra_needles(self.status, &seeds, self.consumption, extra_needles);
+        hits.retain(|hit| remaining.contains(&hit.seed));
+        hits
+    }
+}
+
+/// Print the result of a query for a person to read
+fn print_text_result(search: &TableSearch, result: &QueryResult) {
+    let hits = &result.hits;
+    if hits.is_empty() {
+        println!("No initial seed found.");
+        println!("Searched {} table(s).", search.table_refs.len());
+        println!("This can happen if:");
+        println!("  - The needle values were entered incorrectly");
+        if result.filtered_out {
+            println!("  - The extra needle values were entered incorrectly");
+        } else if search.missing_seeds.is_none() {
+            println!("  - The seed is not covered by the loaded tables");
+        }
+        println!("Try measuring the needle values again.");
+    } else if hits[0].source == HitSource::Tolerant {
+        println!("Found {} candidate seed(s) with corrections:", hits.len());
+        for hit in hits {
+            println!(
+                "  0x{:08X} ({})  {}",
+                hit.seed,
+                hit.seed,
+                format_corrections(&hit.corrections)
+            );
+        }
+    } else {
+        println!("Found {} initial seed(s):", hits.len());
+        for hit in hits {
+            println!("  0x{:08X} ({})", hit.seed, hit.seed);
+        }
+    }
+
+    println!(
+        "Search completed in {:.2} seconds.",
+        result.elapsed.as_secs_f64()
     );
+}
+
+/// Column names of the CSV output
+const CSV_HEADER: &str = "line,needles,status,seed_hex,seed,table_id,source,corrections,elapsed_ms";
+
+/// Search every reading of `lines` and write one record per reading
+///
+/// Empty lines and lines starting with `#` are skipped. Returns the exit
+/// code: the most severe outcome over all readings.
+fn run_queries<I>(search: &TableSearch, lines: I, format: OutputFormat) -> i32
+where
+    I: IntoIterator<Item = io::Result<String>>,
+{
+    if format == OutputFormat::Csv {
+        println!("{}", CSV_HEADER);
+    }
+
+    let mut exit_code = EXIT_FOUND;
+    for (index, line) in lines.into_iter().enumerate() {
+        let line_number = index + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Error: Failed to read line {}: {}", line_number, e);
+                return EXIT_INVALID_INPUT;
+            }
+        };
+        let input = line.trim();
+        if input.is_empty() || input.starts_with('#') {
+            continue;
+        }
+
+        let values = match parse_needle_values(input) {
+            Ok(values) => values,
+            Err(e) => {
+                match format {
+                    OutputFormat::Json => println!(
+                        "{}",
+                        json!({ "line": line_number, "input": input, "error": e })
+                    ),
+                    OutputFormat::Csv => println!("{}", csv_error_record(line_number, input)),
+                    OutputFormat::Text => {}
+                }
+                eprintln!("Error: Line {}: {}", line_number, e);
+                exit_code = exit_code.max(EXIT_INVALID_INPUT);
+                continue;
+            }
+        };
+
+        if format == OutputFormat::Text {
+            println!("\nNeedles: {}", format_needles(&values));
+        }
+        let result = search.run(&values);
+        match format {
+            OutputFormat::Text => print_text_result(search, &result),
+            OutputFormat::Json => println!("{}", json_record(line_number, &values, &result)),
+            OutputFormat::Csv => print_csv_records(line_number, &values, &result),
+        }
+        io::stdout().flush().unwrap();
+
+        if result.hits.is_empty() {
+            exit_code = exit_code.max(EXIT_NOT_FOUND);
+        }
+    }
+    exit_code
+}
+
+fn format_needles(values: &[u64]) -> String {
+    values
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// JSON object of a query result (one line per reading)
+fn json_record(line_number: usize, values: &[u64], result: &QueryResult) -> Value {
+    let seeds: Vec<Value> = result
+        .hits
+        .iter()
+        .map(|hit| {
+            let corrections: Vec<Value> = hit
+                .corrections
+                .iter()
+                .map(|c| {
+                    json!({
+                        "needle": c.position + 1,
+                        "observed": c.observed,
+                        "corrected": c.corrected,
+                    })
+                })
+                .collect();
+            json!({
+                "seed": hit.seed,
+                "seed_hex": format!("0x{:08X}", hit.seed),
+                "table_id": hit.table_id,
+                "source": hit.source.as_str(),
+                "corrections": corrections,
+            })
+        })
+        .collect();
+
+    json!({
+        "line": line_number,
+        "needles": values,
+        "found": !result.hits.is_empty(),
+        "seeds": seeds,
+        "elapsed_ms": result.elapsed.as_secs_f64() * 1000.0,
+    })
+}
+
+/// Quote a CSV field if needed (RFC 4180: embedded quotes are doubled)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// CSV row of a reading that could not be parsed
+fn csv_error_record(line_number: usize, input: &str) -> String {
+    format!("{},{},error,,,,,,", line_number, csv_field(input))
+}
+
+/// CSV rows of a query result (one row per seed, or one row if none is found)
+fn print_csv_records(line_number: usize, values: &[u64], result: &QueryResult) {
+    let needles = csv_field(&format_needles(values));
+    let elapsed_ms = result.elapsed.as_secs_f64() * 1000.0;
+
+    if result.hits.is_empty() {
+        println!(
+            "{},{},not_found,,,,,,{:.3}",
+            line_number, needles, elapsed_ms
+        );
+        return;
+    }
+    for hit in &result.hits {
+        let corrections: Vec<String> = hit
+            .corrections
+            .iter()
+            .map(|c| format!("{}:{}->{}", c.position + 1, c.observed, c.corrected))
+            .collect();
+        println!(
+            "{},{},found,0x{:08X},{},{},{},{},{:.3}",
+            line_number,
+            needles,
+            hit.seed,
+            hit.seed,
+            hit.table_id.map(|id| id.to_string()).unwrap_or_default(),
+            hit.source.as_str(),
+            csv_field(&corrections.join(" ")),
+            elapsed_ms
+        );
+    }
+}
+
+/// Parse a reading of 8 or more needle values (0-16, space-separated)
+fn parse_needle_values(input: &str) -> Result<Vec<u64>, String> {
+    let values = input
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, s)| match s.parse::<u64>() {
+            Ok(v) if v <= 16 => Ok(v),
+            _ => Err(format!(
+                "Value at position {} is {} (must be 0-16)",
+                i + 1,
+                s
+            )),
+        })
+        .collect::<Result<Vec<u64>, String>>()?;
 
+    if values.len() < NEEDLE_COUNT {
+        return Err(format!(
+            "Expected at least {} values, got {}",
+            NEEDLE_COUNT,
+            values.len()
+        ));
+    }
+    Ok(values)
+}
+
+/// Prompt until valid needle values are entered
+///
+/// Returns `None` when the user quits or input ends.
+fn prompt_needle_values() -> Option<Vec<u64>> {
     loop {
         print!(
-            "\nEnter needle values ({} values, 0-16, space-separated, or 'q' to quit): ",
+            "\nEnter needle values ({}+ values, 0-16, space-separated, or 'q' to quit): ",
             NEEDLE_COUNT
         );
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
-        if io::stdin().read_line(&mut input).is_err() {
-            eprintln!("Error reading input.");
-            continue;
+        match io::stdin().read_line(&mut input) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(_) => {
+                eprintln!("Error reading input.");
+                continue;
+            }
         }
 
         let input = input.trim();
 
         if input.eq_ignore_ascii_case("q") || input.eq_ignore_ascii_case("quit") {
             println!("Goodbye!");
-            break;
+            return None;
         }
 
-        let values: Vec<u64> = input
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
+        match parse_needle_values(input) {
+            Ok(values) => return Some(values),
+            Err(e) => eprintln!("Error: {}. Please try again.", e),
+        }
+    }
+}
 
-        if values.len() != NEEDLE_COUNT {
-            eprintln!(
-                "Error: Expected {} values, got {}. Please try again.",
-                NEEDLE_COUNT,
-                values.len()
-            );
-            continue;
+/// Filter candidates with the extra needles and report how many each one removed
+fn apply_extra_needles(
+    status: Status,
+    seeds: &[u32],
+    consumption: i32,
+    extra_needles: &[u64],
+) -> Vec<u32> {
+    status.print(format!(
+        "Filtering {} candidate(s) with {} extra needle(s)...",
+        seeds.len(),
+        extra_needles.len()
+    ));
+
+    let filter = filter_by_extra_needles(seeds, consumption, extra_needles);
+    for (i, (&value, removed)) in extra_needles
+        .iter()
+        .zip(&filter.removed_per_needle)
+        .enumerate()
+    {
+        status.print(format!(
+            "  Needle {} (value {}): removed {} candidate(s)",
+            NEEDLE_COUNT + i + 1,
+            value,
+            removed
+        ));
+    }
+
+    filter.remaining
+}
+
+/// Check the checksums of the table and missing seeds files, exiting on a mismatch
+fn verify_files(status: Status, table_path: &Path, table: &MappedSingleTable, missing_path: &Path) {
+    status.print("Verifying table checksum...");
+    match table.verify_checksum() {
+        Ok(ChecksumStatus::Verified(checksum)) => {
+            status.print(format!("Table checksum OK ({:016x})", checksum))
+        }
+        Ok(ChecksumStatus::NotRecorded) => {
+            status.print("Table has no checksum (created by an older version); skipped")
+        }
+        Err(e) => {
+            eprintln!("Error: {}", format_table_error(table_path, e));
+            std::process::exit(EXIT_TABLE_ERROR);
         }
+    }
 
-        let mut valid = true;
-        for (i, &v) in values.iter().enumerate() {
-            if v > 16 {
-                eprintln!(
-                    "Error: Value at position {} is {} (must be 0-16).",
-                    i + 1,
-                    v
-                );
-                valid = false;
-            }
+    if !missing_path.exists() {
+        return;
+    }
+    match verify_missing_seeds_file(missing_path) {
+        Ok(ChecksumStatus::Verified(checksum)) => {
+            status.print(format!("Missing seeds checksum OK ({:016x})", checksum))
+        }
+        Ok(ChecksumStatus::NotRecorded) => status
+            .print("Missing seeds file has no checksum (created by an older version); skipped"),
+        Err(e) => {
+            eprintln!("Error: {}: {}", missing_path.display(), e);
+            std::process::exit(EXIT_TABLE_ERROR);
         }
+    }
+}
 
-        if !valid {
-            continue;
+/// Get the missing seeds stored in a sectioned table file, if any
+fn embedded_missing_seeds(
+    status: Status,
+    table: &MappedSingleTable,
+) -> Option<MissingSeedsView<&[u8]>> {
+    match MissingSeedsView::embedded(table) {
+        Ok(Some(seeds)) => {
+            status.print(format!(
+                "Using {} missing seeds stored in the table",
+                seeds.len()
+            ));
+            Some(seeds)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            eprintln!("Warning: Ignoring missing seeds stored in the table: {}", e);
+            None
         }
+    }
+}
 
-        let needle_values: [u64; NEEDLE_COUNT] = values.try_into().unwrap();
+/// Open the missing seeds file used when the table search finds nothing
+///
+/// The file is memory-mapped and streamed during the fallback search.
+/// Returns `None` (with a notice) if the file is absent or does not belong to the table.
+fn load_missing_seeds_fallback(
+    status: Status,
+    path: &Path,
+    table: &MappedSingleTable,
+) -> Option<MissingSeeds> {
+    if !path.exists() {
+        status.print(format!(
+            "Missing seeds file not found: {} (fallback search disabled)",
+            path.display()
+        ));
+        return None;
+    }
 
-        println!("Searching across {} tables...", table_count);
-        let start = Instant::now();
+    match MissingSeeds::open_for_mapped_table(path, table) {
+        Ok(seeds) => {
+            status.print(format!(
+                "Opened {} missing seeds from {}",
+                seeds.len(),
+                path.display()
+            ));
+            Some(seeds)
+        }
+        Err(e) => {
+            eprintln!(
+                "Warning: Ignoring missing seeds file {}: {}",
+                path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+// =============================================================================
+// Brute-force search (no table)
+// =============================================================================
+
+/// Interactive loop for table-free brute-force search
+fn run_brute_force(consumption: i32, stop_on_hit: bool) {
+    println!(
+        "Brute-force mode: checking all {} seeds for consumption {} (no table needed).",
+        SEED_SPACE, consumption
+    );
+    println!("Press Ctrl+C to cancel a running scan.");
+
+    // Ctrl+C cancels the running scan, or exits when waiting for input
+    let active_scan: Arc<Mutex<Option<CancellationToken>>> = Arc::new(Mutex::new(None));
+    let handler_scan = Arc::clone(&active_scan);
+    if let Err(e) = ctrlc::set_handler(move || match handler_scan.lock().unwrap().as_ref() {
+        Some(token) => token.cancel(),
+        None => std::process::exit(130),
+    }) {
+        eprintln!("Warning: Failed to install Ctrl+C handler: {}", e);
+    }
+
+    while let Some(values) = prompt_needle_values() {
+        let (head, extra_needles) = values.split_at(NEEDLE_COUNT);
+        let needle_values: [u64; NEEDLE_COUNT] = head.try_into().unwrap();
 
-        // Use binary search parallel when multi-sfmt is enabled
-        #[cfg(feature = "multi-sfmt")]
-        let search_result = search_all_tables_x16(needle_values, consumption, &table);
+        let token = CancellationToken::new();
+        *active_scan.lock().unwrap() = Some(token.clone());
+
+        let last_permille = AtomicU64::new(0);
+        let progress_callback = |progress: &BruteForceProgress| {
+            let permille = (progress.ratio() * 1000.0) as u64;
+            if last_permille.fetch_max(permille, Ordering::Relaxed) < permille
+                || progress.checked == progress.total
+            {
+                let eta = progress
+                    .eta()
+                    .map(format_duration)
+                    .unwrap_or_else(|| "-".to_string());
+                print!(
+                    "\rProgress: {:.1}% ({}/{}), found {}, ETA {}    ",
+                    progress.ratio() * 100.0,
+                    progress.checked,
+                    progress.total,
+                    progress.found,
+                    eta
+                );
+                io::stdout().flush().unwrap();
+            }
+        };
 
-        // Fall back to sequential search when multi-sfmt is not enabled
-        #[cfg(not(feature = "multi-sfmt"))]
-        let search_result = search_tables_sequential(needle_values, consumption, &table);
+        let result = brute_force_search(
+            needle_values,
+            consumption,
+            BruteForceOptions::default()
+                .with_stop_on_hit(stop_on_hit)
+                .with_cancel(token)
+                .with_progress(progress_callback),
+        );
 
-        let elapsed = start.elapsed();
+        *active_scan.lock().unwrap() = None;
+        println!();
 
-        if search_result.is_empty() {
+        match result.status {
+            BruteForceStatus::Completed => {}
+            BruteForceStatus::StoppedOnHit => {
+                println!(
+                    "Stopped at the first hit ({} seeds checked).",
+                    result.checked
+                )
+            }
+            BruteForceStatus::Cancelled => {
+                println!("Scan cancelled ({} seeds checked).", result.checked)
+            }
+        }
+
+        let mut seeds = result.seeds;
+        let found_before_filter = !seeds.is_empty();
+        if found_before_filter && !extra_needles.is_empty() {
+            seeds = apply_extra_needles(
+                Status { to_stderr: false },
+                &seeds,
+                consumption,
+                extra_needles,
+            );
+        }
+
+        if seeds.is_empty() {
             println!("No initial seed found.");
-            println!("Searched {} table(s).", table_count);
-            println!("This can happen if:");
-            println!("  - The needle values were entered incorrectly");
-            println!("  - The seed is not covered by the loaded tables");
-            println!("Try measuring the needle values again.");
+            if found_before_filter {
+                println!("The extra needle values may have been entered incorrectly.");
+            } else if result.status == BruteForceStatus::Completed {
+                println!("The needle values may have been entered incorrectly.");
+            }
         } else {
-            let mut seeds: Vec<u32> = search_result.iter().map(|(_, seed)| *seed).collect();
-            seeds.sort();
-            seeds.dedup();
-
             println!("Found {} initial seed(s):", seeds.len());
             for seed in &seeds {
                 println!("  0x{:08X} ({})", seed, seed);
             }
         }
 
-        println!("Search completed in {:.2} seconds.", elapsed.as_secs_f64());
+        println!(
+            "Search completed in {:.2} seconds.",
+            result.elapsed.as_secs_f64()
+        );
+    }
+}
+
+/// Format a duration as "1h 02m 03s", "2m 03s" or "5s"
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}h {:02}m {:02}s", h, m, s)
+    } else if m > 0 {
+        format!("{}m {:02}s", m, s)
+    } else {
+        format!("{}s", s)
     }
 }
 
 // =============================================================================
-// Parallel search with binary search
+// Error-tolerant search
 // =============================================================================
 
-/// Search all 16 tables in parallel using multi-sfmt
-#[cfg(feature = "multi-sfmt")]
-fn search_all_tables_x16(
+/// Search all tables and the missing seeds list with corrected readings
+fn search_tolerant(
     needle_values: [u64; NEEDLE_COUNT],
     consumption: i32,
     table: &MappedSingleTable,
-) -> Vec<(u32, u32)> {
-    let tables: [&[ChainEntry]; 16] =
-        std::array::from_fn(|i| table.table(i as u32).expect("table should exist"));
-    search_seeds_x16(needle_values, consumption, tables)
+    missing_seeds: Option<&MissingSeedsView<&[u8]>>,
+    tolerance: &NeedleTolerance,
+) -> Vec<TolerantHit> {
+    let mut hits = search_seeds_tolerant_multi_table(
+        needle_values,
+        consumption,
+        &table.table_refs(),
+        tolerance,
+    );
+
+    if let Some(missing) = missing_seeds {
+        hits.extend(search_missing_seeds_tolerant(
+            needle_values,
+            consumption,
+            missing,
+            tolerance,
+        ));
+    }
+
+    hits.sort_by_key(|hit| {
+        let total_delta: u64 = hit.corrections.iter().map(NeedleCorrection::delta).sum();
+        (hit.corrections.len(), total_delta, hit.seed)
+    });
+    keep_first_per_seed(&mut hits, |hit| hit.seed);
+    hits
 }
 
-// =============================================================================
-// Sequential search (fallback when multi-sfmt is disabled)
-// =============================================================================
+/// Keep only the first hit of every seed (the best one if `hits` is ranked)
+fn keep_first_per_seed<T>(hits: &mut Vec<T>, seed: impl Fn(&T) -> u32) {
+    let mut seen = HashSet::new();
+    hits.retain(|hit| seen.insert(seed(hit)));
+}
 
-/// Search tables sequentially with early exit
-#[cfg(not(feature = "multi-sfmt"))]
-fn search_tables_sequential(
-    needle_values: [u64; NEEDLE_COUNT],
-    consumption: i32,
-    table: &MappedSingleTable,
-) -> Vec<(u32, u32)> {
-    for table_id in 0..NUM_TABLES {
-        if let Some(view) = table.table(table_id) {
-            let results = search_seeds(needle_values, consumption, view, table_id);
-            if !results.is_empty() {
-                return results.into_iter().map(|seed| (table_id, seed)).collect();
-            }
-        }
+/// Format corrections as "needle 3: 5 -> 6, ..." (1-based positions)
+fn format_corrections(corrections: &[NeedleCorrection]) -> String {
+    if corrections.is_empty() {
+        return "exact match".to_string();
+    }
+    corrections
+        .iter()
+        .map(|c| {
+            format!(
+                "needle {}: {} -> {}",
+                c.position + 1,
+                c.observed,
+                c.corrected
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_first_per_seed() {
+        // Ranked as (corrections, seed): the same seed appears non-adjacently
+        let mut hits = vec![(0, 7), (1, 3), (1, 7), (2, 3), (2, 5)];
+        keep_first_per_seed(&mut hits, |&(_, seed)| seed);
+        assert_eq!(hits, vec![(0, 7), (1, 3), (2, 5)]);
+    }
+
+    #[test]
+    fn test_csv_error_record_quotes_input() {
+        assert_eq!(
+            csv_error_record(3, "1,2,3 \"4\""),
+            "3,\"1,2,3 \"\"4\"\"\",error,,,,,,"
+        );
+        assert_eq!(csv_error_record(4, "1 2 x"), "4,1 2 x,error,,,,,,");
+        assert_eq!(
+            csv_error_record(5, "0,1,2,3,4,5,6,7"),
+            "5,\"0,1,2,3,4,5,6,7\",error,,,,,,"
+        );
     }
-    Vec::new()
 }
@@ -0,0 +1,342 @@
+//! Table verification CLI
+//!
+//! Usage:
+//!   gen7seed_verify <consumption> [options]
+//!
+//! Options:
+//!   --table-dir <PATH>  Table directory (default: current directory)
+//!   --samples <N>       Chains recomputed per table (default: 256)
+//!   --round-trips <N>   Seeds searched for again (default: 16)
+//!   --seed <S>          Seed of the sampling RNG (default: current time)
+//!   --no-order          Skip the sort order check of every chain
+//!   --json              Print the report as JSON
+//!   --help, -h          Show help
+//!
+//! Example:
+//!   gen7seed_verify 417 --table-dir .\tables --samples 1024
+//!
+//! Unsorted tables (interrupted generation) are checked without the sort
+//! order check and the round trips, which need a sorted table.
+//!
+//! Exits with 0 if every check passed, 1 if a check failed and 2 on errors.
+
+use gen7seed_rainbow::app::verify::{DEFAULT_ROUND_TRIPS, DEFAULT_VERIFY_SAMPLES};
+use gen7seed_rainbow::infra::table_io::get_single_table_path;
+use gen7seed_rainbow::{
+    MappedSingleTable, ValidationOptions, VerifyFailure, VerifyOptions, verify_round_trips,
+    verify_table,
+};
+use serde_json::{Value, json};
+use std::env;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Every check passed
+const EXIT_PASSED: i32 = 0;
+/// A check failed
+const EXIT_FAILED: i32 = 1;
+/// Invalid arguments or the table could not be opened
+const EXIT_ERROR: i32 = 2;
+
+struct Args {
+    consumption: i32,
+    table_dir: Option<PathBuf>,
+    samples: usize,
+    round_trips: usize,
+    seed: Option<u32>,
+    check_order: bool,
+    json: bool,
+}
+
+fn print_usage(program: &str) {
+    eprintln!("Usage: {} <consumption> [options]", program);
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --table-dir <PATH>  Table directory (default: current directory)");
+    eprintln!(
+        "  --samples <N>       Chains recomputed per table (default: {})",
+        DEFAULT_VERIFY_SAMPLES
+    );
+    eprintln!(
+        "  --round-trips <N>   Seeds searched for again (default: {})",
+        DEFAULT_ROUND_TRIPS
+    );
+    eprintln!("  --seed <S>          Seed of the sampling RNG (default: current time)");
+    eprintln!("  --no-order          Skip the sort order check of every chain");
+    eprintln!("  --json              Print the report as JSON");
+    eprintln!("  --help, -h          Show this help message");
+}
+
+fn parse_args() -> Result<Args, String> {
+    let args: Vec<String> = env::args().collect();
+
+    let mut consumption: Option<i32> = None;
+    let mut table_dir: Option<PathBuf> = None;
+    let mut samples = DEFAULT_VERIFY_SAMPLES;
+    let mut round_trips = DEFAULT_ROUND_TRIPS;
+    let mut seed: Option<u32> = None;
+    let mut check_order = true;
+    let mut json = false;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--table-dir" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--table-dir requires a value".to_string());
+                }
+                table_dir = Some(PathBuf::from(&args[i]));
+            }
+            option @ ("--samples" | "--round-trips") => {
+                i += 1;
+                if i >= args.len() {
+                    return Err(format!("{} requires a value", option));
+                }
+                let value = args[i]
+                    .parse()
+                    .map_err(|_| format!("Invalid value for {}: {}", option, args[i]))?;
+                if option == "--samples" {
+                    samples = value;
+                } else {
+                    round_trips = value;
+                }
+            }
+            "--seed" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("--seed requires a value".to_string());
+                }
+                seed = Some(
+                    args[i]
+                        .parse()
+                        .map_err(|_| format!("Invalid value for --seed: {}", args[i]))?,
+                );
+            }
+            "--no-order" => check_order = false,
+            "--json" => json = true,
+            "--help" | "-h" => {
+                print_usage(&args[0]);
+                std::process::exit(EXIT_PASSED);
+            }
+            arg if !arg.starts_with('-') && consumption.is_none() => {
+                consumption = Some(
+                    arg.parse()
+                        .map_err(|_| format!("Invalid consumption value: {}", arg))?,
+                );
+            }
+            _ => return Err(format!("Unknown option: {}", args[i])),
+        }
+        i += 1;
+    }
+
+    Ok(Args {
+        consumption: consumption.ok_or("Missing consumption argument")?,
+        table_dir,
+        samples,
+        round_trips,
+        seed,
+        check_order,
+        json,
+    })
+}
+
+/// Print a progress message (to stderr when stdout holds JSON)
+fn status(args: &Args, message: &str) {
+    if args.json {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
+fn failure_report(failure: &VerifyFailure) -> Value {
+    match *failure {
+        VerifyFailure::ChainMismatch {
+            table_id,
+            index,
+            start_seed,
+        } => json!({
+            "kind": "chain_mismatch",
+            "table_id": table_id,
+            "index": index,
+            "start_seed": format!("{:08X}", start_seed),
+        }),
+        VerifyFailure::EndHashMismatch {
+            table_id,
+            index,
+            count,
+        } => json!({
+            "kind": "end_hash_mismatch",
+            "table_id": table_id,
+            "index": index,
+            "count": count,
+        }),
+        VerifyFailure::OutOfOrder {
+            table_id,
+            index,
+            count,
+        } => json!({
+            "kind": "out_of_order",
+            "table_id": table_id,
+            "index": index,
+            "count": count,
+        }),
+        VerifyFailure::RoundTripMissed {
+            table_id,
+            index,
+            column,
+            seed,
+        } => json!({
+            "kind": "round_trip_missed",
+            "table_id": table_id,
+            "index": index,
+            "column": column,
+            "seed": format!("{:08X}", seed),
+        }),
+    }
+}
+
+/// Run every check, returning the exit code
+fn run(args: &Args) -> Result<i32, String> {
+    let table_dir = args.table_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+    let table_path = get_single_table_path(&table_dir, args.consumption);
+    status(
+        args,
+        &format!("Loading table from {}...", table_path.display()),
+    );
+    let validation = ValidationOptions {
+        expected_consumption: Some(args.consumption),
+        ..ValidationOptions::default()
+    };
+    let table = MappedSingleTable::open(&table_path, &validation)
+        .map_err(|e| format!("{}: {}", table_path.display(), e))?;
+    let tables = table.table_refs();
+    let sorted = table.header().is_sorted();
+    if !sorted {
+        status(
+            args,
+            "Table is not sorted: skipping the sort order check and the round trips",
+        );
+    }
+
+    let seed = args.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u32)
+    });
+    let options = VerifyOptions::new(args.consumption)
+        .with_samples(args.samples)
+        .with_round_trips(args.round_trips)
+        .with_check_order(args.check_order)
+        .with_sorted(sorted)
+        .with_seed(seed);
+    status(
+        args,
+        &format!("Sampling seed: {} (pass --seed to repeat)", seed),
+    );
+
+    let mut passed = true;
+    let mut table_reports = Vec::with_capacity(tables.len());
+    for (table_id, table_ref) in tables.iter().enumerate() {
+        let report = verify_table(table_ref, table_id as u32, &options);
+        status(
+            args,
+            &format!(
+                "[Table {}] {}: {} chains recomputed{}",
+                table_id,
+                if report.passed() { "PASS" } else { "FAIL" },
+                report.chains_checked,
+                if report.order_checked {
+                    ", sort order checked"
+                } else {
+                    ""
+                }
+            ),
+        );
+        for failure in &report.failures {
+            status(args, &format!("    {}", failure));
+        }
+        passed &= report.passed();
+        table_reports.push(json!({
+            "table_id": report.table_id,
+            "passed": report.passed(),
+            "chains_checked": report.chains_checked,
+            "order_checked": report.order_checked,
+            "failures": report.failures.iter().map(failure_report).collect::<Vec<_>>(),
+        }));
+    }
+
+    if sorted {
+        status(
+            args,
+            &format!(
+                "Searching {} seeds from random chains...",
+                options.round_trips
+            ),
+        );
+    }
+    let round_trips = verify_round_trips(&tables, &options);
+    if round_trips.skipped {
+        status(args, "[Round trips] SKIPPED: table is not sorted");
+    } else {
+        status(
+            args,
+            &format!(
+                "[Round trips] {}: {} of {} seeds found",
+                if round_trips.passed() { "PASS" } else { "FAIL" },
+                round_trips.checked - round_trips.failures.len(),
+                round_trips.checked
+            ),
+        );
+    }
+    for failure in &round_trips.failures {
+        status(args, &format!("    {}", failure));
+    }
+    passed &= round_trips.passed();
+
+    if args.json {
+        let report = json!({
+            "path": table_path.display().to_string(),
+            "consumption": args.consumption,
+            "seed": seed,
+            "sorted": sorted,
+            "passed": passed,
+            "tables": table_reports,
+            "round_trips": {
+                "passed": round_trips.passed(),
+                "skipped": round_trips.skipped,
+                "checked": round_trips.checked,
+                "failures": round_trips.failures.iter().map(failure_report).collect::<Vec<_>>(),
+            },
+        });
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!("Result: {}", if passed { "PASS" } else { "FAIL" });
+    }
+
+    Ok(if passed { EXIT_PASSED } else { EXIT_FAILED })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!();
+            print_usage(&env::args().next().unwrap_or_default());
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+
+    let start = Instant::now();
+    let code = run(&args).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        EXIT_ERROR
+    });
+    status(
+        &args,
+        &format!("Done in {:.2} seconds.", start.elapsed().as_secs_f64()),
+    );
+    std::process::exit(code);
+}
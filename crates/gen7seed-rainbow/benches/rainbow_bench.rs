@@ -10,7 +10,7 @@ use std::time::Duration;
 
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 use gen7seed_rainbow::{
-    GenerateOptions, domain::chain::compute_chain, generate_table,
+    GenerateOptions, MAX_CHAIN_LENGTH, domain::chain::compute_chain, generate_table,
     infra::table_sort::sort_table_parallel, search_seeds,
 };
 
@@ -40,7 +40,7 @@ fn bench_chain(c: &mut Criterion) {
     let mut group = c.benchmark_group("chain");
 
     group.bench_function("compute_chain_full", |b| {
-        b.iter(|| compute_chain(black_box(12345), CONSUMPTION, 0))
+        b.iter(|| compute_chain(black_box(12345), CONSUMPTION, 0, MAX_CHAIN_LENGTH))
     });
 
     group.finish();
@@ -102,7 +102,7 @@ fn bench_multi_sfmt_core(c: &mut Criterion) {
     });
 
     group.bench_function("chain_multi_x16", |b| {
-        b.iter(|| compute_chains_x16(black_box(seeds), CONSUMPTION, 0))
+        b.iter(|| compute_chains_x16(black_box(seeds), CONSUMPTION, 0, MAX_CHAIN_LENGTH))
     });
 
     group.bench_function("chain_multi_x64", |b| {
@@ -110,7 +110,8 @@ fn bench_multi_sfmt_core(c: &mut Criterion) {
             let mut results = Vec::with_capacity(64);
             for batch in 0..4 {
                 let batch_seeds: [u32; 16] = std::array::from_fn(|i| (batch * 16 + i) as u32);
-                let batch_results = compute_chains_x16(black_box(batch_seeds), CONSUMPTION, 0);
+                let batch_results =
+                    compute_chains_x16(black_box(batch_seeds), CONSUMPTION, 0, MAX_CHAIN_LENGTH);
                 results.extend(batch_results);
             }
             results
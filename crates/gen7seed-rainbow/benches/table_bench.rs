@@ -15,7 +15,7 @@ use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use gen7seed_rainbow::domain::chain::ChainEntry;
+use gen7seed_rainbow::domain::chain::{ChainEntry, EndHashKey};
 use gen7seed_rainbow::infra::table_io::load_single_table;
 use gen7seed_rainbow::infra::table_sort::sort_table_parallel;
 use gen7seed_rainbow::{GenerateOptions, Sfmt, ValidationOptions, generate_table, search_seeds};
@@ -64,8 +64,12 @@ fn get_full_table() -> Option<&'static Vec<ChainEntry>> {
                 eprintln!("[table_bench] Loading full table from {:?}...", path);
                 let start = Instant::now();
                 let options = ValidationOptions::for_search(CONSUMPTION);
-                let (_header, tables) = load_single_table(&path, &options).ok()?;
-                let table = tables.into_iter().next()?;
+                let (header, tables) = load_single_table(&path, &options).ok()?;
+                let mut table = tables.into_iter().next()?;
+                // Legacy tables are sorted by the truncated hash; re-sort them
+                if header.end_hash_key() != EndHashKey::Full {
+                    sort_table_parallel(&mut table, CONSUMPTION);
+                }
                 eprintln!(
                     "[table_bench] Loaded {} entries in {:.2}s",
                     table.len(),
@@ -89,11 +93,17 @@ fn get_full_tables_16() -> Option<&'static Vec<Vec<ChainEntry>>> {
                 eprintln!("[table_bench] Loading all 16 tables from {:?}...", path);
                 let start = Instant::now();
                 let options = ValidationOptions::for_search(CONSUMPTION);
-                let (_header, tables) = load_single_table(&path, &options).ok()?;
+                let (header, mut tables) = load_single_table(&path, &options).ok()?;
                 if tables.len() != 16 {
                     eprintln!("[table_bench] Expected 16 tables, found {}", tables.len());
                     return None;
                 }
+                // Legacy tables are sorted by the truncated hash; re-sort them
+                if header.end_hash_key() != EndHashKey::Full {
+                    for table in tables.iter_mut() {
+                        sort_table_parallel(table, CONSUMPTION);
+                    }
+                }
                 eprintln!(
                     "[table_bench] Loaded 16 tables ({} entries each) in {:.2}s",
                     tables[0].len(),
@@ -170,7 +180,7 @@ fn bench_search_mini_table_compare_x16(c: &mut Criterion) {
         b.iter(|| {
             let mut total = 0usize;
             for table in tables.iter() {
-                total += search_seeds(black_box(needle), CONSUMPTION, table, 0).len();
+                total += search_seeds(black_box(needle), CONSUMPTION, *table, 0).len();
             }
             black_box(total)
         })
@@ -29,7 +29,7 @@ use std::time::Instant;
 
 use gen7seed_rainbow::Sfmt;
 use gen7seed_rainbow::ValidationOptions;
-use gen7seed_rainbow::domain::chain::ChainEntry;
+use gen7seed_rainbow::domain::chain::TableRef;
 use gen7seed_rainbow::infra::table_io::{get_single_table_path, load_single_table};
 use rand::Rng;
 
@@ -103,7 +103,9 @@ fn run_detection_rate() {
         let needle = generate_needle_from_seed(seed, CONSUMPTION);
 
         // Search across all 16 tables simultaneously using multi-sfmt
-        let table_refs: [&[ChainEntry]; 16] = std::array::from_fn(|i| tables[i].as_slice());
+        let table_refs: [TableRef; 16] = std::array::from_fn(|i| {
+            TableRef::new(tables[i].as_slice()).with_sort_key(header.end_hash_key())
+        });
         let results = search_seeds_x16(needle, CONSUMPTION, table_refs);
 
         // Check if seed was found in any table
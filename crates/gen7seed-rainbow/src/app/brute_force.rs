@@ -0,0 +1,375 @@
+//! Table-free brute-force search workflow
+//!
+//! This module scans the seed space directly, computing the needle hash of
+//! every seed. It needs no rainbow table, so it works for any consumption
+//! value, at the cost of checking all 2^32 seeds.
+
+use crate::app::cancel::CancellationToken;
+use crate::constants::SEED_SPACE;
+use crate::domain::hash::gen_hash;
+use rayon::prelude::*;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "multi-sfmt")]
+use crate::domain::hash::gen_hash_from_seed_x16;
+
+#[cfg(not(feature = "multi-sfmt"))]
+use crate::domain::hash::gen_hash_from_seed;
+
+/// Number of seeds processed per work unit (progress/cancellation granularity)
+const CHUNK_SIZE: u64 = 1 << 16;
+
+/// Progress of a brute-force scan
+#[derive(Clone, Copy, Debug)]
+pub struct BruteForceProgress {
+    /// Number of seeds checked so far
+    pub checked: u64,
+    /// Total number of seeds to check
+    pub total: u64,
+    /// Number of matching seeds found so far
+    pub found: usize,
+    /// Time elapsed since the scan started
+    pub elapsed: Duration,
+}
+
+impl BruteForceProgress {
+    /// Fraction of the scan completed (0.0 to 1.0)
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.checked as f64 / self.total as f64
+        }
+    }
+
+    /// Estimated time remaining, based on the average rate so far
+    pub fn eta(&self) -> Option<Duration> {
+        if self.checked == 0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.checked);
+        Some(self.elapsed.mul_f64(remaining as f64 / self.checked as f64))
+    }
+}
+
+/// How a brute-force scan ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BruteForceStatus {
+    /// The whole range was scanned
+    Completed,
+    /// The scan stopped early because a seed was found
+    StoppedOnHit,
+    /// The scan was cancelled
+    Cancelled,
+}
+
+/// Result of a brute-force scan
+#[derive(Clone, Debug)]
+pub struct BruteForceResult {
+    /// Matching seeds in ascending order
+    pub seeds: Vec<u32>,
+    /// Number of seeds checked
+    pub checked: u64,
+    /// How the scan ended
+    pub status: BruteForceStatus,
+    /// Total scan time
+    pub elapsed: Duration,
+}
+
+/// Options for brute-force search
+#[derive(Clone)]
+pub struct BruteForceOptions<F = fn(&BruteForceProgress)> {
+    /// Start of the seed range (inclusive, default: 0)
+    pub start: u64,
+    /// End of the seed range (exclusive, default: SEED_SPACE)
+    pub end: u64,
+    /// Stop all workers as soon as a seed is found (default: false)
+    pub stop_on_hit: bool,
+    /// Cancellation token (default: None)
+    pub cancel: Option<CancellationToken>,
+    /// Progress callback
+    pub on_progress: Option<F>,
+}
+
+impl Default for BruteForceOptions<fn(&BruteForceProgress)> {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            end: SEED_SPACE,
+            stop_on_hit: false,
+            cancel: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl<F> BruteForceOptions<F> {
+    /// Set the seed range (end is clamped to SEED_SPACE)
+    pub fn with_range(mut self, start: u64, end: u64) -> Self {
+        self.start = start;
+        self.end = end.min(SEED_SPACE);
+        self
+    }
+
+    /// Stop as soon as a seed is found
+    pub fn with_stop_on_hit(mut self, stop_on_hit: bool) -> Self {
+        self.stop_on_hit = stop_on_hit;
+        self
+    }
+
+    /// Set the cancellation token
+    pub fn with_cancel(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Set the progress callback
+    pub fn with_progress<G>(self, callback: G) -> BruteForceOptions<G> {
+        BruteForceOptions {
+            start: self.start,
+            end: self.end,
+            stop_on_hit: self.stop_on_hit,
+            cancel: self.cancel,
+            on_progress: Some(callback),
+        }
+    }
+}
+
+/// Search for initial seeds by checking every seed in the range
+///
+/// Splits the range into chunks processed in parallel with rayon; within a
+/// chunk, 16 seeds are hashed at once with multi-sfmt when available.
+/// The progress callback is called after each chunk and once at the end.
+///
+/// # Arguments
+/// * `needle_values` - 8 needle values (0-16 each) representing clock hand positions
+/// * `consumption` - The RNG consumption value (any value, no table needed)
+/// * `options` - Range, early exit, cancellation and progress options
+pub fn brute_force_search<F>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    options: BruteForceOptions<F>,
+) -> BruteForceResult
+where
+    F: Fn(&BruteForceProgress) + Sync,
+{
+    let BruteForceOptions {
+        start,
+        end,
+        stop_on_hit,
+        cancel,
+        on_progress,
+    } = options;
+
+    let target_hash = gen_hash(needle_values);
+    let started = Instant::now();
+    let total = end.saturating_sub(start);
+    let num_chunks = total.div_ceil(CHUNK_SIZE);
+
+    let checked = AtomicU64::new(0);
+    let found_count = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let found = Mutex::new(Vec::new());
+    let is_cancelled = || cancel.as_ref().is_some_and(CancellationToken::is_cancelled);
+
+    (0..num_chunks).into_par_iter().for_each(|chunk| {
+        if stop.load(Ordering::Relaxed) || is_cancelled() {
+            return;
+        }
+
+        let chunk_start = start + chunk * CHUNK_SIZE;
+        let chunk_end = (chunk_start + CHUNK_SIZE).min(end);
+        let hits = scan_range(chunk_start, chunk_end, consumption, target_hash);
+
+        if !hits.is_empty() {
+            found_count.fetch_add(hits.len(), Ordering::Relaxed);
+            found.lock().unwrap().extend(hits);
+            if stop_on_hit {
+                stop.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let done = checked.fetch_add(chunk_end - chunk_start, Ordering::Relaxed)
+            + (chunk_end - chunk_start);
+        if let Some(ref callback) = on_progress {
+            callback(&BruteForceProgress {
+                checked: done,
+                total,
+                found: found_count.load(Ordering::Relaxed),
+                elapsed: started.elapsed(),
+            });
+        }
+    });
+
+    let checked = checked.into_inner();
+    let mut seeds = found.into_inner().unwrap();
+    seeds.sort_unstable();
+
+    let status = if is_cancelled() && checked < total {
+        BruteForceStatus::Cancelled
+    } else if checked < total {
+        BruteForceStatus::StoppedOnHit
+    } else {
+        BruteForceStatus::Completed
+    };
+
+    let elapsed = started.elapsed();
+    if let Some(ref callback) = on_progress {
+        callback(&BruteForceProgress {
+            checked,
+            total,
+            found: seeds.len(),
+            elapsed,
+        });
+    }
+
+    BruteForceResult {
+        seeds,
+        checked,
+        status,
+        elapsed,
+    }
+}
+
+/// Check all seeds in `start..end` (16 at a time with multi-sfmt)
+#[cfg(feature = "multi-sfmt")]
+fn scan_range(start: u64, end: u64, consumption: i32, target_hash: u64) -> Vec<u32> {
+    let mut hits = Vec::new();
+    let mut base = start;
+
+    while base < end {
+        let count = (end - base).min(16) as usize;
+        // Lanes past the end of the range repeat the last seed and are ignored
+        let seeds: [u32; 16] = std::array::from_fn(|i| (base + i.min(count - 1) as u64) as u32);
+        let hashes = gen_hash_from_seed_x16(seeds, consumption);
+
+        for i in 0..count {
+            if hashes[i] == target_hash {
+                hits.push(seeds[i]);
+            }
+        }
+        base += 16;
+    }
+
+    hits
+}
+
+/// Check all seeds in `start..end` (fallback version without multi-sfmt)
+#[cfg(not(feature = "multi-sfmt"))]
+fn scan_range(start: u64, end: u64, consumption: i32, target_hash: u64) -> Vec<u32> {
+    (start..end)
+        .map(|seed| seed as u32)
+        .filter(|&seed| gen_hash_from_seed(seed, consumption) == target_hash)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::needle::gen_needle_values;
+
+    fn needle_values_from_seed(seed: u32, consumption: i32) -> [u64; 8] {
+        gen_needle_values(seed, consumption, 8).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_brute_force_finds_seed_in_range() {
+        let needle_values = needle_values_from_seed(1234, 417);
+        let result = brute_force_search(
+            needle_values,
+            417,
+            BruteForceOptions::default().with_range(1200, 1300),
+        );
+
+        assert_eq!(result.seeds, vec![1234]);
+        assert_eq!(result.checked, 100);
+        assert_eq!(result.status, BruteForceStatus::Completed);
+    }
+
+    #[test]
+    fn test_brute_force_arbitrary_consumption() {
+        let needle_values = needle_values_from_seed(77, 1000);
+        let result = brute_force_search(
+            needle_values,
+            1000,
+            BruteForceOptions::default().with_range(70, 85),
+        );
+
+        assert_eq!(result.seeds, vec![77]);
+    }
+
+    #[test]
+    fn test_brute_force_empty_range() {
+        let needle_values = needle_values_from_seed(1234, 417);
+        let result = brute_force_search(
+            needle_values,
+            417,
+            BruteForceOptions::default().with_range(10, 10),
+        );
+
+        assert!(result.seeds.is_empty());
+        assert_eq!(result.checked, 0);
+        assert_eq!(result.status, BruteForceStatus::Completed);
+    }
+
+    #[test]
+    fn test_brute_force_cancelled_before_start() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let needle_values = needle_values_from_seed(5, 417);
+        let result = brute_force_search(
+            needle_values,
+            417,
+            BruteForceOptions::default()
+                .with_range(0, 1000)
+                .with_cancel(token),
+        );
+
+        assert!(result.seeds.is_empty());
+        assert_eq!(result.checked, 0);
+        assert_eq!(result.status, BruteForceStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_brute_force_reports_progress() {
+        let calls = AtomicUsize::new(0);
+        let last_checked = AtomicU64::new(0);
+
+        let needle_values = needle_values_from_seed(5, 417);
+        let result = brute_force_search(
+            needle_values,
+            417,
+            BruteForceOptions::default()
+                .with_range(0, 100)
+                .with_progress(|progress: &BruteForceProgress| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    last_checked.store(progress.checked, Ordering::Relaxed);
+                }),
+        );
+
+        assert!(calls.load(Ordering::Relaxed) >= 1);
+        assert_eq!(last_checked.load(Ordering::Relaxed), result.checked);
+        assert_eq!(result.seeds, vec![5]);
+    }
+
+    #[test]
+    fn test_progress_eta() {
+        let progress = BruteForceProgress {
+            checked: 25,
+            total: 100,
+            found: 0,
+            elapsed: Duration::from_secs(10),
+        };
+        assert_eq!(progress.eta(), Some(Duration::from_secs(30)));
+        assert!((progress.ratio() - 0.25).abs() < 1e-9);
+
+        let not_started = BruteForceProgress {
+            checked: 0,
+            ..progress
+        };
+        assert_eq!(not_started.eta(), None);
+    }
+}
@@ -0,0 +1,55 @@
+//! Cancellation support for long-running workflows
+//!
+//! This module provides a cloneable token that can be used to request
+//! cancellation from another thread (e.g. a Ctrl+C handler or a GUI).
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Cancellation token shared between the caller and a running workflow
+///
+/// Clones share the same state, so cancelling any clone cancels all of them.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new token (not cancelled)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if cancellation has been requested
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_initially_not_cancelled() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        clone.cancel();
+
+        assert!(token.is_cancelled());
+        assert!(clone.is_cancelled());
+    }
+}
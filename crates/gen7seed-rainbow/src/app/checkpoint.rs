@@ -0,0 +1,798 @@
+//! Checkpointed table generation workflow
+//!
+//! Generating all tables takes hours. This module generates them in chunks,
+//! writing each finished chunk and each finished table to a work directory,
+//! so that an interrupted run can be resumed without redoing completed work.
+//! The final table file is written atomically from the finished checkpoints.
+//!
+//! With coverage-guided start points, the coverage of resumed checkpoints is
+//! restored by replaying their chains before generation continues.
+
+use crate::app::cancel::CancellationToken;
+use crate::app::generator::{
+    GenerateOptions, MergeStats, generate_table, generate_table_guided, make_table_perfect,
+    mark_chain_seeds,
+};
+use crate::constants::CHECKPOINT_CHUNK_SIZE;
+use crate::domain::chain::ChainEntry;
+use crate::domain::checkpoint_format::CheckpointHeader;
+use crate::domain::coverage::SeedBitmap;
+use crate::domain::table_format::{
+    StartPoints, TableFormatError, TableHeader, TableParams, ValidationOptions,
+};
+use crate::infra::checkpoint_io::{
+    find_checkpoints, get_chunk_checkpoint_path, get_table_checkpoint_path, load_checkpoint,
+    save_checkpoint,
+};
+use crate::infra::table_io::{save_compact_table, save_single_table_with_header};
+use crate::infra::table_sort::{sort_table_parallel, sort_table_with_end_hashes};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Progress of checkpointed generation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckpointEvent {
+    /// A checkpoint from a previous run was reused
+    Resumed { table_id: u32, range: Range<u32> },
+    /// Chains generated so far in a table (including resumed chunks)
+    Generating {
+        table_id: u32,
+        done: u32,
+        total: u32,
+    },
+    /// All chunks of a table are done and the table is being sorted
+    Sorting { table_id: u32 },
+    /// Merged chains of a table were replaced (perfect tables only)
+    Perfected { table_id: u32, stats: MergeStats },
+    /// A table checkpoint was written
+    TableDone { table_id: u32 },
+    /// The final table file is being written
+    Writing,
+}
+
+/// Summary of a checkpointed generation run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CheckpointSummary {
+    /// Chunks generated in this run
+    pub chunks_generated: u32,
+    /// Chunks reused from a previous run
+    pub chunks_resumed: u32,
+    /// Finished tables reused from a previous run
+    pub tables_resumed: u32,
+}
+
+/// Checkpointed generation errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// Checkpoints from a previous run exist, but resuming was not requested
+    ExistingCheckpoints(PathBuf),
+    /// A checkpoint was written with different options or for a different range
+    OptionsMismatch(PathBuf),
+    /// Generation was cancelled (finished checkpoints are kept)
+    Cancelled,
+    /// Table or checkpoint file error
+    Format(TableFormatError),
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExistingCheckpoints(dir) => write!(
+                f,
+                "Checkpoints from a previous run found in {} (resume, or delete them to start over)",
+                dir.display()
+            ),
+            Self::OptionsMismatch(path) => write!(
+                f,
+                "Checkpoint {} was created with different options",
+                path.display()
+            ),
+            Self::Cancelled => write!(f, "Generation was cancelled"),
+            Self::Format(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<TableFormatError> for CheckpointError {
+    fn from(e: TableFormatError) -> Self {
+        Self::Format(e)
+    }
+}
+
+impl From<std::io::Error> for CheckpointError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Format(e.into())
+    }
+}
+
+/// Options for checkpointed generation
+#[derive(Clone)]
+pub struct CheckpointOptions<F = fn(&CheckpointEvent)> {
+    /// Directory for checkpoint files
+    pub work_dir: PathBuf,
+    /// Table parameters (default: compile-time constants)
+    pub params: TableParams,
+    /// Reuse checkpoints from a previous run (default: false)
+    pub resume: bool,
+    /// Sort the tables (default: true)
+    pub sort: bool,
+    /// Store the end hash of every chain; requires sorting (default: false)
+    pub end_hashes: bool,
+    /// How the start seeds are chosen (default: `StartPoints::Sequential`)
+    pub start_points: StartPoints,
+    /// Replace merged chains so that every end hash is distinct; requires sorting (default: false)
+    pub perfect: bool,
+    /// Write the table file in the compact format; requires sorting (default: false)
+    pub compact: bool,
+    /// Creation timestamp recorded in the table file (default: `creation_timestamp()`)
+    pub created_at: Option<u64>,
+    /// Number of chains per chunk checkpoint (default: CHECKPOINT_CHUNK_SIZE)
+    pub chunk_size: u32,
+    /// Cancellation token, checked between chunks (default: None)
+    pub cancel: Option<CancellationToken>,
+    /// Progress callback
+    pub on_progress: Option<F>,
+}
+
+impl CheckpointOptions<fn(&CheckpointEvent)> {
+    /// Create options writing checkpoints to `work_dir`
+    pub fn new(work_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            work_dir: work_dir.into(),
+            params: TableParams::default(),
+            resume: false,
+            sort: true,
+            end_hashes: false,
+            start_points: StartPoints::Sequential,
+            perfect: false,
+            compact: false,
+            created_at: None,
+            chunk_size: CHECKPOINT_CHUNK_SIZE,
+            cancel: None,
+            on_progress: None,
+        }
+    }
+}
+
+impl<F> CheckpointOptions<F> {
+    /// Set the table parameters
+    pub fn with_params(mut self, params: TableParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Reuse checkpoints from a previous run
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Set whether the tables are sorted
+    pub fn with_sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Store the end hash of every chain
+    pub fn with_end_hashes(mut self, end_hashes: bool) -> Self {
+        self.end_hashes = end_hashes;
+        self
+    }
+
+    /// Set how the start seeds are chosen
+    ///
+    /// `StartPoints::CoverageGuided` keeps a 512 MB `SeedBitmap` during generation.
+    pub fn with_start_points(mut self, start_points: StartPoints) -> Self {
+        self.start_points = start_points;
+        self
+    }
+
+    /// Generate perfect tables
+    ///
+    /// After all chunks of a table are done, chains whose end hash collides
+    /// with another chain are removed and replaced by chains from fresh start
+    /// seeds (`chains_per_table..` for sequential start points). With
+    /// coverage-guided start points, the seeds of removed chains stay marked as
+    /// covered, so a resumed run may choose other start seeds than an
+    /// uninterrupted one.
+    pub fn with_perfect(mut self, perfect: bool) -> Self {
+        self.perfect = perfect;
+        self
+    }
+
+    /// Write the table file in the compact format (see `save_compact_table`)
+    ///
+    /// The table checkpoints then keep the end hashes of their chains.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Record a fixed creation timestamp (Unix epoch seconds) in the table file
+    ///
+    /// Runs with the same options and timestamp write byte-identical files.
+    pub fn with_created_at(mut self, created_at: u64) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    /// Set the number of chains per chunk checkpoint (at least 1)
+    pub fn with_chunk_size(mut self, chunk_size: u32) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Set the cancellation token
+    pub fn with_cancel(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Set the progress callback
+    pub fn with_progress<G>(self, callback: G) -> CheckpointOptions<G> {
+        CheckpointOptions {
+            work_dir: self.work_dir,
+            params: self.params,
+            resume: self.resume,
+            sort: self.sort,
+            end_hashes: self.end_hashes,
+            start_points: self.start_points,
+            perfect: self.perfect,
+            compact: self.compact,
+            created_at: self.created_at,
+            chunk_size: self.chunk_size,
+            cancel: self.cancel,
+            on_progress: Some(callback),
+        }
+    }
+}
+
+/// Generate all tables with checkpoints and write them to `output_path`
+///
+/// Each chunk of `chunk_size` chains is saved to the work directory as soon
+/// as it is generated; once all chunks of a table exist, the table is sorted
+/// and saved as a single checkpoint. With `resume`, existing checkpoints are
+/// validated against the consumption and table parameters and reused.
+/// On success, the table file is written atomically and the checkpoints are
+/// deleted.
+pub fn generate_tables_with_checkpoints<F>(
+    consumption: i32,
+    output_path: impl AsRef<Path>,
+    options: CheckpointOptions<F>,
+) -> Result<CheckpointSummary, CheckpointError>
+where
+    F: Fn(&CheckpointEvent) + Sync,
+{
+    let params = options.params;
+    params.validate()?;
+
+    let work_dir = options.work_dir.as_path();
+    if !options.resume && !find_checkpoints(work_dir, consumption)?.is_empty() {
+        return Err(CheckpointError::ExistingCheckpoints(work_dir.to_path_buf()));
+    }
+
+    let emit = |event: CheckpointEvent| {
+        if let Some(ref callback) = options.on_progress {
+            callback(&event);
+        }
+    };
+    let is_cancelled = || {
+        options
+            .cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    };
+
+    let compact = options.compact && options.sort;
+    let end_hashes = (options.end_hashes || compact) && options.sort;
+    let perfect = options.perfect && options.sort;
+    let validation = ValidationOptions {
+        expected_consumption: Some(consumption),
+        require_sorted: false,
+        expected_params: Some(params),
+    };
+    let num_chains = params.chains_per_table;
+    let start_points = options.start_points;
+    let bitmap = (start_points == StartPoints::CoverageGuided).then(SeedBitmap::new);
+    let restore_coverage = |entries: &[ChainEntry], table_id: u32| {
+        if let Some(bitmap) = &bitmap {
+            mark_chain_seeds(bitmap, entries, consumption, table_id, params.chain_length);
+        }
+    };
+
+    let mut summary = CheckpointSummary::default();
+    let mut tables = Vec::with_capacity(params.num_tables as usize);
+    let mut table_hashes = Vec::new();
+
+    for table_id in 0..params.num_tables {
+        // Reuse a finished table
+        let table_path = get_table_checkpoint_path(work_dir, consumption, table_id);
+        if options.resume && table_path.exists() {
+            let (header, entries, hashes) = load_checkpoint(&table_path, &validation)?;
+            if header.table_id != table_id
+                || header.range() != (0..num_chains)
+                || header.is_sorted() != options.sort
+                || header.has_end_hashes() != end_hashes
+                || header.start_points() != start_points
+                || header.is_perfect() != perfect
+            {
+                return Err(CheckpointError::OptionsMismatch(table_path));
+            }
+            restore_coverage(&entries, table_id);
+            emit(CheckpointEvent::Resumed {
+                table_id,
+                range: header.range(),
+            });
+            summary.tables_resumed += 1;
+            tables.push(entries);
+            table_hashes.extend(hashes);
+            continue;
+        }
+
+        let mut entries: Vec<ChainEntry> = Vec::with_capacity(num_chains as usize);
+        let mut chunk_paths = Vec::new();
+
+        for start in (0..num_chains).step_by(options.chunk_size as usize) {
+            if is_cancelled() {
+                return Err(CheckpointError::Cancelled);
+            }
+
+            let range = start..(start + options.chunk_size).min(num_chains);
+            let chunk_path =
+                get_chunk_checkpoint_path(work_dir, consumption, table_id, range.clone());
+
+            if options.resume && chunk_path.exists() {
+                let (header, chunk, _) = load_checkpoint(&chunk_path, &validation)?;
+                if header.table_id != table_id
+                    || header.range() != range
+                    || header.flags != start_points.flags()
+                {
+                    return Err(CheckpointError::OptionsMismatch(chunk_path));
+                }
+                restore_coverage(&chunk, table_id);
+                emit(CheckpointEvent::Resumed {
+                    table_id,
+                    range: range.clone(),
+                });
+                summary.chunks_resumed += 1;
+                entries.extend(chunk);
+            } else {
+                let generate_options = GenerateOptions::default()
+                    .with_params(params)
+                    .with_table_id(table_id)
+                    .with_range(range.start, range.end)
+                    .with_progress(|current, _total| {
+                        emit(CheckpointEvent::Generating {
+                            table_id,
+                            done: range.start + current,
+                            total: num_chains,
+                        });
+                    });
+                let chunk = match &bitmap {
+                    Some(bitmap) => generate_table_guided(consumption, bitmap, generate_options),
+                    None => generate_table(consumption, generate_options),
+                };
+                let mut header =
+                    CheckpointHeader::new(consumption, params, table_id, range.clone());
+                header.set_start_points(start_points);
+                save_checkpoint(&chunk_path, &header, &chunk, None)?;
+                summary.chunks_generated += 1;
+                entries.extend(chunk);
+            }
+            chunk_paths.push(chunk_path);
+        }
+
+        // Sort the finished table and replace its chunks with a single checkpoint
+        let mut hashes = None;
+        if options.sort {
+            emit(CheckpointEvent::Sorting { table_id });
+            if perfect {
+                let mut next_start = num_chains;
+                let (perfect_entries, perfect_hashes, stats) =
+                    make_table_perfect(entries, num_chains, consumption, |count| {
+                        let start = next_start;
+                        next_start = next_start.saturating_add(count);
+                        let generate_options = GenerateOptions::default()
+                            .with_params(params)
+                            .with_table_id(table_id)
+                            .with_range(start, next_start);
+                        match &bitmap {
+                            Some(bitmap) => {
+                                generate_table_guided(consumption, bitmap, generate_options)
+                            }
+                            None => generate_table(consumption, generate_options),
+                        }
+                    });
+                entries = perfect_entries;
+                hashes = end_hashes.then_some(perfect_hashes);
+                emit(CheckpointEvent::Perfected { table_id, stats });
+            } else if end_hashes {
+                hashes = Some(sort_table_with_end_hashes(&mut entries, consumption));
+            } else {
+                sort_table_parallel(&mut entries, consumption);
+            }
+        }
+
+        let mut header = CheckpointHeader::new(consumption, params, table_id, 0..num_chains);
+        header.set_sorted(options.sort);
+        header.set_start_points(start_points);
+        header.set_perfect(perfect);
+        save_checkpoint(&table_path, &header, &entries, hashes.as_deref())?;
+        for path in chunk_paths {
+            fs::remove_file(path).ok();
+        }
+        emit(CheckpointEvent::TableDone { table_id });
+
+        tables.push(entries);
+        table_hashes.extend(hashes);
+    }
+
+    if is_cancelled() {
+        return Err(CheckpointError::Cancelled);
+    }
+
+    emit(CheckpointEvent::Writing);
+    let mut header = TableHeader::with_params(consumption, params, options.sort);
+    if let Some(created_at) = options.created_at {
+        header.created_at = created_at;
+    }
+    header.set_start_points(start_points);
+    header.set_perfect(perfect);
+    if compact {
+        save_compact_table(&output_path, &header, &tables, &table_hashes)?;
+    } else {
+        let table_hashes = end_hashes.then_some(table_hashes.as_slice());
+        save_single_table_with_header(&output_path, &header, &tables, table_hashes)?;
+    }
+
+    for path in find_checkpoints(work_dir, consumption)? {
+        fs::remove_file(path).ok();
+    }
+    // Only removed if no other files are left
+    fs::remove_dir(work_dir).ok();
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::table_io::load_single_table;
+    use serial_test::serial;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    const CHUNK: u32 = 16;
+    const PARAMS: TableParams = TableParams {
+        chain_length: 32,
+        chains_per_table: 40,
+        num_tables: 4,
+    };
+
+    fn options(dir: &TempDir) -> CheckpointOptions {
+        CheckpointOptions::new(dir.path().join("work"))
+            .with_params(PARAMS)
+            .with_chunk_size(CHUNK)
+    }
+
+    /// Write a finished (sorted) table checkpoint with placeholder chains
+    fn save_placeholder_table(work_dir: &Path, table_id: u32, sorted: bool) -> Vec<ChainEntry> {
+        let num_chains = PARAMS.chains_per_table;
+        let entries: Vec<ChainEntry> = (0..num_chains)
+            .map(|i| ChainEntry::new(i, table_id * num_chains + i))
+            .collect();
+        let mut header = CheckpointHeader::new(417, PARAMS, table_id, 0..num_chains);
+        header.set_sorted(sorted);
+        save_checkpoint(
+            get_table_checkpoint_path(work_dir, 417, table_id),
+            &header,
+            &entries,
+            None,
+        )
+        .unwrap();
+        entries
+    }
+
+    #[test]
+    fn test_resume_generates_remaining_work() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("417.g7rt");
+        let work_dir = dir.path().join("work");
+
+        // Simulate an interrupted run: all tables but 2 finished, first chunk of table 2 done
+        let placeholders: Vec<Vec<ChainEntry>> = (0..PARAMS.num_tables)
+            .map(|table_id| match table_id {
+                2 => Vec::new(),
+                _ => save_placeholder_table(&work_dir, table_id, true),
+            })
+            .collect();
+        let chunk = generate_table(
+            417,
+            GenerateOptions::default()
+                .with_params(PARAMS)
+                .with_table_id(2)
+                .with_range(0, CHUNK),
+        );
+        save_checkpoint(
+            get_chunk_checkpoint_path(&work_dir, 417, 2, 0..CHUNK),
+            &CheckpointHeader::new(417, PARAMS, 2, 0..CHUNK),
+            &chunk,
+            None,
+        )
+        .unwrap();
+
+        // Without resume, existing checkpoints are an error
+        let result = generate_tables_with_checkpoints(417, &output, options(&dir));
+        assert!(matches!(
+            result,
+            Err(CheckpointError::ExistingCheckpoints(_))
+        ));
+
+        let resumed = Mutex::new(Vec::new());
+        let summary = generate_tables_with_checkpoints(
+            417,
+            &output,
+            options(&dir)
+                .with_resume(true)
+                .with_progress(|event: &CheckpointEvent| {
+                    if let CheckpointEvent::Resumed { table_id: 2, range } = event {
+                        resumed.lock().unwrap().push(range.clone());
+                    }
+                }),
+        )
+        .unwrap();
+
+        assert_eq!(summary.tables_resumed, PARAMS.num_tables - 1);
+        assert_eq!(summary.chunks_resumed, 1);
+        assert_eq!(
+            summary.chunks_generated,
+            PARAMS.chains_per_table.div_ceil(CHUNK) - 1
+        );
+        assert_eq!(resumed.into_inner().unwrap(), vec![0..CHUNK]);
+
+        let (header, tables) =
+            load_single_table(&output, &ValidationOptions::for_search(417)).unwrap();
+        assert!(header.is_sorted());
+        assert_eq!(header.params(), PARAMS);
+        assert_eq!(tables[0], placeholders[0]);
+
+        let mut expected = generate_table(
+            417,
+            GenerateOptions::default()
+                .with_params(PARAMS)
+                .with_table_id(2),
+        );
+        sort_table_parallel(&mut expected, 417);
+        assert_eq!(tables[2], expected);
+
+        // Checkpoints and the work directory are removed on success
+        assert!(!work_dir.exists());
+    }
+
+    #[test]
+    fn test_resume_rejects_mismatched_checkpoint() {
+        let dir = TempDir::new().unwrap();
+
+        // Unsorted table checkpoint, but the run asks for sorted tables
+        save_placeholder_table(&dir.path().join("work"), 0, false);
+
+        let result = generate_tables_with_checkpoints(
+            417,
+            dir.path().join("417.g7rt"),
+            options(&dir).with_resume(true),
+        );
+        assert!(matches!(result, Err(CheckpointError::OptionsMismatch(_))));
+    }
+
+    #[test]
+    fn test_resume_rejects_different_params() {
+        let dir = TempDir::new().unwrap();
+        save_placeholder_table(&dir.path().join("work"), 0, true);
+
+        let result = generate_tables_with_checkpoints(
+            417,
+            dir.path().join("417.g7rt"),
+            options(&dir)
+                .with_params(TableParams::new(64, 40, 4))
+                .with_resume(true),
+        );
+        assert!(matches!(
+            result,
+            Err(CheckpointError::Format(
+                TableFormatError::ChainLengthMismatch { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_cancelled_keeps_finished_chunks() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("417.g7rt");
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+
+        // Cancel while the first chunk is being generated
+        let result = generate_tables_with_checkpoints(
+            417,
+            &output,
+            options(&dir)
+                .with_cancel(token)
+                .with_progress(move |_: &CheckpointEvent| cancel.cancel()),
+        );
+
+        assert_eq!(result, Err(CheckpointError::Cancelled));
+        assert!(!output.exists());
+        assert_eq!(
+            find_checkpoints(dir.path().join("work"), 417).unwrap(),
+            vec![get_chunk_checkpoint_path(
+                dir.path().join("work"),
+                417,
+                0,
+                0..CHUNK
+            )]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_coverage_guided_resume_matches_uninterrupted() {
+        let guided = |dir: &TempDir| options(dir).with_start_points(StartPoints::CoverageGuided);
+
+        let full_dir = TempDir::new().unwrap();
+        let full_output = full_dir.path().join("417.g7rt");
+        generate_tables_with_checkpoints(417, &full_output, guided(&full_dir)).unwrap();
+
+        // Interrupt after two finished tables and the first chunk of table 2
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("417.g7rt");
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let result = generate_tables_with_checkpoints(
+            417,
+            &output,
+            guided(&dir)
+                .with_cancel(token)
+                .with_progress(move |event: &CheckpointEvent| {
+                    if let CheckpointEvent::Generating {
+                        table_id: 2,
+                        done: CHUNK,
+                        ..
+                    } = event
+                    {
+                        cancel.cancel();
+                    }
+                }),
+        );
+        assert_eq!(result, Err(CheckpointError::Cancelled));
+
+        // Resuming as sequential generation is rejected
+        let result =
+            generate_tables_with_checkpoints(417, &output, options(&dir).with_resume(true));
+        assert!(matches!(result, Err(CheckpointError::OptionsMismatch(_))));
+
+        let summary =
+            generate_tables_with_checkpoints(417, &output, guided(&dir).with_resume(true)).unwrap();
+        assert_eq!(summary.tables_resumed, 2);
+        assert_eq!(summary.chunks_resumed, 1);
+
+        let validation = ValidationOptions::for_search(417);
+        let (header, tables) = load_single_table(&output, &validation).unwrap();
+        let (_, expected) = load_single_table(&full_output, &validation).unwrap();
+        assert_eq!(header.start_points(), StartPoints::CoverageGuided);
+        assert_eq!(tables, expected);
+    }
+
+    #[test]
+    fn test_perfect_tables_report_merges() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("417.g7rt");
+        let perfected = Mutex::new(Vec::new());
+
+        generate_tables_with_checkpoints(
+            417,
+            &output,
+            options(&dir)
+                .with_perfect(true)
+                .with_end_hashes(true)
+                .with_progress(|event: &CheckpointEvent| {
+                    if let CheckpointEvent::Perfected { table_id, stats } = event {
+                        perfected.lock().unwrap().push((*table_id, *stats));
+                    }
+                }),
+        )
+        .unwrap();
+
+        let perfected = perfected.into_inner().unwrap();
+        assert_eq!(
+            perfected.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            (0..PARAMS.num_tables).collect::<Vec<_>>()
+        );
+        for (_, stats) in &perfected {
+            assert_eq!(
+                stats.generated - stats.merged,
+                PARAMS.chains_per_table as u64
+            );
+        }
+
+        let table = crate::infra::table_io::MappedSingleTable::open(
+            &output,
+            &ValidationOptions::for_search(417),
+        )
+        .unwrap();
+        assert!(table.header().is_perfect());
+        for table_id in 0..PARAMS.num_tables {
+            let end_hashes = table.le_end_hashes(table_id).unwrap().to_vec();
+            assert!(end_hashes.windows(2).all(|w| w[0] < w[1]));
+        }
+
+        // A perfect run cannot resume from a table checkpoint that is not perfect
+        save_placeholder_table(&dir.path().join("work"), 0, true);
+        let result = generate_tables_with_checkpoints(
+            417,
+            &output,
+            options(&dir).with_perfect(true).with_resume(true),
+        );
+        assert!(matches!(result, Err(CheckpointError::OptionsMismatch(_))));
+    }
+
+    #[test]
+    fn test_compact_output_matches_end_hash_output() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("417.g7rt");
+        let compact_output = dir.path().join("compact").join("417.g7rt");
+
+        generate_tables_with_checkpoints(417, &output, options(&dir).with_end_hashes(true))
+            .unwrap();
+        generate_tables_with_checkpoints(417, &compact_output, options(&dir).with_compact(true))
+            .unwrap();
+
+        let validation = ValidationOptions::for_search(417);
+        let table = crate::infra::table_io::MappedSingleTable::open(&output, &validation).unwrap();
+        let compact =
+            crate::infra::table_io::MappedSingleTable::open(&compact_output, &validation).unwrap();
+        assert!(compact.header().is_compact());
+        assert!(
+            std::fs::metadata(&compact_output).unwrap().len()
+                < std::fs::metadata(&output).unwrap().len()
+        );
+
+        for table_id in 0..PARAMS.num_tables {
+            let expected = table.table_ref(table_id).unwrap();
+            let actual = compact.table_ref(table_id).unwrap();
+            assert_eq!(actual.len(), expected.len());
+            for i in 0..expected.len() {
+                assert_eq!(actual.start_seed(i), expected.start_seed(i));
+                assert_eq!(actual.end_hash(i, 417), expected.end_hash(i, 417));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_timestamp_output_is_reproducible() {
+        let dir = TempDir::new().unwrap();
+        let first = dir.path().join("first").join("417.g7rt");
+        let second = dir.path().join("second").join("417.g7rt");
+
+        for output in [&first, &second] {
+            generate_tables_with_checkpoints(
+                417,
+                output,
+                options(&dir).with_created_at(1_700_000_000),
+            )
+            .unwrap();
+        }
+
+        let bytes = std::fs::read(&first).unwrap();
+        assert_eq!(bytes, std::fs::read(&second).unwrap());
+        let header = TableHeader::from_bytes(
+            bytes[..crate::constants::FILE_HEADER_SIZE]
+                .try_into()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(header.created_at, 1_700_000_000);
+    }
+}
@@ -0,0 +1,297 @@
+//! Conversion between the original table layout and `.g7rt`
+//!
+//! Tables of fujidig/sfmt-rainbow share the chain layout of this crate but
+//! have no header, are not sorted, and use a different reduction function
+//! (see `domain::original_format`). Import checks the reduction function by
+//! recomputing sample chains and refuses tables this crate cannot search,
+//! which includes every table generated by the original implementation:
+//! its unsorted files use the original reduction and its sorted files hold
+//! truncated end hashes instead of end seeds.
+
+use crate::domain::chain::ChainEntry;
+use crate::domain::original_format::{
+    DEFAULT_REDUCTION_SAMPLES, ORIGINAL_CHAIN_LENGTH, Reduction, detect_reduction,
+    holds_truncated_end_hashes,
+};
+use crate::domain::table_format::{TableFormatError, TableHeader, TableParams};
+use crate::infra::table_io::SingleTableView;
+use crate::infra::table_sort::sort_table_parallel;
+
+/// Options for importing original tables
+#[derive(Clone, Debug)]
+pub struct ImportOptions {
+    /// RNG consumption value the tables were generated for
+    pub consumption: i32,
+    /// Chain length the tables were generated with (default: ORIGINAL_CHAIN_LENGTH)
+    pub chain_length: u32,
+    /// Number of chains recomputed per table (default: DEFAULT_REDUCTION_SAMPLES)
+    pub samples: usize,
+}
+
+impl ImportOptions {
+    /// Create options for the given consumption
+    pub fn new(consumption: i32) -> Self {
+        Self {
+            consumption,
+            chain_length: ORIGINAL_CHAIN_LENGTH,
+            samples: DEFAULT_REDUCTION_SAMPLES,
+        }
+    }
+
+    /// Set the chain length
+    pub fn with_chain_length(mut self, chain_length: u32) -> Self {
+        self.chain_length = chain_length;
+        self
+    }
+
+    /// Set the number of chains recomputed per table
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples;
+        self
+    }
+}
+
+/// Table conversion errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+    /// Chains were generated with the reduction function of the original implementation
+    ReductionMismatch { table_id: u32 },
+    /// Table is a sorted file of the original implementation, holding
+    /// truncated end hashes instead of end seeds
+    TruncatedEndHashes { table_id: u32 },
+    /// Chains match neither reduction function
+    ChainMismatch { table_id: u32 },
+    /// Table file error
+    Format(TableFormatError),
+}
+
+impl std::fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReductionMismatch { table_id } => write!(
+                f,
+                "Table {} uses the reduction function of fujidig/sfmt-rainbow \
+                 ((hash + column) mod 2^32 without a table salt), while this crate uses a \
+                 salted SplitMix64 reduction; its chains cannot be searched here, so the \
+                 table has to be regenerated",
+                table_id
+            ),
+            Self::TruncatedEndHashes { table_id } => write!(
+                f,
+                "Table {} is a sorted table of fujidig/sfmt-rainbow (.sorted.bin), which \
+                 stores truncated end hashes instead of end seeds and uses the original \
+                 reduction function; tables of the original implementation cannot be \
+                 imported, so the table has to be regenerated",
+                table_id
+            ),
+            Self::ChainMismatch { table_id } => write!(
+                f,
+                "Chains of table {} match neither reduction function (check the consumption \
+                 and chain length)",
+                table_id
+            ),
+            Self::Format(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl From<TableFormatError> for ConvertError {
+    fn from(e: TableFormatError) -> Self {
+        Self::Format(e)
+    }
+}
+
+impl From<std::io::Error> for ConvertError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Format(e.into())
+    }
+}
+
+/// Import tables in the original layout
+///
+/// `tables` are the chains of each table in table ID order (table `i` must
+/// have been generated with salt `i`). The reduction function of every table
+/// is checked by recomputing sample chains; tables generated with the
+/// original reduction are refused with `ReductionMismatch`, and sorted tables
+/// of the original implementation with `TruncatedEndHashes`. The tables are
+/// sorted with `sort_table_parallel`, and the returned header records the
+/// consumption, chain length and table size.
+pub fn import_original_tables(
+    mut tables: Vec<Vec<ChainEntry>>,
+    options: &ImportOptions,
+) -> Result<(TableHeader, Vec<Vec<ChainEntry>>), ConvertError> {
+    let chains_per_table = tables.first().map_or(0, Vec::len) as u32;
+    let params = TableParams::new(options.chain_length, chains_per_table, tables.len() as u32);
+    params.validate()?;
+
+    for (table_id, table) in tables.iter().enumerate() {
+        let table_id = table_id as u32;
+        if table.len() != chains_per_table as usize {
+            return Err(TableFormatError::ChainCountMismatch {
+                expected: chains_per_table,
+                found: table.len() as u32,
+            }
+            .into());
+        }
+
+        match detect_reduction(
+            table,
+            options.consumption,
+            table_id,
+            options.chain_length,
+            options.samples,
+        ) {
+            Some(Reduction::Salted) => {}
+            Some(Reduction::Original) => return Err(ConvertError::ReductionMismatch { table_id }),
+            None if holds_truncated_end_hashes(
+                table,
+                options.consumption,
+                options.chain_length,
+                options.samples,
+            ) =>
+            {
+                return Err(ConvertError::TruncatedEndHashes { table_id });
+            }
+            None => return Err(ConvertError::ChainMismatch { table_id }),
+        }
+    }
+
+    for table in &mut tables {
+        sort_table_parallel(table, options.consumption);
+    }
+
+    let header = TableHeader::with_params(options.consumption, params, true);
+    Ok((header, tables))
+}
+
+/// Export the chains of every table in the original layout
+///
+/// The chains keep the reduction function of this crate, so the search of
+/// the original implementation cannot use them; the export is meant for
+/// tools sharing this crate's reduction and for re-importing. Compact tables
+/// have no end seeds and are rejected.
+pub fn export_original_tables<B: AsRef<[u8]>>(
+    table: &SingleTableView<B>,
+) -> Result<Vec<Vec<ChainEntry>>, ConvertError> {
+    if table.header().is_compact() {
+        return Err(TableFormatError::CompactNotSupported.into());
+    }
+
+    Ok((0..table.num_tables())
+        .filter_map(|table_id| table.entries(table_id))
+        .map(|entries| entries.to_vec())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chain::compute_chain;
+    use crate::domain::hash::gen_hash_from_seed;
+    use crate::domain::original_format::compute_original_chain;
+    use crate::domain::table_format::ValidationOptions;
+    use crate::infra::table_io::write_single_table;
+
+    const CHAIN_LENGTH: u32 = 8;
+
+    fn salted_tables() -> Vec<Vec<ChainEntry>> {
+        (0..2)
+            .map(|table_id| {
+                (0..32)
+                    .map(|seed| compute_chain(seed * 3, 417, table_id, CHAIN_LENGTH))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_import_sorts_and_fills_header() {
+        let options = ImportOptions::new(417).with_chain_length(CHAIN_LENGTH);
+        let (header, tables) = import_original_tables(salted_tables(), &options).unwrap();
+
+        assert!(header.is_sorted());
+        assert_eq!(header.params(), TableParams::new(CHAIN_LENGTH, 32, 2));
+        for (table, original) in tables.iter().zip(salted_tables()) {
+            let mut expected = original;
+            sort_table_parallel(&mut expected, 417);
+            assert_eq!(table, &expected);
+        }
+    }
+
+    #[test]
+    fn test_import_refuses_original_reduction() {
+        let mut tables = salted_tables();
+        tables[1] = (0..32)
+            .map(|seed| compute_original_chain(seed, 417, CHAIN_LENGTH))
+            .collect();
+        let options = ImportOptions::new(417).with_chain_length(CHAIN_LENGTH);
+
+        let result = import_original_tables(tables, &options);
+        assert_eq!(result, Err(ConvertError::ReductionMismatch { table_id: 1 }));
+        assert!(result.unwrap_err().to_string().contains("regenerated"));
+    }
+
+    #[test]
+    fn test_import_refuses_original_sorted_table() {
+        let mut tables = salted_tables();
+        tables[0] = (0..32)
+            .map(|seed| {
+                let end_seed = compute_original_chain(seed, 417, CHAIN_LENGTH).end_seed;
+                ChainEntry {
+                    start_seed: seed,
+                    end_seed: gen_hash_from_seed(end_seed, 417) as u32,
+                }
+            })
+            .collect();
+        let options = ImportOptions::new(417).with_chain_length(CHAIN_LENGTH);
+
+        let result = import_original_tables(tables, &options);
+        assert_eq!(
+            result,
+            Err(ConvertError::TruncatedEndHashes { table_id: 0 })
+        );
+        assert!(result.unwrap_err().to_string().contains(".sorted.bin"));
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_chains() {
+        let options = ImportOptions::new(477).with_chain_length(CHAIN_LENGTH);
+        assert_eq!(
+            import_original_tables(salted_tables(), &options),
+            Err(ConvertError::ChainMismatch { table_id: 0 })
+        );
+
+        let mut tables = salted_tables();
+        tables[1].pop();
+        let options = ImportOptions::new(417).with_chain_length(CHAIN_LENGTH);
+        assert!(matches!(
+            import_original_tables(tables, &options),
+            Err(ConvertError::Format(
+                TableFormatError::ChainCountMismatch { .. }
+            ))
+        ));
+        assert!(matches!(
+            import_original_tables(Vec::new(), &options),
+            Err(ConvertError::Format(TableFormatError::InvalidParams(_)))
+        ));
+    }
+
+    #[test]
+    fn test_export_roundtrip() {
+        let options = ImportOptions::new(417).with_chain_length(CHAIN_LENGTH);
+        let (header, tables) = import_original_tables(salted_tables(), &options).unwrap();
+
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        write_single_table(&mut cursor, &header, &tables, None).unwrap();
+        let bytes = cursor.into_inner();
+        let view =
+            SingleTableView::from_bytes(bytes.as_slice(), &ValidationOptions::default()).unwrap();
+
+        let exported = export_original_tables(&view).unwrap();
+        assert_eq!(exported, tables);
+        let (_, reimported) = import_original_tables(exported, &options).unwrap();
+        assert_eq!(reimported, tables);
+    }
+}
@@ -3,7 +3,8 @@
 //! This module provides functions for building seed bitmaps and extracting
 //! seeds that are not reachable from any chain in the rainbow table.
 
-use crate::domain::chain::ChainEntry;
+use crate::constants::MAX_CHAIN_LENGTH;
+use crate::domain::chain::ChainTable;
 use crate::domain::coverage::SeedBitmap;
 use crate::domain::missing_format::MissingSeedsHeader;
 use crate::domain::table_format::TableHeader;
@@ -35,6 +36,8 @@ pub struct MissingSeedsResult {
 pub struct BitmapOptions<F = fn(u32, u32)> {
     /// Table ID used as salt (default: 0)
     pub table_id: u32,
+    /// Chain length (default: MAX_CHAIN_LENGTH)
+    pub chain_length: u32,
     /// Progress callback (current, total)
     pub on_progress: Option<F>,
 }
@@ -43,6 +46,7 @@ impl Default for BitmapOptions<fn(u32, u32)> {
     fn default() -> Self {
         Self {
             table_id: 0,
+            chain_length: MAX_CHAIN_LENGTH,
             on_progress: None,
         }
     }
@@ -55,35 +59,67 @@ impl<F> BitmapOptions<F> {
         self
     }
 
+    /// Set the chain length
+    pub fn with_chain_length(mut self, chain_length: u32) -> Self {
+        self.chain_length = chain_length;
+        self
+    }
+
     /// Set the progress callback
     pub fn with_progress<G>(self, callback: G) -> BitmapOptions<G> {
         BitmapOptions {
             table_id: self.table_id,
+            chain_length: self.chain_length,
             on_progress: Some(callback),
         }
     }
 }
 
+/// Split `len` chains into parallel ranges of up to 16 chains
+#[cfg(feature = "multi-sfmt")]
+fn chunk_ranges(len: usize) -> impl ParallelIterator<Item = std::ops::Range<usize>> {
+    (0..len.div_ceil(16))
+        .into_par_iter()
+        .map(move |chunk| chunk * 16..(chunk * 16 + 16).min(len))
+}
+
+/// Start seeds of a range of up to 16 chains
+///
+/// Remaining slots are filled with the first seed (duplicates are fine).
+#[cfg(feature = "multi-sfmt")]
+fn chunk_start_seeds<T: ChainTable + ?Sized>(
+    table: &T,
+    chunk: std::ops::Range<usize>,
+) -> [u32; 16] {
+    let mut start_seeds = [table.start_seed(chunk.start); 16];
+    for (slot, index) in start_seeds.iter_mut().zip(chunk) {
+        *slot = table.start_seed(index);
+    }
+    start_seeds
+}
+
 /// Build a seed bitmap from the table
 ///
 /// Processes all chains in parallel using rayon.
 /// When multi-sfmt feature is enabled, processes 16 chains simultaneously using SIMD.
 ///
 /// # Arguments
-/// * `table` - The rainbow table entries
+/// * `table` - The rainbow table entries (in memory or a `LeChainEntries` view)
 /// * `consumption` - The RNG consumption value
-/// * `options` - Bitmap building options (table_id, progress callback)
+/// * `options` - Bitmap building options (table_id, chain length, progress callback)
 #[cfg(feature = "multi-sfmt")]
-pub fn build_seed_bitmap<F>(
-    table: &[ChainEntry],
+pub fn build_seed_bitmap<T, F>(
+    table: &T,
     consumption: i32,
     options: BitmapOptions<F>,
 ) -> Arc<SeedBitmap>
 where
+    T: ChainTable + ?Sized,
     F: Fn(u32, u32) + Sync,
 {
     let BitmapOptions {
         table_id,
+        chain_length,
         on_progress,
     } = options;
 
@@ -92,17 +128,10 @@ where
     let progress = AtomicU32::new(0);
 
     // Process 16 chains at a time using multi-sfmt
-    table.par_chunks(16).for_each(|chunk| {
-        let mut start_seeds = [0u32; 16];
-        for (i, entry) in chunk.iter().enumerate() {
-            start_seeds[i] = entry.start_seed;
-        }
-        // Fill remaining slots with first seed (duplicates are fine)
-        for i in chunk.len()..16 {
-            start_seeds[i] = start_seeds[0];
-        }
+    chunk_ranges(table.len()).for_each(|chunk| {
+        let start_seeds = chunk_start_seeds(table, chunk.clone());
 
-        enumerate_chain_seeds_x16(start_seeds, consumption, table_id, |seeds| {
+        enumerate_chain_seeds_x16(start_seeds, consumption, table_id, chain_length, |seeds| {
             bitmap.set_batch(seeds);
         });
 
@@ -122,16 +151,18 @@ where
 
 /// Build a seed bitmap from the table (fallback version without multi-sfmt)
 #[cfg(not(feature = "multi-sfmt"))]
-pub fn build_seed_bitmap<F>(
-    table: &[ChainEntry],
+pub fn build_seed_bitmap<T, F>(
+    table: &T,
     consumption: i32,
     options: BitmapOptions<F>,
 ) -> Arc<SeedBitmap>
 where
+    T: ChainTable + ?Sized,
     F: Fn(u32, u32) + Sync,
 {
     let BitmapOptions {
         table_id,
+        chain_length,
         on_progress,
     } = options;
 
@@ -139,8 +170,9 @@ where
     let total = table.len() as u32;
     let progress = AtomicU32::new(0);
 
-    table.par_iter().for_each(|entry| {
-        let seeds = enumerate_chain_seeds(entry.start_seed, consumption, table_id);
+    (0..table.len()).into_par_iter().for_each(|index| {
+        let seeds =
+            enumerate_chain_seeds(table.start_seed(index), consumption, table_id, chain_length);
         for seed in seeds {
             bitmap.set(seed);
         }
@@ -167,14 +199,17 @@ where
 /// # Arguments
 /// * `tables` - Array of (table, table_id) pairs
 /// * `consumption` - The RNG consumption value
+/// * `chain_length` - The chain length of the tables
 /// * `on_progress` - Progress callback (table_id, current, total)
 #[cfg(feature = "multi-sfmt")]
-pub fn build_seed_bitmap_multi_table<F>(
-    tables: &[(Vec<ChainEntry>, u32)],
+pub fn build_seed_bitmap_multi_table<T, F>(
+    tables: &[(T, u32)],
     consumption: i32,
+    chain_length: u32,
     on_progress: F,
 ) -> Arc<SeedBitmap>
 where
+    T: ChainTable,
     F: Fn(u32, u32, u32) + Sync,
 {
     let bitmap = Arc::new(SeedBitmap::new());
@@ -183,16 +218,10 @@ where
         let total = table.len() as u32;
         let progress = AtomicU32::new(0);
 
-        table.par_chunks(16).for_each(|chunk| {
-            let mut start_seeds = [0u32; 16];
-            for (i, entry) in chunk.iter().enumerate() {
-                start_seeds[i] = entry.start_seed;
-            }
-            for i in chunk.len()..16 {
-                start_seeds[i] = start_seeds[0];
-            }
+        chunk_ranges(table.len()).for_each(|chunk| {
+            let start_seeds = chunk_start_seeds(table, chunk.clone());
 
-            enumerate_chain_seeds_x16(start_seeds, consumption, *table_id, |seeds| {
+            enumerate_chain_seeds_x16(start_seeds, consumption, *table_id, chain_length, |seeds| {
                 bitmap.set_batch(seeds);
             });
 
@@ -208,6 +237,47 @@ where
     bitmap
 }
 
+/// Build a seed bitmap from multiple tables (fallback version without multi-sfmt)
+#[cfg(not(feature = "multi-sfmt"))]
+pub fn build_seed_bitmap_multi_table<T, F>(
+    tables: &[(T, u32)],
+    consumption: i32,
+    chain_length: u32,
+    on_progress: F,
+) -> Arc<SeedBitmap>
+where
+    T: ChainTable,
+    F: Fn(u32, u32, u32) + Sync,
+{
+    let bitmap = Arc::new(SeedBitmap::new());
+
+    for (table, table_id) in tables {
+        let total = table.len() as u32;
+        let progress = AtomicU32::new(0);
+
+        (0..table.len()).into_par_iter().for_each(|index| {
+            let seeds = enumerate_chain_seeds(
+                table.start_seed(index),
+                consumption,
+                *table_id,
+                chain_length,
+            );
+            for seed in seeds {
+                bitmap.set(seed);
+            }
+
+            let count = progress.fetch_add(1, Ordering::Relaxed);
+            if count % 10_000 == 0 {
+                on_progress(*table_id, count, total);
+            }
+        });
+
+        on_progress(*table_id, total, total);
+    }
+
+    bitmap
+}
+
 /// Extract missing seeds from the table
 ///
 /// Builds a bitmap of all reachable seeds and extracts those not reachable.
@@ -215,13 +285,14 @@ where
 /// # Arguments
 /// * `table` - The rainbow table entries
 /// * `consumption` - The RNG consumption value
-/// * `options` - Bitmap building options (table_id, progress callback)
-pub fn extract_missing_seeds<F>(
-    table: &[ChainEntry],
+/// * `options` - Bitmap building options (table_id, chain length, progress callback)
+pub fn extract_missing_seeds<T, F>(
+    table: &T,
     consumption: i32,
     options: BitmapOptions<F>,
 ) -> MissingSeedsResult
 where
+    T: ChainTable + ?Sized,
     F: Fn(u32, u32) + Sync,
 {
     let bitmap = build_seed_bitmap(table, consumption, options);
@@ -240,14 +311,18 @@ where
 }
 
 /// Extract missing seeds and build a header from the source table metadata.
-pub fn extract_missing_seeds_with_header<F>(
-    table: &[ChainEntry],
+///
+/// The chain length is taken from the source header.
+pub fn extract_missing_seeds_with_header<T, F>(
+    table: &T,
     source_header: &TableHeader,
     options: BitmapOptions<F>,
 ) -> (MissingSeedsHeader, MissingSeedsResult)
 where
+    T: ChainTable + ?Sized,
     F: Fn(u32, u32) + Sync,
 {
+    let options = options.with_chain_length(source_header.chain_length);
     let result = extract_missing_seeds(table, source_header.consumption, options);
     let header = MissingSeedsHeader::new(source_header, result.missing_count);
     (header, result)
@@ -257,18 +332,24 @@ where
 ///
 /// Builds a combined bitmap from all tables and extracts seeds not reachable
 /// from any table.
-#[cfg(feature = "multi-sfmt")]
-pub fn extract_missing_seeds_multi_table<F>(
-    tables: &[(Vec<ChainEntry>, u32)],
+pub fn extract_missing_seeds_multi_table<T, F>(
+    tables: &[(T, u32)],
     consumption: i32,
+    chain_length: u32,
     on_progress: F,
 ) -> MissingSeedsResult
 where
+    T: ChainTable,
     F: Fn(&str, u32, u32, u32) + Sync,
 {
-    let bitmap = build_seed_bitmap_multi_table(tables, consumption, |table_id, current, total| {
-        on_progress("Building bitmap", table_id, current, total);
-    });
+    let bitmap = build_seed_bitmap_multi_table(
+        tables,
+        consumption,
+        chain_length,
+        |table_id, current, total| {
+            on_progress("Building bitmap", table_id, current, total);
+        },
+    );
 
     let missing_seeds = bitmap.extract_missing_seeds();
     let reachable_count = bitmap.count_reachable();
@@ -284,16 +365,21 @@ where
 }
 
 /// Extract missing seeds from multiple tables and build a header from source metadata.
-#[cfg(feature = "multi-sfmt")]
-pub fn extract_missing_seeds_multi_table_with_header<F>(
-    tables: &[(Vec<ChainEntry>, u32)],
+pub fn extract_missing_seeds_multi_table_with_header<T, F>(
+    tables: &[(T, u32)],
     source_header: &TableHeader,
     on_progress: F,
 ) -> (MissingSeedsHeader, MissingSeedsResult)
 where
+    T: ChainTable,
     F: Fn(&str, u32, u32, u32) + Sync,
 {
-    let result = extract_missing_seeds_multi_table(tables, source_header.consumption, on_progress);
+    let result = extract_missing_seeds_multi_table(
+        tables,
+        source_header.consumption,
+        source_header.chain_length,
+        on_progress,
+    );
     let header = MissingSeedsHeader::new(source_header, result.missing_count);
     (header, result)
 }
@@ -301,11 +387,11 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::chain::compute_chain;
+    use crate::domain::chain::{ChainEntry, LeChainEntries, compute_chain};
 
     fn create_mini_table(size: u32, consumption: i32, table_id: u32) -> Vec<ChainEntry> {
         (0..size)
-            .map(|seed| compute_chain(seed, consumption, table_id))
+            .map(|seed| compute_chain(seed, consumption, table_id, MAX_CHAIN_LENGTH))
             .collect()
     }
 
@@ -363,4 +449,62 @@ mod tests {
         let count1 = bitmap1.count_reachable();
         assert!(count0 > 0 && count1 > 0);
     }
+
+    #[test]
+    fn test_build_seed_bitmap_with_chain_length() {
+        let table = vec![compute_chain(12345, 417, 0, 4)];
+        let bitmap = build_seed_bitmap(&table, 417, BitmapOptions::default().with_chain_length(4));
+
+        assert!(bitmap.count_reachable() <= 5);
+        for seed in crate::domain::chain::enumerate_chain_seeds(12345, 417, 0, 4) {
+            assert!(bitmap.is_set(seed));
+        }
+    }
+
+    #[test]
+    fn test_build_seed_bitmap_multi_table() {
+        let tables = [
+            (create_mini_table(21, 417, 0), 0),
+            (create_mini_table(5, 417, 3), 3),
+        ];
+        let finished = std::sync::Mutex::new(Vec::new());
+
+        let bitmap = build_seed_bitmap_multi_table(
+            &tables,
+            417,
+            MAX_CHAIN_LENGTH,
+            |table_id, current, total| {
+                if current == total {
+                    finished.lock().unwrap().push(table_id);
+                }
+            },
+        );
+        for (table, _) in &tables {
+            for entry in table {
+                assert!(bitmap.is_set(entry.start_seed));
+                assert!(bitmap.is_set(entry.end_seed));
+            }
+        }
+        assert_eq!(*finished.lock().unwrap(), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_build_seed_bitmap_from_le_view() {
+        // 21 chains: not a multiple of the 16-chain batch
+        let table = create_mini_table(21, 417, 0);
+        let bytes: Vec<u8> = table
+            .iter()
+            .flat_map(|e| [e.start_seed.to_le_bytes(), e.end_seed.to_le_bytes()])
+            .flatten()
+            .collect();
+        let view = LeChainEntries::new(&bytes).unwrap();
+
+        let expected = build_seed_bitmap(&table, 417, BitmapOptions::default());
+        let bitmap = build_seed_bitmap(&view, 417, BitmapOptions::default());
+        assert_eq!(bitmap.count_reachable(), expected.count_reachable());
+        for entry in &table {
+            assert!(bitmap.is_set(entry.start_seed));
+            assert!(bitmap.is_set(entry.end_seed));
+        }
+    }
 }
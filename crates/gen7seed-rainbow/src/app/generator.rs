@@ -1,18 +1,32 @@
 //! Table generation workflow
 //!
 //! This module provides a unified function for generating rainbow tables
-//! with configurable options for range, table_id, and progress reporting.
+//! with configurable options for range, table_id, table parameters and
+//! progress reporting, a coverage-guided variant that chooses the start
+//! seeds from seeds not yet covered by earlier chains, and perfect table
+//! generation that replaces merged chains.
 
-use crate::constants::{NUM_CHAINS, NUM_TABLES};
+use crate::constants::{MAX_CHAIN_LENGTH, NUM_CHAINS};
 use crate::domain::chain::{ChainEntry, compute_chain};
+use crate::domain::coverage::SeedBitmap;
+use crate::domain::table_format::TableParams;
+use crate::infra::table_sort::{deduplicate_table_with_end_hashes, sort_table_with_end_hashes};
 use rayon::prelude::*;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 #[cfg(feature = "multi-sfmt")]
-use crate::domain::chain::compute_chains_x16;
+use crate::domain::chain::{compute_chains_x16, enumerate_chain_seeds_x16};
+#[cfg(not(feature = "multi-sfmt"))]
+use crate::domain::hash::{gen_hash_from_seed, reduce_hash_with_salt};
 
 const PROGRESS_INTERVAL: u32 = 10_000;
 
+/// Number of start seeds chosen together in coverage-guided generation
+///
+/// The chains of a batch are computed in parallel, so their start seeds are
+/// chosen before any of them is marked as covered.
+const GUIDED_BATCH_SIZE: u32 = 4096;
+
 /// Options for table generation
 #[derive(Clone)]
 pub struct GenerateOptions<F = fn(u32, u32)> {
@@ -22,6 +36,8 @@ pub struct GenerateOptions<F = fn(u32, u32)> {
     pub end: u32,
     /// Table ID used as salt (default: 0)
     pub table_id: u32,
+    /// Chain length (default: MAX_CHAIN_LENGTH)
+    pub chain_length: u32,
     /// Progress callback (current, total)
     pub on_progress: Option<F>,
 }
@@ -32,12 +48,24 @@ impl Default for GenerateOptions<fn(u32, u32)> {
             start: 0,
             end: NUM_CHAINS,
             table_id: 0,
+            chain_length: MAX_CHAIN_LENGTH,
             on_progress: None,
         }
     }
 }
 
 impl<F> GenerateOptions<F> {
+    /// Set the table parameters
+    ///
+    /// Uses their chain length and resets the range to the whole table
+    /// (`0..chains_per_table`); call `with_range` afterwards to narrow it.
+    pub fn with_params(mut self, params: TableParams) -> Self {
+        self.start = 0;
+        self.end = params.chains_per_table;
+        self.chain_length = params.chain_length;
+        self
+    }
+
     /// Set the generation range
     pub fn with_range(mut self, start: u32, end: u32) -> Self {
         self.start = start;
@@ -57,6 +85,7 @@ impl<F> GenerateOptions<F> {
             start: self.start,
             end: self.end,
             table_id: self.table_id,
+            chain_length: self.chain_length,
             on_progress: Some(callback),
         }
     }
@@ -83,6 +112,10 @@ impl<F> GenerateOptions<F> {
 /// let entries = generate_table(417, GenerateOptions::default()
 ///     .with_table_id(3)
 ///     .with_range(0, 1000));
+///
+/// // Small table for experiments
+/// let entries = generate_table(417, GenerateOptions::default()
+///     .with_params(TableParams::new(256, 4096, 4)));
 /// ```
 pub fn generate_table<F>(consumption: i32, options: GenerateOptions<F>) -> Vec<ChainEntry>
 where
@@ -99,17 +132,257 @@ where
 }
 
 /// Generate all tables for a consumption value.
-pub fn generate_all_tables(consumption: i32) -> Vec<Vec<ChainEntry>> {
-    (0..NUM_TABLES)
+pub fn generate_all_tables(consumption: i32, params: TableParams) -> Vec<Vec<ChainEntry>> {
+    (0..params.num_tables)
         .map(|table_id| {
             generate_table(
                 consumption,
-                GenerateOptions::default().with_table_id(table_id),
+                GenerateOptions::default()
+                    .with_params(params)
+                    .with_table_id(table_id),
             )
         })
         .collect()
 }
 
+/// Generate a rainbow table whose start seeds are chosen by coverage
+///
+/// Instead of `start..end`, the start seeds are the lowest seeds not yet set
+/// in `bitmap`, chosen `GUIDED_BATCH_SIZE` at a time. Every seed on the
+/// generated chains is then set in `bitmap`, so that later batches and later
+/// tables sharing the bitmap start from uncovered seeds. The range only
+/// determines the number of chains.
+///
+/// The result depends on the bitmap contents and the batch boundaries: the
+/// same tables and chunks generated in the same order give the same chains.
+///
+/// # Examples
+///
+/// ```ignore
+/// let bitmap = SeedBitmap::new();
+/// let tables: Vec<_> = (0..params.num_tables)
+///     .map(|table_id| generate_table_guided(417, &bitmap, GenerateOptions::default()
+///         .with_params(params)
+///         .with_table_id(table_id)))
+///     .collect();
+/// ```
+pub fn generate_table_guided<F>(
+    consumption: i32,
+    bitmap: &SeedBitmap,
+    options: GenerateOptions<F>,
+) -> Vec<ChainEntry>
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let GenerateOptions {
+        start,
+        end,
+        table_id,
+        chain_length,
+        on_progress,
+    } = options;
+
+    let total = end.saturating_sub(start);
+    let mut result = Vec::with_capacity(total as usize);
+    let mut cursor = 0;
+
+    while (result.len() as u32) < total {
+        let count = GUIDED_BATCH_SIZE.min(total - result.len() as u32);
+        let start_seeds = pick_uncovered_seeds(bitmap, &mut cursor, count);
+        result.extend(compute_chains_marking(
+            &start_seeds,
+            consumption,
+            table_id,
+            chain_length,
+            bitmap,
+        ));
+
+        if let Some(ref callback) = on_progress {
+            callback(result.len() as u32, total);
+        }
+    }
+
+    if total == 0
+        && let Some(ref callback) = on_progress
+    {
+        callback(0, 0);
+    }
+    result
+}
+
+/// Generate all tables with coverage-guided start seeds
+///
+/// All tables share one `SeedBitmap` (512 MB), so each table starts from the
+/// seeds not covered by the previous ones.
+pub fn generate_all_tables_guided(consumption: i32, params: TableParams) -> Vec<Vec<ChainEntry>> {
+    let bitmap = SeedBitmap::new();
+    (0..params.num_tables)
+        .map(|table_id| {
+            generate_table_guided(
+                consumption,
+                &bitmap,
+                GenerateOptions::default()
+                    .with_params(params)
+                    .with_table_id(table_id),
+            )
+        })
+        .collect()
+}
+
+/// Set every seed on the given chains in `bitmap`
+///
+/// Used to restore the coverage of chains generated earlier, e.g. when
+/// resuming coverage-guided generation.
+pub fn mark_chain_seeds(
+    bitmap: &SeedBitmap,
+    entries: &[ChainEntry],
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+) {
+    let start_seeds: Vec<u32> = entries.iter().map(|entry| entry.start_seed).collect();
+    compute_chains_marking(&start_seeds, consumption, table_id, chain_length, bitmap);
+}
+
+/// Chains removed while making a table perfect
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MergeStats {
+    /// Chains generated, including replacement chains
+    pub generated: u64,
+    /// Chains removed because their end hash collided with another chain
+    pub merged: u64,
+    /// Rounds of replacement chains generated
+    pub rounds: u32,
+}
+
+impl MergeStats {
+    /// Fraction of the generated chains that were removed
+    pub fn merge_rate(&self) -> f64 {
+        if self.generated == 0 {
+            0.0
+        } else {
+            self.merged as f64 / self.generated as f64
+        }
+    }
+}
+
+/// Make a table perfect: every chain ends with a distinct end hash
+///
+/// Sorts the table, keeps one chain per end hash (the one with the lowest
+/// start seed) and asks `replacements(count)` for `count` chains from fresh
+/// start seeds until the table has `chains_per_table` chains again. Returns
+/// the sorted table, its end hashes and the number of merged chains.
+/// Stops early if `replacements` returns no chains.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut next_start = params.chains_per_table;
+/// let (entries, end_hashes, stats) = make_table_perfect(entries, params.chains_per_table, 417,
+///     |count| {
+///         let start = next_start;
+///         next_start += count;
+///         generate_table(417, GenerateOptions::default()
+///             .with_params(params)
+///             .with_range(start, start + count))
+///     });
+/// println!("Merge rate: {:.2}%", stats.merge_rate() * 100.0);
+/// ```
+pub fn make_table_perfect(
+    mut entries: Vec<ChainEntry>,
+    chains_per_table: u32,
+    consumption: i32,
+    mut replacements: impl FnMut(u32) -> Vec<ChainEntry>,
+) -> (Vec<ChainEntry>, Vec<u64>, MergeStats) {
+    let mut stats = MergeStats {
+        generated: entries.len() as u64,
+        ..MergeStats::default()
+    };
+
+    loop {
+        let mut end_hashes = sort_table_with_end_hashes(&mut entries, consumption);
+        stats.merged += deduplicate_table_with_end_hashes(&mut entries, &mut end_hashes) as u64;
+
+        let missing = chains_per_table.saturating_sub(entries.len() as u32);
+        let replaced = if missing > 0 {
+            replacements(missing)
+        } else {
+            Vec::new()
+        };
+        if replaced.is_empty() {
+            return (entries, end_hashes, stats);
+        }
+
+        stats.generated += replaced.len() as u64;
+        stats.rounds += 1;
+        entries.extend(replaced);
+    }
+}
+
+/// Choose the `count` lowest seeds at or after `cursor` that are not in `bitmap`
+///
+/// `cursor` is advanced past the last chosen seed. Seeds below it are all
+/// covered (coverage only grows), so it can be reused for the next batch.
+/// Once every seed is covered, consecutive seeds are used instead.
+fn pick_uncovered_seeds(bitmap: &SeedBitmap, cursor: &mut u32, count: u32) -> Vec<u32> {
+    (0..count)
+        .map(|_| {
+            let seed = bitmap.next_missing(*cursor).unwrap_or(*cursor);
+            *cursor = seed.wrapping_add(1);
+            seed
+        })
+        .collect()
+}
+
+/// Compute the chains of `start_seeds`, setting every seed on them in `bitmap`
+#[cfg(feature = "multi-sfmt")]
+fn compute_chains_marking(
+    start_seeds: &[u32],
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+    bitmap: &SeedBitmap,
+) -> Vec<ChainEntry> {
+    start_seeds
+        .par_chunks(16)
+        .flat_map_iter(|chunk| {
+            // Fill remaining lanes with the first seed (duplicates are fine)
+            let seeds: [u32; 16] = std::array::from_fn(|i| *chunk.get(i).unwrap_or(&chunk[0]));
+            let mut end_seeds = seeds;
+            enumerate_chain_seeds_x16(seeds, consumption, table_id, chain_length, |current| {
+                bitmap.set_batch(current);
+                end_seeds = current;
+            });
+
+            (0..chunk.len()).map(move |i| ChainEntry::new(seeds[i], end_seeds[i]))
+        })
+        .collect()
+}
+
+/// Compute the chains of `start_seeds`, setting every seed on them in `bitmap`
+#[cfg(not(feature = "multi-sfmt"))]
+fn compute_chains_marking(
+    start_seeds: &[u32],
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+    bitmap: &SeedBitmap,
+) -> Vec<ChainEntry> {
+    start_seeds
+        .par_iter()
+        .map(|&start_seed| {
+            let mut current = start_seed;
+            bitmap.set(current);
+            for n in 0..chain_length {
+                let hash = gen_hash_from_seed(current, consumption);
+                current = reduce_hash_with_salt(hash, n, table_id);
+                bitmap.set(current);
+            }
+            ChainEntry::new(start_seed, current)
+        })
+        .collect()
+}
+
 /// Multi-SFMT + rayon parallel implementation
 #[cfg(feature = "multi-sfmt")]
 fn generate_impl_multi<F>(consumption: i32, options: GenerateOptions<F>) -> Vec<ChainEntry>
@@ -120,6 +393,7 @@ where
         start,
         end,
         table_id,
+        chain_length,
         on_progress,
     } = options;
 
@@ -145,7 +419,7 @@ where
     // Handle case where range is too small for SIMD
     if aligned_start >= end {
         for seed in start..end {
-            let entry = compute_chain(seed, consumption, table_id);
+            let entry = compute_chain(seed, consumption, table_id, chain_length);
             if let Some(ref callback) = on_progress {
                 let count = progress.fetch_add(1, Ordering::Relaxed);
                 if count.is_multiple_of(PROGRESS_INTERVAL) {
@@ -165,7 +439,7 @@ where
 
     // Process unaligned prefix
     for seed in start..aligned_start {
-        let entry = compute_chain(seed, consumption, table_id);
+        let entry = compute_chain(seed, consumption, table_id, chain_length);
         if let Some(ref callback) = on_progress {
             let count = progress.fetch_add(1, Ordering::Relaxed);
             if count.is_multiple_of(PROGRESS_INTERVAL) {
@@ -180,7 +454,7 @@ where
     result.par_extend((0..batches).into_par_iter().flat_map_iter(|batch| {
         let base = aligned_start + batch * 16;
         let seeds: [u32; 16] = std::array::from_fn(|i| base + i as u32);
-        let entries = compute_chains_x16(seeds, consumption, table_id);
+        let entries = compute_chains_x16(seeds, consumption, table_id, chain_length);
 
         if let Some(ref callback) = on_progress {
             let count = progress.fetch_add(16, Ordering::Relaxed);
@@ -194,7 +468,7 @@ where
 
     // Process unaligned suffix
     for seed in aligned_end..end {
-        let entry = compute_chain(seed, consumption, table_id);
+        let entry = compute_chain(seed, consumption, table_id, chain_length);
         if let Some(ref callback) = on_progress {
             let count = progress.fetch_add(1, Ordering::Relaxed);
             if count.is_multiple_of(PROGRESS_INTERVAL) {
@@ -220,6 +494,7 @@ where
         start,
         end,
         table_id,
+        chain_length,
         on_progress,
     } = options;
 
@@ -236,7 +511,7 @@ where
     let entries: Vec<ChainEntry> = (start..end)
         .into_par_iter()
         .map(|seed| {
-            let entry = compute_chain(seed, consumption, table_id);
+            let entry = compute_chain(seed, consumption, table_id, chain_length);
 
             if let Some(ref callback) = on_progress {
                 let count = progress.fetch_add(1, Ordering::Relaxed);
@@ -258,6 +533,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::chain::enumerate_chain_seeds;
+    use serial_test::serial;
 
     #[test]
     fn test_generate_default() {
@@ -366,6 +643,29 @@ mod tests {
         assert!(progress_count.load(Ordering::Relaxed) >= 1);
     }
 
+    #[test]
+    fn test_generate_with_params() {
+        let params = TableParams::new(8, 20, 2);
+        let entries = generate_table(
+            417,
+            GenerateOptions::default()
+                .with_params(params)
+                .with_table_id(1),
+        );
+        assert_eq!(entries.len(), 20);
+
+        for (i, entry) in entries.iter().enumerate() {
+            assert_eq!(*entry, compute_chain(i as u32, 417, 1, 8));
+        }
+    }
+
+    #[test]
+    fn test_generate_all_tables_with_params() {
+        let tables = generate_all_tables(417, TableParams::new(4, 10, 3));
+        assert_eq!(tables.len(), 3);
+        assert!(tables.iter().all(|table| table.len() == 10));
+    }
+
     #[cfg(feature = "multi-sfmt")]
     #[test]
     fn test_generate_aligned_range() {
@@ -397,4 +697,104 @@ mod tests {
         let entries = generate_table(417, GenerateOptions::default().with_range(0, 5));
         assert_eq!(entries.len(), 5);
     }
+
+    #[test]
+    #[serial]
+    fn test_generate_table_guided_skips_covered_seeds() {
+        let bitmap = SeedBitmap::new();
+        for seed in (0..100).chain([105]) {
+            bitmap.set(seed);
+        }
+
+        let entries = generate_table_guided(
+            417,
+            &bitmap,
+            GenerateOptions::default().with_params(TableParams::new(8, 10, 1)),
+        );
+
+        let start_seeds: Vec<u32> = entries.iter().map(|e| e.start_seed).collect();
+        assert_eq!(
+            start_seeds,
+            vec![100, 101, 102, 103, 104, 106, 107, 108, 109, 110]
+        );
+        for entry in &entries {
+            assert_eq!(*entry, compute_chain(entry.start_seed, 417, 0, 8));
+            let seeds = enumerate_chain_seeds(entry.start_seed, 417, 0, 8);
+            assert!(seeds.iter().all(|&seed| bitmap.is_set(seed)));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_generate_all_tables_guided() {
+        let params = TableParams::new(16, 64, 3);
+        let tables = generate_all_tables_guided(417, params);
+        assert_eq!(tables.len(), 3);
+
+        // The first table starts from the lowest seeds, later ones from uncovered seeds
+        let first: Vec<u32> = tables[0].iter().map(|e| e.start_seed).collect();
+        assert_eq!(first, (0..64).collect::<Vec<u32>>());
+
+        let mut start_seeds: Vec<u32> = tables.iter().flatten().map(|e| e.start_seed).collect();
+        start_seeds.sort_unstable();
+        start_seeds.dedup();
+        assert_eq!(start_seeds.len(), 3 * 64);
+
+        for (table_id, table) in tables.iter().enumerate() {
+            for entry in table {
+                assert_eq!(
+                    *entry,
+                    compute_chain(entry.start_seed, 417, table_id as u32, 16)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_make_table_perfect_replaces_merged_chains() {
+        // Chains 0..10 end in only 4 distinct seeds
+        let entries: Vec<ChainEntry> = (0..10).map(|i| ChainEntry::new(i, i % 4)).collect();
+        let mut next_start = 10;
+        let mut requests = Vec::new();
+
+        let (entries, end_hashes, stats) = make_table_perfect(entries, 10, 417, |count| {
+            requests.push(count);
+            // The first replacement chain merges again with an existing chain
+            let replaced: Vec<ChainEntry> = (next_start..next_start + count)
+                .map(|start| ChainEntry::new(start, if start == 10 { 0 } else { start }))
+                .collect();
+            next_start += count;
+            replaced
+        });
+
+        assert_eq!(entries.len(), 10);
+        assert_eq!(
+            end_hashes,
+            crate::infra::table_sort::compute_end_hashes(&entries, 417)
+        );
+        assert!(end_hashes.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(requests, vec![6, 1]);
+        assert_eq!(
+            stats,
+            MergeStats {
+                generated: 17,
+                merged: 7,
+                rounds: 2,
+            }
+        );
+        assert!((stats.merge_rate() - 7.0 / 17.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_make_table_perfect_without_merges() {
+        let mut expected = generate_table(417, GenerateOptions::default().with_range(0, 32));
+        let (entries, _, stats) = make_table_perfect(expected.clone(), 32, 417, |_| {
+            panic!("no replacement should be needed")
+        });
+
+        crate::infra::table_sort::sort_table_parallel(&mut expected, 417);
+        assert_eq!(entries, expected);
+        assert_eq!(stats.merged, 0);
+        assert_eq!(stats.rounds, 0);
+    }
 }
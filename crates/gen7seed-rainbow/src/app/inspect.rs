@@ -0,0 +1,177 @@
+//! Table inspection
+//!
+//! Summarizes the end keys of a table and traces single chains, for tools
+//! that report on the contents of a table file.
+
+use crate::domain::chain::{TableRef, enumerate_chain_seeds};
+use crate::domain::hash::gen_hash_from_seed;
+use rayon::prelude::*;
+
+/// Summary of the end keys of a table
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EndKeyStats {
+    /// Number of chains
+    pub chains: usize,
+    /// Smallest end key (None for an empty table)
+    pub min: Option<u64>,
+    /// Largest end key (None for an empty table)
+    pub max: Option<u64>,
+    /// Number of chains whose end key equals that of an earlier chain
+    /// (merged chains beyond the first of each key; 0 for a perfect table)
+    pub duplicates: usize,
+    /// Whether the chains are in ascending end key order
+    pub sorted: bool,
+}
+
+/// Compute the end key statistics of a table
+///
+/// End keys use the sort key of the table. Missing end hashes are
+/// recomputed in parallel.
+pub fn end_key_stats(table: &TableRef<'_>, consumption: i32) -> EndKeyStats {
+    let mut keys: Vec<u64> = (0..table.len())
+        .into_par_iter()
+        .map(|index| table.sort_key.key(table.end_hash(index, consumption)))
+        .collect();
+
+    let sorted = keys.windows(2).all(|pair| pair[0] <= pair[1]);
+    if !sorted {
+        keys.par_sort_unstable();
+    }
+
+    EndKeyStats {
+        chains: keys.len(),
+        min: keys.first().copied(),
+        max: keys.last().copied(),
+        duplicates: keys.windows(2).filter(|pair| pair[0] == pair[1]).count(),
+        sorted,
+    }
+}
+
+/// Seeds visited by a single chain of a table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainTrace {
+    /// Table the chain belongs to
+    pub table_id: u32,
+    /// Position of the chain in the table
+    pub index: usize,
+    /// Seeds from the start seed to the end seed (`chain_length + 1` seeds)
+    pub seeds: Vec<u32>,
+    /// End hash stored in (or recomputed from) the table
+    pub end_hash: u64,
+    /// Whether the recomputed end seed matches the end key of the table
+    pub verified: bool,
+}
+
+impl ChainTrace {
+    /// Start seed of the chain
+    pub fn start_seed(&self) -> u32 {
+        self.seeds[0]
+    }
+
+    /// End seed reached by recomputing the chain
+    pub fn end_seed(&self) -> u32 {
+        *self.seeds.last().unwrap()
+    }
+}
+
+/// Recompute the chain at `index` of a table
+///
+/// Returns `None` if `index` is out of range. The chain is walked with the
+/// salt of `table_id` and the chain length of the table.
+pub fn trace_chain(
+    table: &TableRef<'_>,
+    consumption: i32,
+    table_id: u32,
+    index: usize,
+) -> Option<ChainTrace> {
+    if index >= table.len() {
+        return None;
+    }
+
+    let seeds = enumerate_chain_seeds(
+        table.start_seed(index),
+        consumption,
+        table_id,
+        table.chain_length,
+    );
+    let end_hash = table.end_hash(index, consumption);
+    let recomputed = gen_hash_from_seed(*seeds.last().unwrap(), consumption);
+
+    Some(ChainTrace {
+        table_id,
+        index,
+        seeds,
+        end_hash,
+        verified: table.sort_key.key(recomputed) == table.sort_key.key(end_hash),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chain::{ChainEntry, compute_chain};
+    use crate::infra::table_sort::sort_table_parallel;
+
+    const CHAIN_LENGTH: u32 = 8;
+
+    fn sample_table() -> Vec<ChainEntry> {
+        (0..64)
+            .map(|seed| compute_chain(seed, 417, 1, CHAIN_LENGTH))
+            .collect()
+    }
+
+    #[test]
+    fn test_end_key_stats() {
+        let mut entries = sample_table();
+        sort_table_parallel(&mut entries, 417);
+        // Duplicate the first chain to simulate a merge
+        entries[1] = entries[0];
+
+        let stats = end_key_stats(&TableRef::new(&entries), 417);
+        assert_eq!(stats.chains, 64);
+        assert!(stats.sorted);
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(
+            stats.min,
+            Some(gen_hash_from_seed(entries[0].end_seed, 417))
+        );
+        assert_eq!(
+            stats.max,
+            Some(gen_hash_from_seed(entries[63].end_seed, 417))
+        );
+
+        entries.reverse();
+        let stats = end_key_stats(&TableRef::new(&entries), 417);
+        assert!(!stats.sorted);
+        assert_eq!(stats.duplicates, 1);
+
+        // Three chains sharing a key leave two merged chains
+        entries[2] = entries[63];
+        let stats = end_key_stats(&TableRef::new(&entries), 417);
+        assert_eq!(stats.duplicates, 2);
+
+        assert_eq!(
+            end_key_stats(&TableRef::new(&[]), 417),
+            EndKeyStats {
+                sorted: true,
+                ..EndKeyStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_trace_chain() {
+        let entries = sample_table();
+        let table = TableRef::new(&entries).with_chain_length(CHAIN_LENGTH);
+
+        let trace = trace_chain(&table, 417, 1, 5).unwrap();
+        assert_eq!(trace.seeds.len(), CHAIN_LENGTH as usize + 1);
+        assert_eq!(trace.start_seed(), entries[5].start_seed);
+        assert_eq!(trace.end_seed(), entries[5].end_seed);
+        assert!(trace.verified);
+
+        // Wrong salt
+        assert!(!trace_chain(&table, 417, 2, 5).unwrap().verified);
+        assert!(trace_chain(&table, 417, 1, 64).is_none());
+    }
+}
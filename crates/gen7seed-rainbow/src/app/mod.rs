@@ -2,6 +2,12 @@
 //!
 //! This module coordinates domain and infrastructure layers to implement use cases.
 
+pub mod brute_force;
+pub mod cancel;
+pub mod checkpoint;
+pub mod convert;
 pub mod coverage;
 pub mod generator;
+pub mod inspect;
 pub mod searcher;
+pub mod verify;
@@ -3,17 +3,586 @@
 //! This module provides a unified function for searching initial seeds from needle values
 //! using the rainbow table algorithm.
 
-use crate::constants::MAX_CHAIN_LENGTH;
-use crate::domain::chain::{ChainEntry, verify_chain};
+use crate::app::cancel::CancellationToken;
+use crate::constants::{NEEDLE_COUNT, NEEDLE_STATES};
+use crate::domain::chain::{ChainEntry, TableRef, verify_chain};
 use crate::domain::hash::{gen_hash, gen_hash_from_seed, reduce_hash_with_salt};
+use crate::domain::needle::{
+    NeedleCandidate, NeedleCorrection, NeedleTolerance, enumerate_needle_candidates,
+    gen_needle_values,
+};
 use crate::domain::table_format::{
     TableFormatError, TableHeader, ValidationOptions, validate_header,
 };
+use crate::infra::missing_seeds_io::MissingSeedSource;
 use rayon::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "multi-sfmt")]
+use crate::domain::hash::{gen_hash_from_seed_x16, reduce_hash_x16_with_salts, table_salts_x16};
+
+/// Number of needle candidates walked together in the error-tolerant search
+const TOLERANT_BATCH: usize = 16;
 
+/// Number of tables walked together in the multi-table search
 #[cfg(feature = "multi-sfmt")]
-use crate::domain::hash::{gen_hash_from_seed_x16, reduce_hash_x16_multi_table};
+const TABLE_GROUP_SIZE: usize = 16;
+#[cfg(not(feature = "multi-sfmt"))]
+const TABLE_GROUP_SIZE: usize = 1;
+
+// =============================================================================
+// Search results
+// =============================================================================
+
+/// Seed found in a rainbow table, with the chain it was found in
+///
+/// Ordered by seed, then table_id, column and chain_index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SearchHit {
+    /// Initial seed
+    pub seed: u32,
+    /// Table the chain belongs to (used as salt)
+    pub table_id: u32,
+    /// Position of the seed in the chain (0 = start seed)
+    pub column: u32,
+    /// Index of the chain in the sorted table
+    pub chain_index: usize,
+    /// Start seed of the chain
+    pub start_seed: u32,
+}
+
+/// Work done while searching a single table
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableSearchStats {
+    /// The table identifier
+    pub table_id: u32,
+    /// Time spent on this table, summed over all worker threads
+    ///
+    /// In the 16-table search, the shared chain walk is split evenly between the tables.
+    pub cpu_time: Duration,
+    /// End hashes read while resolving the column walks against the table
+    pub probes: u64,
+    /// Chains replayed from their start seed to verify a candidate
+    pub chains_replayed: u64,
+    /// Replayed chains that did not contain the target hash
+    pub false_alarms: u64,
+    /// Number of hits in this table
+    pub hits: usize,
+}
+
+impl TableSearchStats {
+    fn new(table_id: u32) -> Self {
+        Self {
+            table_id,
+            ..Self::default()
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.cpu_time += other.cpu_time;
+        self.probes += other.probes;
+        self.chains_replayed += other.chains_replayed;
+        self.false_alarms += other.false_alarms;
+        self.hits += other.hits;
+    }
+}
+
+/// How a table search ended
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchStatus {
+    /// All columns were searched
+    #[default]
+    Completed,
+    /// The search stopped early because a verified hit was found
+    StoppedOnHit,
+    /// The search was cancelled
+    Cancelled,
+    /// The deadline passed before all columns were searched
+    DeadlineExceeded,
+}
+
+/// Detailed result of a table search
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchReport {
+    /// Hits in ascending order (see `SearchHit`)
+    pub hits: Vec<SearchHit>,
+    /// Per-table statistics in ascending order of table_id
+    pub tables: Vec<TableSearchStats>,
+    /// Wall-clock time of the whole search
+    pub elapsed: Duration,
+    /// How the search ended
+    pub status: SearchStatus,
+    /// Number of columns searched (the chain length when completed)
+    pub columns_searched: u32,
+}
+
+impl SearchReport {
+    fn new(outcome: SearchOutcome, started: Instant) -> Self {
+        let SearchOutcome {
+            hits,
+            mut tables,
+            status,
+            columns_searched,
+        } = outcome;
+
+        let mut hits: Vec<SearchHit> = hits.into_iter().map(|(_, hit)| hit).collect();
+
+        hits.sort_unstable();
+        hits.dedup();
+        for stats in &mut tables {
+            stats.hits = hits
+                .iter()
+                .filter(|hit| hit.table_id == stats.table_id)
+                .count();
+        }
+        Self {
+            hits,
+            tables,
+            elapsed: started.elapsed(),
+            status,
+            columns_searched,
+        }
+    }
+
+    /// Distinct seeds found, in ascending order
+    pub fn seeds(&self) -> Vec<u32> {
+        let mut seeds: Vec<u32> = self.hits.iter().map(|hit| hit.seed).collect();
+        seeds.dedup();
+        seeds
+    }
+
+    /// Total number of end hashes read from the tables
+    pub fn probes(&self) -> u64 {
+        self.tables.iter().map(|t| t.probes).sum()
+    }
+
+    /// Total number of chains replayed
+    pub fn chains_replayed(&self) -> u64 {
+        self.tables.iter().map(|t| t.chains_replayed).sum()
+    }
+
+    /// Total number of false alarms
+    pub fn false_alarms(&self) -> u64 {
+        self.tables.iter().map(|t| t.false_alarms).sum()
+    }
+}
+
+// =============================================================================
+// Search options
+// =============================================================================
+
+/// Options for cancellable table search
+#[derive(Clone)]
+pub struct SearchOptions<F = fn(u32, u32)> {
+    /// Cancellation token (default: None)
+    pub cancel: Option<CancellationToken>,
+    /// Stop searching once this instant has passed (default: None)
+    pub deadline: Option<Instant>,
+    /// Stop all workers as soon as a verified hit is found (default: false)
+    pub stop_on_hit: bool,
+    /// Progress callback (columns done, chain length)
+    pub on_progress: Option<F>,
+}
+
+impl Default for SearchOptions<fn(u32, u32)> {
+    fn default() -> Self {
+        Self {
+            cancel: None,
+            deadline: None,
+            stop_on_hit: false,
+            on_progress: None,
+        }
+    }
+}
+
+impl<F> SearchOptions<F> {
+    /// Set the cancellation token
+    pub fn with_cancel(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Set the deadline
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop as soon as a verified hit is found
+    pub fn with_stop_on_hit(mut self, stop_on_hit: bool) -> Self {
+        self.stop_on_hit = stop_on_hit;
+        self
+    }
+
+    /// Set the progress callback
+    pub fn with_progress<G>(self, callback: G) -> SearchOptions<G> {
+        SearchOptions {
+            cancel: self.cancel,
+            deadline: self.deadline,
+            stop_on_hit: self.stop_on_hit,
+            on_progress: Some(callback),
+        }
+    }
+}
+
+// =============================================================================
+// Batched column lookup
+// =============================================================================
+
+/// Hits and statistics collected by a search
+struct SearchOutcome {
+    /// Hits, each with the index of the target hash it was found from
+    hits: Vec<(usize, SearchHit)>,
+    tables: Vec<TableSearchStats>,
+    status: SearchStatus,
+    columns_searched: u32,
+}
+
+/// End of a column walk, to be resolved against a sorted table
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Lookup {
+    /// Sort key of the end hash reached by the walk
+    key: u64,
+    /// Column the walk started from
+    column: u32,
+    /// Index of the target hash the walk started from
+    target: usize,
+}
+
+/// Number of columns walked, resolved and replayed together
+///
+/// The stop conditions are checked between batches (and cancellation and the
+/// deadline also within them), so the batch size bounds the work done after
+/// a hit when `stop_on_hit` is set.
+const COLUMN_BATCH: u32 = 64;
+
+/// Search N tables for the given target hashes
+///
+/// The columns are searched in batches of `COLUMN_BATCH`, each in three phases:
+/// 1. Walk every (target, column) pair of the batch to the chain end in
+///    parallel; `walk` returns the end hash reached in each of the N tables.
+///    All tables must share the same chain length.
+/// 2. Sort the end hashes and resolve them against each sorted table in a
+///    single forward pass (see `merge_join_end_hashes`).
+/// 3. Replay the matching chains in parallel to drop false alarms.
+///
+/// Cancellation and the deadline are checked before each walk and each
+/// replayed chain; `stop_on_hit` stops before the next batch once a hit is
+/// found. Progress is reported per column when its batch is done.
+fn search_tables<F, const N: usize>(
+    consumption: i32,
+    target_hashes: &[u64],
+    tables: &[TableRef<'_>; N],
+    table_ids: [u32; N],
+    options: &SearchOptions<F>,
+    walk: impl Fn(u64, u32, u32) -> [u64; N] + Sync,
+) -> SearchOutcome
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let chain_length = tables[0].chain_length;
+    assert!(
+        tables
+            .iter()
+            .all(|table| table.chain_length == chain_length),
+        "tables searched together must have the same chain length"
+    );
+
+    let is_cancelled = || {
+        options
+            .cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    };
+    let is_past_deadline = || options.deadline.is_some_and(|d| Instant::now() >= d);
+    let is_interrupted = || is_cancelled() || is_past_deadline();
+
+    let mut stats: [TableSearchStats; N] = table_ids.map(TableSearchStats::new);
+    let mut hits = Vec::new();
+    let mut columns_searched = 0;
+
+    while columns_searched < chain_length {
+        if (options.stop_on_hit && !hits.is_empty()) || is_interrupted() {
+            break;
+        }
+
+        let columns = columns_searched..(columns_searched + COLUMN_BATCH).min(chain_length);
+        let completed = search_column_batch(
+            consumption,
+            target_hashes,
+            tables,
+            table_ids,
+            columns.clone(),
+            &walk,
+            &is_interrupted,
+            &mut stats,
+            &mut hits,
+        );
+        if !completed {
+            break;
+        }
+
+        for _ in columns {
+            columns_searched += 1;
+            if let Some(ref callback) = options.on_progress {
+                callback(columns_searched, chain_length);
+            }
+        }
+    }
+
+    let status = if columns_searched == chain_length {
+        SearchStatus::Completed
+    } else if is_cancelled() {
+        SearchStatus::Cancelled
+    } else if options.stop_on_hit && !hits.is_empty() {
+        SearchStatus::StoppedOnHit
+    } else {
+        SearchStatus::DeadlineExceeded
+    };
+
+    SearchOutcome {
+        hits,
+        tables: stats.to_vec(),
+        status,
+        columns_searched,
+    }
+}
+
+/// Walk, resolve and replay one batch of columns (see `search_tables`)
+///
+/// Adds the verified hits to `hits` and the work done to `stats`. Returns
+/// false if the batch was interrupted before all of its walks and replays
+/// were done.
+#[allow(clippy::too_many_arguments)]
+fn search_column_batch<const N: usize>(
+    consumption: i32,
+    target_hashes: &[u64],
+    tables: &[TableRef<'_>; N],
+    table_ids: [u32; N],
+    columns: Range<u32>,
+    walk: &(impl Fn(u64, u32, u32) -> [u64; N] + Sync),
+    is_interrupted: &(impl Fn() -> bool + Sync),
+    stats: &mut [TableSearchStats; N],
+    hits: &mut Vec<(usize, SearchHit)>,
+) -> bool {
+    let chain_length = tables[0].chain_length;
+    let batch_columns = columns.len() as u32;
+
+    // Phase 1: walk every column of the batch to the chain end
+    let total_walks = target_hashes.len() as u32 * batch_columns;
+    let (walks, walk_time) = (0..total_walks)
+        .into_par_iter()
+        .fold(
+            || (Vec::new(), Duration::ZERO),
+            |(mut walks, mut time), i| {
+                if is_interrupted() {
+                    return (walks, time);
+                }
+
+                let started = Instant::now();
+                let target = (i / batch_columns) as usize;
+                let column = columns.start + i % batch_columns;
+                walks.push((
+                    target,
+                    column,
+                    walk(target_hashes[target], column, chain_length),
+                ));
+                time += started.elapsed();
+                (walks, time)
+            },
+        )
+        .reduce(
+            || (Vec::new(), Duration::ZERO),
+            |(mut walks, time), (other_walks, other_time)| {
+                walks.extend(other_walks);
+                (walks, time + other_time)
+            },
+        );
+
+    // Phase 2: resolve the end hashes against each table
+    let walk_share = walk_time / N as u32;
+    let matches: Vec<Vec<(Lookup, Range<usize>)>> = tables
+        .par_iter()
+        .zip(stats.par_iter_mut())
+        .enumerate()
+        .map(|(i, (table, stats))| {
+            if table.is_empty() {
+                return Vec::new();
+            }
+            let started = Instant::now();
+            let mut lookups: Vec<Lookup> = walks
+                .iter()
+                .map(|(target, column, end_hashes)| Lookup {
+                    key: table.sort_key.key(end_hashes[i]),
+                    column: *column,
+                    target: *target,
+                })
+                .collect();
+            lookups.sort_unstable();
+            let matches = merge_join_end_hashes(table, &lookups, consumption, &mut stats.probes);
+            stats.cpu_time += walk_share + started.elapsed();
+            matches
+        })
+        .collect();
+
+    // Phase 3: replay the candidate chains
+    let candidates: Vec<(usize, Lookup, usize)> = matches
+        .iter()
+        .enumerate()
+        .flat_map(|(i, matches)| {
+            matches.iter().flat_map(move |(lookup, range)| {
+                range
+                    .clone()
+                    .map(move |chain_index| (i, *lookup, chain_index))
+            })
+        })
+        .collect();
+
+    let (batch_hits, replay_stats) = candidates
+        .par_iter()
+        .fold(
+            || (Vec::new(), [TableSearchStats::default(); N]),
+            |(mut hits, mut stats), &(i, lookup, chain_index)| {
+                if is_interrupted() {
+                    return (hits, stats);
+                }
+
+                let started = Instant::now();
+                let start_seed = tables[i].start_seed(chain_index);
+                let target_hash = target_hashes[lookup.target];
+                stats[i].chains_replayed += 1;
+                match verify_chain(
+                    start_seed,
+                    lookup.column,
+                    target_hash,
+                    consumption,
+                    table_ids[i],
+                ) {
+                    Some(seed) => hits.push((
+                        lookup.target,
+                        SearchHit {
+                            seed,
+                            table_id: table_ids[i],
+                            column: lookup.column,
+                            chain_index,
+                            start_seed,
+                        },
+                    )),
+                    None => stats[i].false_alarms += 1,
+                }
+                stats[i].cpu_time += started.elapsed();
+                (hits, stats)
+            },
+        )
+        .reduce(
+            || (Vec::new(), [TableSearchStats::default(); N]),
+            |(mut hits, mut stats), (other_hits, other_stats)| {
+                hits.extend(other_hits);
+                merge_stats(&mut stats, &other_stats);
+                (hits, stats)
+            },
+        );
+    merge_stats(stats, &replay_stats);
+    hits.extend(batch_hits);
+
+    let chains_replayed: u64 = replay_stats.iter().map(|s| s.chains_replayed).sum();
+    walks.len() as u32 == total_walks && chains_replayed == candidates.len() as u64
+}
+
+/// Resolve sorted lookups against a sorted table in a single forward pass
+///
+/// Each lookup gallops forward from where the previous one ended, so the
+/// table is read in ascending order instead of with independent binary
+/// searches. Returns the lookups that matched at least one chain, with the
+/// index range of the matching chains; every end hash read is counted in
+/// `probes`.
+fn merge_join_end_hashes(
+    table: &TableRef<'_>,
+    lookups: &[Lookup],
+    consumption: i32,
+    probes: &mut u64,
+) -> Vec<(Lookup, Range<usize>)> {
+    let mut results = Vec::new();
+    let mut cursor = 0;
+    let mut previous: Option<(u64, Range<usize>)> = None;
+
+    for lookup in lookups {
+        let range = match &previous {
+            Some((key, range)) if *key == lookup.key => range.clone(),
+            _ => {
+                let range = gallop_by_end_hash(table, cursor, lookup.key, consumption, probes);
+                cursor = range.end;
+                previous = Some((lookup.key, range.clone()));
+                range
+            }
+        };
+        if !range.is_empty() {
+            results.push((*lookup, range));
+        }
+    }
+
+    results
+}
+
+/// Find the entries whose sort key equals `target_key`, starting at `from`
+///
+/// The table stores end_seed, but the sort key is
+/// gen_hash_from_seed(end_seed, consumption) ascending, either in full or
+/// truncated to u32 (see `EndHashKey`). Precomputed end hashes are used when
+/// available. All entries before `from` must have a smaller key.
+fn gallop_by_end_hash(
+    table: &TableRef<'_>,
+    from: usize,
+    target_key: u64,
+    consumption: i32,
+    probes: &mut u64,
+) -> Range<usize> {
+    let len = table.len();
+    let mut end_key = |index: usize| {
+        *probes += 1;
+        table.sort_key.key(table.end_hash(index, consumption))
+    };
+
+    // Gallop forward (from, from+1, from+3, from+7, ...) to bracket the key
+    let mut left = from;
+    let mut step = 1;
+    let mut right = loop {
+        let probe = from + step - 1;
+        if probe >= len {
+            break len;
+        }
+        if end_key(probe) >= target_key {
+            break probe;
+        }
+        left = probe + 1;
+        step *= 2;
+    };
+
+    // Binary search for the first matching entry within the bracket
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if end_key(mid) < target_key {
+            left = mid + 1;
+        } else {
+            right = mid;
+        }
+    }
+
+    // Extend over all matching entries
+    let mut end = left;
+    while end < len && end_key(end) == target_key {
+        end += 1;
+    }
+
+    left..end
+}
+
+// =============================================================================
+// Single table search
+// =============================================================================
 
 /// Search for initial seeds from needle values
 ///
@@ -23,28 +592,109 @@ use crate::domain::hash::{gen_hash_from_seed_x16, reduce_hash_x16_multi_table};
 /// # Arguments
 /// * `needle_values` - 8 needle values (0-16 each) representing clock hand positions
 /// * `consumption` - The RNG consumption value
-/// * `table` - The sorted rainbow table to search
-/// * `table_id` - The table identifier (0 to NUM_TABLES-1), used as salt
+/// * `table` - The sorted rainbow table to search (`&[ChainEntry]` or `TableRef`)
+/// * `table_id` - The table identifier (0 to num_tables-1), used as salt
 ///
 /// # Returns
-/// A vector of initial seed candidates found in the table
-pub fn search_seeds(
+/// Initial seed candidates found in the table, in ascending order
+pub fn search_seeds<'a>(
     needle_values: [u64; 8],
     consumption: i32,
-    table: &[ChainEntry],
+    table: impl Into<TableRef<'a>>,
     table_id: u32,
 ) -> Vec<u32> {
-    let target_hash = gen_hash(needle_values);
+    search_seeds_with_report(needle_values, consumption, table, table_id).seeds()
+}
 
-    let results: HashSet<u32> = (0..MAX_CHAIN_LENGTH)
-        .into_par_iter()
-        .flat_map(|column| search_column(consumption, target_hash, column, table, table_id))
-        .collect();
+/// Search for initial seeds, reporting where each seed was found
+///
+/// Same as `search_seeds`, but returns every hit with its chain and the
+/// work done (end hashes read, chains replayed, false alarms).
+pub fn search_seeds_with_report<'a>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    table: impl Into<TableRef<'a>>,
+    table_id: u32,
+) -> SearchReport {
+    search_seeds_with_options(
+        needle_values,
+        consumption,
+        table,
+        table_id,
+        SearchOptions::default(),
+    )
+}
 
-    results.into_iter().collect()
+/// Search for initial seeds with cancellation, deadline, early exit and progress
+///
+/// # Examples
+///
+/// ```ignore
+/// let token = CancellationToken::new();
+/// let report = search_seeds_with_options(needle_values, 417, table, 0,
+///     SearchOptions::default()
+///         .with_cancel(token.clone())
+///         .with_deadline(Instant::now() + Duration::from_secs(10))
+///         .with_stop_on_hit(true)
+///         .with_progress(|done, total| println!("{}/{}", done, total)));
+/// ```
+pub fn search_seeds_with_options<'a, F>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    table: impl Into<TableRef<'a>>,
+    table_id: u32,
+    options: SearchOptions<F>,
+) -> SearchReport
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let started = Instant::now();
+    let outcome = search_tables(
+        consumption,
+        &[gen_hash(needle_values)],
+        &[table.into()],
+        [table_id],
+        &options,
+        |hash, column, chain_length| {
+            [walk_to_chain_end(
+                hash,
+                column,
+                chain_length,
+                consumption,
+                table_id,
+            )]
+        },
+    );
+
+    SearchReport::new(outcome, started)
+}
+
+/// Walk from `column` to the end of the chain, returning the end hash
+fn walk_to_chain_end(
+    hash: u64,
+    column: u32,
+    chain_length: u32,
+    consumption: i32,
+    table_id: u32,
+) -> u64 {
+    let mut h = hash;
+    for n in column..chain_length {
+        let seed = reduce_hash_with_salt(h, n, table_id);
+        h = gen_hash_from_seed(seed, consumption);
+    }
+    h
+}
+
+/// Merge per-table statistics collected by different workers
+fn merge_stats(stats: &mut [TableSearchStats], other: &[TableSearchStats]) {
+    for (s, o) in stats.iter_mut().zip(other) {
+        s.merge(o);
+    }
 }
 
 /// Search for initial seeds with table metadata validation
+///
+/// The sort key and chain length recorded in the header are used for the search.
 pub fn search_seeds_with_validation(
     needle_values: [u64; 8],
     expected_consumption: i32,
@@ -57,11 +707,285 @@ pub fn search_seeds_with_validation(
     Ok(search_seeds(
         needle_values,
         expected_consumption,
-        table,
+        TableRef::new(table)
+            .with_sort_key(header.end_hash_key())
+            .with_chain_length(header.chain_length),
         table_id,
     ))
 }
 
+// =============================================================================
+// Missing seeds fallback search
+// =============================================================================
+
+/// Search the missing seeds list by brute force
+///
+/// Seeds listed in the `.g7ms` file are not reachable from any chain, so the
+/// rainbow table can never find them. This checks each of them directly, which
+/// together with the table search covers the whole seed space.
+///
+/// # Arguments
+/// * `needle_values` - 8 needle values (0-16 each) representing clock hand positions
+/// * `consumption` - The RNG consumption value
+/// * `missing_seeds` - Seeds not reachable from the table (a list from
+///   `load_missing_seeds` or a `MissingSeeds` view streamed from the file)
+///
+/// # Returns
+/// Matching seeds in the order they appear in `missing_seeds`
+pub fn search_missing_seeds<M>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    missing_seeds: &M,
+) -> Vec<u32>
+where
+    M: MissingSeedSource + ?Sized,
+{
+    let target_hash = gen_hash(needle_values);
+
+    scan_missing_seeds(missing_seeds, consumption, |hash| hash == target_hash)
+        .into_iter()
+        .map(|(seed, _)| seed)
+        .collect()
+}
+
+/// Compute the hash of every seed and keep those accepted by `matches`
+#[cfg(feature = "multi-sfmt")]
+fn scan_missing_seeds<M, P>(missing_seeds: &M, consumption: i32, matches: P) -> Vec<(u32, u64)>
+where
+    M: MissingSeedSource + ?Sized,
+    P: Fn(u64) -> bool + Sync,
+{
+    missing_seeds.flat_map_chunks(|seeds| {
+        seeds
+            .par_chunks(16)
+            .flat_map_iter(|chunk| {
+                let mut seeds = [chunk[0]; 16];
+                seeds[..chunk.len()].copy_from_slice(chunk);
+
+                let hashes = gen_hash_from_seed_x16(seeds, consumption);
+                chunk
+                    .iter()
+                    .copied()
+                    .zip(hashes)
+                    .filter(|&(_, hash)| matches(hash))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    })
+}
+
+/// Compute the hash of every seed and keep those accepted by `matches` (fallback version)
+#[cfg(not(feature = "multi-sfmt"))]
+fn scan_missing_seeds<M, P>(missing_seeds: &M, consumption: i32, matches: P) -> Vec<(u32, u64)>
+where
+    M: MissingSeedSource + ?Sized,
+    P: Fn(u64) -> bool + Sync,
+{
+    missing_seeds.flat_map_chunks(|seeds| {
+        seeds
+            .par_iter()
+            .map(|&seed| (seed, gen_hash_from_seed(seed, consumption)))
+            .filter(|&(_, hash)| matches(hash))
+            .collect()
+    })
+}
+
+// =============================================================================
+// Candidate filtering with extra needles
+// =============================================================================
+
+/// Result of filtering candidates with needles read after the first 8
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtraNeedleFilter {
+    /// Candidates consistent with every extra needle (in input order)
+    pub remaining: Vec<u32>,
+    /// Number of candidates removed by each extra needle
+    ///
+    /// A candidate is counted at the first extra needle it contradicts.
+    pub removed_per_needle: Vec<usize>,
+}
+
+/// Filter candidate seeds with extra needle readings
+///
+/// Continues each candidate's SFMT stream past `consumption + NEEDLE_COUNT`
+/// and compares it with `extra_needles`.
+///
+/// # Arguments
+/// * `candidates` - Seeds found from the first 8 needles
+/// * `consumption` - The RNG consumption value
+/// * `extra_needles` - Needle values (0-16 each) read after the first 8
+pub fn filter_by_extra_needles(
+    candidates: &[u32],
+    consumption: i32,
+    extra_needles: &[u64],
+) -> ExtraNeedleFilter {
+    let total = NEEDLE_COUNT + extra_needles.len();
+    let mismatches: Vec<Option<usize>> = candidates
+        .par_iter()
+        .map(|&seed| {
+            let values = gen_needle_values(seed, consumption, total);
+            values[NEEDLE_COUNT..]
+                .iter()
+                .zip(extra_needles)
+                .position(|(&actual, &expected)| actual != expected % NEEDLE_STATES)
+        })
+        .collect();
+
+    let mut removed_per_needle = vec![0; extra_needles.len()];
+    let mut remaining = Vec::new();
+    for (&seed, mismatch) in candidates.iter().zip(mismatches) {
+        match mismatch {
+            Some(index) => removed_per_needle[index] += 1,
+            None => remaining.push(seed),
+        }
+    }
+
+    ExtraNeedleFilter {
+        remaining,
+        removed_per_needle,
+    }
+}
+
+// =============================================================================
+// Error-tolerant search
+// =============================================================================
+
+/// Seed found from a (possibly corrected) needle reading
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TolerantHit {
+    /// Initial seed
+    pub seed: u32,
+    /// Needle values that produced the hit
+    pub needle_values: [u64; 8],
+    /// Corrections applied to the observed reading (empty for an exact match)
+    pub corrections: Vec<NeedleCorrection>,
+}
+
+/// Search a single table, also trying needle readings within the tolerance
+///
+/// Every candidate reading from `enumerate_needle_candidates` is searched.
+/// Candidates are walked in batches of `TOLERANT_BATCH`, with all
+/// (candidate, column) pairs of a batch looked up together. Tolerances
+/// beyond `MAX_NEEDLE_CANDIDATES` are not searched and return no hits.
+///
+/// # Returns
+/// One hit per seed (the one with the fewest corrections), ranked by number
+/// of corrections, then total correction distance
+pub fn search_seeds_tolerant<'a>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    table: impl Into<TableRef<'a>>,
+    table_id: u32,
+    tolerance: &NeedleTolerance,
+) -> Vec<TolerantHit> {
+    let tables = [table.into()];
+    let candidates = enumerate_needle_candidates(needle_values, tolerance);
+
+    let found = search_candidates(&candidates, |target_hashes| {
+        search_tables(
+            consumption,
+            target_hashes,
+            &tables,
+            [table_id],
+            &SearchOptions::default(),
+            |hash, column, chain_length| {
+                [walk_to_chain_end(
+                    hash,
+                    column,
+                    chain_length,
+                    consumption,
+                    table_id,
+                )]
+            },
+        )
+    });
+
+    rank_tolerant_hits(&candidates, found)
+}
+
+/// Search the missing seeds list, also trying needle readings within the tolerance
+///
+/// The hash of each missing seed is computed only once and compared against
+/// all candidate readings. Hits are ranked as in `search_seeds_tolerant`.
+pub fn search_missing_seeds_tolerant<M>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    missing_seeds: &M,
+    tolerance: &NeedleTolerance,
+) -> Vec<TolerantHit>
+where
+    M: MissingSeedSource + ?Sized,
+{
+    let candidates = enumerate_needle_candidates(needle_values, tolerance);
+    let index_by_hash: HashMap<u64, usize> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, c)| (gen_hash(c.needle_values), index))
+        .collect();
+
+    let found = scan_missing_seeds(missing_seeds, consumption, |hash| {
+        index_by_hash.contains_key(&hash)
+    })
+    .into_iter()
+    .map(|(seed, hash)| (index_by_hash[&hash], seed));
+
+    rank_tolerant_hits(&candidates, found)
+}
+
+/// Search the candidate readings in batches, returning (candidate index, seed) pairs
+///
+/// Batching bounds the memory used by the column walks of a batch.
+fn search_candidates(
+    candidates: &[NeedleCandidate],
+    search: impl Fn(&[u64]) -> SearchOutcome,
+) -> Vec<(usize, u32)> {
+    let target_hashes: Vec<u64> = candidates
+        .iter()
+        .map(|c| gen_hash(c.needle_values))
+        .collect();
+
+    target_hashes
+        .chunks(TOLERANT_BATCH)
+        .enumerate()
+        .flat_map(|(batch, hashes)| {
+            search(hashes)
+                .hits
+                .into_iter()
+                .map(move |(index, hit)| (batch * TOLERANT_BATCH + index, hit.seed))
+        })
+        .collect()
+}
+
+/// Convert (candidate index, seed) pairs into hits in a deterministic ranked order
+///
+/// Only the best ranked hit of every seed is kept.
+fn rank_tolerant_hits(
+    candidates: &[NeedleCandidate],
+    found: impl IntoIterator<Item = (usize, u32)>,
+) -> Vec<TolerantHit> {
+    let mut found: Vec<(usize, u32)> = found.into_iter().collect();
+    found.sort_by_key(|&(index, seed)| {
+        let candidate = &candidates[index];
+        (
+            candidate.corrections.len(),
+            candidate.total_delta(),
+            index,
+            seed,
+        )
+    });
+    let mut seen = HashSet::new();
+    found.retain(|&(_, seed)| seen.insert(seed));
+
+    found
+        .into_iter()
+        .map(|(index, seed)| TolerantHit {
+            seed,
+            needle_values: candidates[index].needle_values,
+            corrections: candidates[index].corrections.clone(),
+        })
+        .collect()
+}
+
 // =============================================================================
 // 16-table parallel search (multi-sfmt feature)
 // =============================================================================
@@ -76,146 +1000,451 @@ pub fn search_seeds_with_validation(
 /// # Arguments
 /// * `needle_values` - 8 needle values (0-16 each) representing clock hand positions
 /// * `consumption` - The RNG consumption value
-/// * `tables` - 16 sorted rainbow tables (one per table_id 0..15)
+/// * `tables` - 16 sorted rainbow tables (one per table_id 0..15, `&[ChainEntry]` or `TableRef`)
+///   with the same chain length
 ///
 /// # Returns
-/// A vector of (table_id, seed) pairs for all found initial seeds
+/// (table_id, seed) pairs for all found initial seeds, in ascending order
 #[cfg(feature = "multi-sfmt")]
-pub fn search_seeds_x16(
+pub fn search_seeds_x16<'a>(
     needle_values: [u64; 8],
     consumption: i32,
-    tables: [&[ChainEntry]; 16],
+    tables: [impl Into<TableRef<'a>>; 16],
 ) -> Vec<(u32, u32)> {
-    let target_hash = gen_hash(needle_values);
-
-    let results: HashSet<(u32, u32)> = (0..MAX_CHAIN_LENGTH)
-        .into_par_iter()
-        .flat_map(|column| search_column_x16(consumption, target_hash, column, &tables))
+    let report = search_seeds_x16_with_report(needle_values, consumption, tables);
+    let mut results: Vec<(u32, u32)> = report
+        .hits
+        .iter()
+        .map(|hit| (hit.table_id, hit.seed))
         .collect();
+    results.sort_unstable();
+    results.dedup();
+    results
+}
 
-    results.into_iter().collect()
+/// Search 16 tables simultaneously, reporting where each seed was found
+///
+/// Same as `search_seeds_x16`, but returns every hit with its chain and the
+/// work done per table.
+#[cfg(feature = "multi-sfmt")]
+pub fn search_seeds_x16_with_report<'a>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    tables: [impl Into<TableRef<'a>>; 16],
+) -> SearchReport {
+    search_seeds_x16_with_options(needle_values, consumption, tables, SearchOptions::default())
 }
 
-/// Search a single column position across all 16 tables simultaneously
+/// Search 16 tables with cancellation, deadline, early exit and progress
+///
+/// This is the multi-sfmt version of `search_seeds_with_options`.
 #[cfg(feature = "multi-sfmt")]
-fn search_column_x16(
+pub fn search_seeds_x16_with_options<'a, F>(
+    needle_values: [u64; 8],
     consumption: i32,
-    target_hash: u64,
-    column: u32,
-    tables: &[&[ChainEntry]; 16],
-) -> Vec<(u32, u32)> {
-    let mut results = Vec::new();
+    tables: [impl Into<TableRef<'a>>; 16],
+    options: SearchOptions<F>,
+) -> SearchReport
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let started = Instant::now();
+    let salts = table_salts_x16(TABLE_IDS_X16);
+    let outcome = search_tables(
+        consumption,
+        &[gen_hash(needle_values)],
+        &tables.map(Into::into),
+        TABLE_IDS_X16,
+        &options,
+        |hash, column, chain_length| {
+            walk_to_chain_end_x16(hash, column, chain_length, consumption, &salts)
+        },
+    );
+
+    SearchReport::new(outcome, started)
+}
+
+/// Search 16 tables, also trying needle readings within the tolerance
+///
+/// This is the multi-sfmt version of `search_seeds_tolerant`. The table IDs of
+/// the hits are not reported.
+#[cfg(feature = "multi-sfmt")]
+pub fn search_seeds_tolerant_x16<'a>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    tables: [impl Into<TableRef<'a>>; 16],
+    tolerance: &NeedleTolerance,
+) -> Vec<TolerantHit> {
+    let tables: [TableRef<'a>; 16] = tables.map(Into::into);
+    let salts = table_salts_x16(TABLE_IDS_X16);
+    let candidates = enumerate_needle_candidates(needle_values, tolerance);
 
-    // Step 1: Calculate end hashes for all 16 tables simultaneously
-    let mut hashes = [target_hash; 16];
-    for n in column..MAX_CHAIN_LENGTH {
-        let seeds = reduce_hash_x16_multi_table(hashes, n);
+    let found = search_candidates(&candidates, |target_hashes| {
+        search_tables(
+            consumption,
+            target_hashes,
+            &tables,
+            TABLE_IDS_X16,
+            &SearchOptions::default(),
+            |hash, column, chain_length| {
+                walk_to_chain_end_x16(hash, column, chain_length, consumption, &salts)
+            },
+        )
+    });
+
+    rank_tolerant_hits(&candidates, found)
+}
+
+/// Table IDs of the 16 tables searched together
+#[cfg(feature = "multi-sfmt")]
+const TABLE_IDS_X16: [u32; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+/// Walk from `column` to the end of the chain in 16 tables simultaneously
+///
+/// `salts` are the salts of the 16 tables (see `table_salts_x16`).
+#[cfg(feature = "multi-sfmt")]
+fn walk_to_chain_end_x16(
+    hash: u64,
+    column: u32,
+    chain_length: u32,
+    consumption: i32,
+    salts: &[u64; 16],
+) -> [u64; 16] {
+    let mut hashes = [hash; 16];
+    for n in column..chain_length {
+        let seeds = reduce_hash_x16_with_salts(hashes, n, salts);
         hashes = gen_hash_from_seed_x16(seeds, consumption);
     }
+    hashes
+}
 
-    // Step 2: Binary search and verify in each table
-    for (table_id, (table, &end_hash)) in tables.iter().zip(hashes.iter()).enumerate() {
-        let expected_end_hash = end_hash as u32;
-        let candidates = binary_search_by_end_hash(table, expected_end_hash, consumption);
+// =============================================================================
+// Multi-table search (any number of tables)
+// =============================================================================
 
-        for entry in candidates {
-            if let Some(found_seed) = verify_chain(
-                entry.start_seed,
-                column,
-                target_hash,
+/// Search every table of a file for initial seeds
+///
+/// The table ID of each table is its index in `tables`. With the multi-sfmt
+/// feature, tables are walked in groups of 16 (the last group is padded with
+/// empty tables); otherwise every table is searched on its own. The groups are
+/// searched in parallel.
+///
+/// # Returns
+/// (table_id, seed) pairs for all found initial seeds, in ascending order
+pub fn search_seeds_multi_table(
+    needle_values: [u64; 8],
+    consumption: i32,
+    tables: &[TableRef<'_>],
+) -> Vec<(u32, u32)> {
+    let report = search_seeds_multi_table_with_report(needle_values, consumption, tables);
+    let mut results: Vec<(u32, u32)> = report
+        .hits
+        .iter()
+        .map(|hit| (hit.table_id, hit.seed))
+        .collect();
+    results.sort_unstable();
+    results.dedup();
+    results
+}
+
+/// Search every table of a file, reporting where each seed was found
+///
+/// Same as `search_seeds_multi_table`, but returns every hit with its chain
+/// and the work done per table.
+pub fn search_seeds_multi_table_with_report(
+    needle_values: [u64; 8],
+    consumption: i32,
+    tables: &[TableRef<'_>],
+) -> SearchReport {
+    search_seeds_multi_table_with_options(
+        needle_values,
+        consumption,
+        tables,
+        SearchOptions::default(),
+    )
+}
+
+/// Search every table of a file with cancellation, deadline, early exit and progress
+///
+/// Progress is reported over all tables together. `stop_on_hit` applies to
+/// each group of tables separately.
+pub fn search_seeds_multi_table_with_options<F>(
+    needle_values: [u64; 8],
+    consumption: i32,
+    tables: &[TableRef<'_>],
+    options: SearchOptions<F>,
+) -> SearchReport
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let started = Instant::now();
+    let outcome = search_table_groups(consumption, &[gen_hash(needle_values)], tables, &options);
+
+    SearchReport::new(outcome, started)
+}
+
+/// Search every table of a file, also trying needle readings within the tolerance
+///
+/// This is the multi-table version of `search_seeds_tolerant`.
+pub fn search_seeds_tolerant_multi_table(
+    needle_values: [u64; 8],
+    consumption: i32,
+    tables: &[TableRef<'_>],
+    tolerance: &NeedleTolerance,
+) -> Vec<TolerantHit> {
+    let candidates = enumerate_needle_candidates(needle_values, tolerance);
+
+    let found = search_candidates(&candidates, |target_hashes| {
+        search_table_groups(
+            consumption,
+            target_hashes,
+            tables,
+            &SearchOptions::default(),
+        )
+    });
+
+    rank_tolerant_hits(&candidates, found)
+}
+
+/// Search the tables in groups of `TABLE_GROUP_SIZE` and merge the outcomes
+fn search_table_groups<F>(
+    consumption: i32,
+    target_hashes: &[u64],
+    tables: &[TableRef<'_>],
+    options: &SearchOptions<F>,
+) -> SearchOutcome
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let chain_length = tables.iter().map(|t| t.chain_length).max().unwrap_or(0);
+    let total_columns: u32 = tables
+        .chunks(TABLE_GROUP_SIZE)
+        .map(|group| group[0].chain_length)
+        .sum();
+    let columns_done = AtomicU32::new(0);
+    let group_options =
+        shared_progress_options(options, &columns_done, total_columns, chain_length);
+
+    let outcomes: Vec<SearchOutcome> = tables
+        .par_chunks(TABLE_GROUP_SIZE)
+        .enumerate()
+        .map(|(i, group)| {
+            let first_table_id = (i * TABLE_GROUP_SIZE) as u32;
+            search_group(
                 consumption,
-                table_id as u32,
-            ) {
-                results.push((table_id as u32, found_seed));
-            }
-        }
-    }
+                target_hashes,
+                group,
+                first_table_id,
+                &group_options,
+            )
+        })
+        .collect();
 
-    results
+    merge_outcomes(outcomes)
 }
 
-/// Search at a single column position
-fn search_column(
+/// Search up to 16 tables together, padding the missing lanes with empty tables
+#[cfg(feature = "multi-sfmt")]
+fn search_group<F>(
     consumption: i32,
-    target_hash: u64,
-    column: u32,
-    table: &[ChainEntry],
-    table_id: u32,
-) -> Vec<u32> {
-    let mut results = Vec::new();
+    target_hashes: &[u64],
+    group: &[TableRef<'_>],
+    first_table_id: u32,
+    options: &SearchOptions<F>,
+) -> SearchOutcome
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let padding = TableRef::new(&[]).with_chain_length(group[0].chain_length);
+    let tables: [TableRef<'_>; 16] =
+        std::array::from_fn(|lane| group.get(lane).copied().unwrap_or(padding));
+    let table_ids: [u32; 16] = std::array::from_fn(|lane| first_table_id + lane as u32);
+    let salts = table_salts_x16(table_ids);
 
-    // Step 1: Calculate hash from target_hash to chain end
-    let mut h = target_hash;
-    for n in column..MAX_CHAIN_LENGTH {
-        let seed = reduce_hash_with_salt(h, n, table_id);
-        h = gen_hash_from_seed(seed, consumption);
-    }
+    let mut outcome = search_tables(
+        consumption,
+        target_hashes,
+        &tables,
+        table_ids,
+        options,
+        |hash, column, chain_length| {
+            walk_to_chain_end_x16(hash, column, chain_length, consumption, &salts)
+        },
+    );
+    outcome.tables.truncate(group.len());
+    outcome
+}
 
-    // Step 2: Binary search the table by end hash
-    let expected_end_hash = h as u32;
-    let candidates = binary_search_by_end_hash(table, expected_end_hash, consumption);
+/// Search a single table
+#[cfg(not(feature = "multi-sfmt"))]
+fn search_group<F>(
+    consumption: i32,
+    target_hashes: &[u64],
+    group: &[TableRef<'_>],
+    first_table_id: u32,
+    options: &SearchOptions<F>,
+) -> SearchOutcome
+where
+    F: Fn(u32, u32) + Sync,
+{
+    search_tables(
+        consumption,
+        target_hashes,
+        &[group[0]],
+        [first_table_id],
+        options,
+        |hash, column, chain_length| {
+            [walk_to_chain_end(
+                hash,
+                column,
+                chain_length,
+                consumption,
+                first_table_id,
+            )]
+        },
+    )
+}
 
-    // Step 3: Verify candidate chains
-    for entry in candidates {
-        if let Some(found_seed) =
-            verify_chain(entry.start_seed, column, target_hash, consumption, table_id)
+/// Options for one group of tables, reporting progress over all groups
+///
+/// The columns done by every group are scaled to the chain length, so the
+/// callback is called once per column with (columns done, chain length) as
+/// for a single group.
+fn shared_progress_options<'a, F>(
+    options: &'a SearchOptions<F>,
+    columns_done: &'a AtomicU32,
+    total_columns: u32,
+    chain_length: u32,
+) -> SearchOptions<impl Fn(u32, u32) + Sync + 'a>
+where
+    F: Fn(u32, u32) + Sync,
+{
+    let scaled = move |done: u32| (done as u64 * chain_length as u64 / total_columns as u64) as u32;
+    SearchOptions::<fn(u32, u32)> {
+        cancel: options.cancel.clone(),
+        deadline: options.deadline,
+        stop_on_hit: options.stop_on_hit,
+        on_progress: None,
+    }
+    .with_progress(move |_, _| {
+        let done = columns_done.fetch_add(1, Ordering::Relaxed);
+        let current = scaled(done + 1);
+        if current > scaled(done)
+            && let Some(ref callback) = options.on_progress
         {
-            results.push(found_seed);
+            callback(current, chain_length);
         }
-    }
-
-    results
+    })
 }
 
-/// Binary search the table by end hash
+/// Merge the outcomes of table groups searched separately
 ///
-/// The table stores end_seed, but the sort key is
-/// gen_hash_from_seed(end_seed, consumption) as u32 ascending.
-fn binary_search_by_end_hash(
-    table: &[ChainEntry],
-    target_hash: u32,
-    consumption: i32,
-) -> impl Iterator<Item = &ChainEntry> {
-    // Find the starting position using binary search
-    let start_idx = {
-        let mut left = 0;
-        let mut right = table.len();
-
-        while left < right {
-            let mid = left + (right - left) / 2;
-            let mid_hash = gen_hash_from_seed(table[mid].end_seed, consumption) as u32;
-            if mid_hash < target_hash {
-                left = mid + 1;
-            } else {
-                right = mid;
-            }
-        }
-        left
+/// The status is the most severe one (cancelled, deadline, stopped on hit)
+/// and `columns_searched` the smallest of all groups.
+fn merge_outcomes(outcomes: Vec<SearchOutcome>) -> SearchOutcome {
+    let severity = |status: SearchStatus| match status {
+        SearchStatus::Completed => 0,
+        SearchStatus::StoppedOnHit => 1,
+        SearchStatus::DeadlineExceeded => 2,
+        SearchStatus::Cancelled => 3,
     };
 
-    // Return all matching entries
-    table[start_idx..].iter().take_while(move |entry| {
-        gen_hash_from_seed(entry.end_seed, consumption) as u32 == target_hash
-    })
+    let mut merged = SearchOutcome {
+        hits: Vec::new(),
+        tables: Vec::new(),
+        status: SearchStatus::Completed,
+        columns_searched: outcomes
+            .iter()
+            .map(|o| o.columns_searched)
+            .min()
+            .unwrap_or(0),
+    };
+    for outcome in outcomes {
+        merged.hits.extend(outcome.hits);
+        merged.tables.extend(outcome.tables);
+        if severity(outcome.status) > severity(merged.status) {
+            merged.status = outcome.status;
+        }
+    }
+    merged
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::MAX_CHAIN_LENGTH;
+    use crate::domain::chain::compute_chain;
     use crate::domain::sfmt::Sfmt;
+    use crate::infra::table_sort::sort_table_parallel;
 
     #[test]
-    fn test_binary_search_empty_table() {
+    fn test_gallop_empty_table() {
         let table: Vec<ChainEntry> = vec![];
-        let results: Vec<_> = binary_search_by_end_hash(&table, 12345, 417).collect();
+        let mut probes = 0;
+        let results = gallop_by_end_hash(&TableRef::from(&table), 0, 12345, 417, &mut probes);
         assert!(results.is_empty());
+        assert_eq!(probes, 0);
     }
 
     #[test]
-    fn test_search_column_empty_table() {
+    fn test_search_tables_empty_table() {
         let table: Vec<ChainEntry> = vec![];
-        let results = search_column(417, 12345, 0, &table, 0);
-        assert!(results.is_empty());
+        let outcome = search_tables(
+            417,
+            &[12345],
+            &[TableRef::from(&table)],
+            [0],
+            &SearchOptions::default(),
+            |hash, column, chain_length| [walk_to_chain_end(hash, column, chain_length, 417, 0)],
+        );
+        assert!(outcome.hits.is_empty());
+        assert_eq!(outcome.tables[0].chains_replayed, 0);
+        assert_eq!(outcome.status, SearchStatus::Completed);
+    }
+
+    #[test]
+    fn test_merge_join_matches_linear_scan() {
+        let mut table: Vec<ChainEntry> = (0..64)
+            .map(|seed| compute_chain(seed, 417, 0, MAX_CHAIN_LENGTH))
+            .collect();
+        sort_table_parallel(&mut table, 417);
+        let table_ref = TableRef::from(&table);
+        let end_hashes: Vec<u64> = table
+            .iter()
+            .map(|entry| gen_hash_from_seed(entry.end_seed, 417))
+            .collect();
+
+        // Keys present in the table (including duplicates) and keys between them
+        let mut lookups: Vec<Lookup> = end_hashes
+            .iter()
+            .step_by(7)
+            .flat_map(|&key| [key, key, key + 1])
+            .enumerate()
+            .map(|(target, key)| Lookup {
+                key,
+                column: 0,
+                target,
+            })
+            .collect();
+        lookups.sort_unstable();
+
+        let mut probes = 0;
+        let matches = merge_join_end_hashes(&table_ref, &lookups, 417, &mut probes);
+
+        let expected: Vec<(Lookup, Range<usize>)> = lookups
+            .iter()
+            .filter_map(|lookup| {
+                let start = end_hashes.iter().position(|&h| h == lookup.key)?;
+                let end = start
+                    + end_hashes[start..]
+                        .iter()
+                        .take_while(|&&h| h == lookup.key)
+                        .count();
+                Some((*lookup, start..end))
+            })
+            .collect();
+        assert_eq!(matches, expected);
+        assert!(probes > 0);
     }
 
     #[test]
@@ -260,6 +1489,380 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    fn needle_values_from_seed(seed: u32, consumption: i32) -> [u64; 8] {
+        let mut sfmt = Sfmt::new(seed);
+        sfmt.skip(consumption as usize);
+        std::array::from_fn(|_| sfmt.gen_rand_u64() % 17)
+    }
+
+    #[test]
+    fn test_search_missing_seeds_empty() {
+        let needle_values = [1u64, 2, 3, 4, 5, 6, 7, 8];
+        let results = search_missing_seeds(needle_values, 417, &[]);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_missing_seeds_finds_listed_seed() {
+        let missing: Vec<u32> = (1000..1037).collect();
+        let needle_values = needle_values_from_seed(1030, 417);
+
+        let results = search_missing_seeds(needle_values, 417, &missing);
+        assert_eq!(results, vec![1030]);
+    }
+
+    #[test]
+    fn test_search_missing_seeds_streams_view() {
+        use crate::domain::missing_blocks::encode_missing_blocks;
+        use crate::domain::missing_format::MissingSeedsHeader;
+        use crate::infra::missing_seeds_io::MissingSeedsView;
+
+        let missing: Vec<u32> = (1000..1037).chain([70_000, u32::MAX]).collect();
+        let mut header = MissingSeedsHeader::new(&TableHeader::new(417, true), 39);
+        header.set_compressed(true);
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend(encode_missing_blocks(&missing));
+        let view = MissingSeedsView::from_bytes(bytes, Some(417)).unwrap();
+
+        for seed in [1030, 70_000] {
+            let needle_values = needle_values_from_seed(seed, 417);
+            assert_eq!(search_missing_seeds(needle_values, 417, &view), vec![seed]);
+        }
+    }
+
+    #[test]
+    fn test_search_missing_seeds_unlisted_seed() {
+        let missing: Vec<u32> = (1000..1005).collect();
+        let needle_values = needle_values_from_seed(12345, 417);
+
+        let results = search_missing_seeds(needle_values, 417, &missing);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_filter_by_extra_needles_keeps_matching_seed() {
+        let extra = gen_needle_values(42, 417, NEEDLE_COUNT + 4)[NEEDLE_COUNT..].to_vec();
+        let candidates = [7u32, 42, 1000, 123456];
+
+        let filter = filter_by_extra_needles(&candidates, 417, &extra);
+        assert_eq!(filter.remaining, vec![42]);
+        assert_eq!(filter.removed_per_needle.len(), 4);
+        assert_eq!(filter.removed_per_needle.iter().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn test_filter_by_extra_needles_counts_first_mismatch() {
+        let mut extra = gen_needle_values(42, 417, NEEDLE_COUNT + 3)[NEEDLE_COUNT..].to_vec();
+        extra[1] = (extra[1] + 1) % 17;
+
+        let filter = filter_by_extra_needles(&[42], 417, &extra);
+        assert!(filter.remaining.is_empty());
+        assert_eq!(filter.removed_per_needle, vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_filter_by_extra_needles_without_extra() {
+        let filter = filter_by_extra_needles(&[1, 2, 3], 417, &[]);
+        assert_eq!(filter.remaining, vec![1, 2, 3]);
+        assert!(filter.removed_per_needle.is_empty());
+    }
+
+    fn mini_table(table_id: u32) -> Vec<ChainEntry> {
+        let mut table: Vec<ChainEntry> = (0..8)
+            .map(|seed| compute_chain(seed, 417, table_id, MAX_CHAIN_LENGTH))
+            .collect();
+        sort_table_parallel(&mut table, 417);
+        table
+    }
+
+    fn misread(mut needle_values: [u64; 8], position: usize) -> [u64; 8] {
+        needle_values[position] = (needle_values[position] + 1) % 17;
+        needle_values
+    }
+
+    #[test]
+    fn test_search_seeds_tolerant_corrects_misread_needle() {
+        let table = mini_table(0);
+        let observed = misread(needle_values_from_seed(3, 417), 2);
+
+        let hits = search_seeds_tolerant(observed, 417, &table, 0, &NeedleTolerance::off_by_one());
+        let hit = hits
+            .iter()
+            .find(|hit| hit.seed == 3)
+            .expect("seed 3 should be found");
+
+        assert_eq!(hit.needle_values, needle_values_from_seed(3, 417));
+        assert_eq!(hit.corrections.len(), 1);
+        assert_eq!(hit.corrections[0].position, 2);
+        assert_eq!(hit.corrections[0].observed, observed[2]);
+    }
+
+    #[test]
+    fn test_search_seeds_tolerant_ranks_exact_first() {
+        let table = mini_table(0);
+        let observed = needle_values_from_seed(3, 417);
+
+        let hits = search_seeds_tolerant(observed, 417, &table, 0, &NeedleTolerance::off_by_one());
+        assert_eq!(hits[0].seed, 3);
+        assert!(hits[0].corrections.is_empty());
+        assert!(
+            hits.windows(2)
+                .all(|w| w[0].corrections.len() <= w[1].corrections.len())
+        );
+    }
+
+    #[test]
+    fn test_search_seeds_tolerant_over_limit() {
+        let table = mini_table(0);
+        let observed = needle_values_from_seed(3, 417);
+        let tolerance = NeedleTolerance::one_unknown().with_max_corrections(NEEDLE_COUNT);
+
+        assert!(search_seeds_tolerant(observed, 417, &table, 0, &tolerance).is_empty());
+        assert!(search_missing_seeds_tolerant(observed, 417, &[3u32][..], &tolerance).is_empty());
+    }
+
+    #[test]
+    fn test_rank_tolerant_hits_keeps_best_per_seed() {
+        let candidates =
+            enumerate_needle_candidates([1, 2, 3, 4, 5, 6, 7, 8], &NeedleTolerance::off_by_one());
+
+        // Seed 42 found from two corrections and from the reading itself
+        let hits = rank_tolerant_hits(&candidates, [(5, 42), (2, 42), (3, 7), (0, 42)]);
+        assert_eq!(
+            hits.iter().map(|hit| hit.seed).collect::<Vec<_>>(),
+            vec![42, 7]
+        );
+        assert!(hits[0].corrections.is_empty());
+        assert_eq!(hits[1].corrections, candidates[3].corrections);
+
+        // Without the reading, the correction ranked first is kept
+        let hits = rank_tolerant_hits(&candidates, [(5, 42), (2, 42)]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].corrections, candidates[2].corrections);
+    }
+
+    /// Seed at the given column of the chain starting at `start_seed`
+    fn seed_at_column(start_seed: u32, column: u32, table_id: u32) -> u32 {
+        (0..column).fold(start_seed, |s, n| {
+            reduce_hash_with_salt(gen_hash_from_seed(s, 417), n, table_id)
+        })
+    }
+
+    #[test]
+    fn test_search_report_hit_provenance() {
+        let table = mini_table(0);
+        let seed = seed_at_column(2, 5, 0);
+
+        let report = search_seeds_with_report(needle_values_from_seed(seed, 417), 417, &table, 0);
+        let hit = report
+            .hits
+            .iter()
+            .find(|hit| hit.start_seed == 2 && hit.column == 5)
+            .expect("seed should be found in the chain starting at 2");
+
+        assert_eq!(hit.seed, seed);
+        assert_eq!(hit.table_id, 0);
+        assert_eq!(table[hit.chain_index].start_seed, 2);
+        assert!(report.seeds().contains(&seed));
+    }
+
+    #[test]
+    fn test_search_uses_table_chain_length() {
+        let chain_length = 16;
+        let mut table: Vec<ChainEntry> = (0..8)
+            .map(|seed| compute_chain(seed, 417, 0, chain_length))
+            .collect();
+        sort_table_parallel(&mut table, 417);
+        let seed = seed_at_column(2, 9, 0);
+
+        let report = search_seeds_with_report(
+            needle_values_from_seed(seed, 417),
+            417,
+            TableRef::new(&table).with_chain_length(chain_length),
+            0,
+        );
+        assert!(
+            report
+                .hits
+                .iter()
+                .any(|hit| hit.seed == seed && hit.column == 9)
+        );
+        assert_eq!(report.columns_searched, chain_length);
+    }
+
+    #[test]
+    fn test_search_compact_table_matches_entries() {
+        use crate::domain::compact::{CompactTable, CompactTableRef};
+        use crate::infra::table_sort::sort_table_with_end_hashes;
+
+        let mut table = mini_table(0);
+        let end_hashes = sort_table_with_end_hashes(&mut table, 417);
+        let compact = CompactTable::encode(&table, &end_hashes);
+        let bytes = compact.to_le_bytes();
+        let compact_ref = CompactTableRef::new(*compact.descriptor(), &bytes).unwrap();
+
+        let needle = needle_values_from_seed(seed_at_column(2, 5, 0), 417);
+        let expected = search_seeds_with_report(needle, 417, &table, 0);
+        let report = search_seeds_with_report(needle, 417, TableRef::from_compact(compact_ref), 0);
+
+        assert!(!expected.hits.is_empty());
+        assert_eq!(report.hits, expected.hits);
+    }
+
+    #[test]
+    fn test_search_le_view_matches_entries() {
+        use crate::domain::chain::{LeChainEntries, LeEndHashes};
+        use crate::infra::table_sort::sort_table_with_end_hashes;
+
+        let mut table = mini_table(0);
+        let end_hashes = sort_table_with_end_hashes(&mut table, 417);
+        let entry_bytes: Vec<u8> = table
+            .iter()
+            .flat_map(|e| [e.start_seed.to_le_bytes(), e.end_seed.to_le_bytes()])
+            .flatten()
+            .collect();
+        let hash_bytes: Vec<u8> = end_hashes.iter().flat_map(|h| h.to_le_bytes()).collect();
+        let entries = LeChainEntries::new(&entry_bytes).unwrap();
+
+        let needle = needle_values_from_seed(seed_at_column(2, 5, 0), 417);
+        let expected = search_seeds_with_report(needle, 417, &table, 0);
+        assert!(!expected.hits.is_empty());
+
+        let report = search_seeds_with_report(needle, 417, entries, 0);
+        assert_eq!(report.hits, expected.hits);
+
+        let with_hashes =
+            TableRef::from_le(entries).with_le_end_hashes(LeEndHashes::new(&hash_bytes).unwrap());
+        let report = search_seeds_with_report(needle, 417, with_hashes, 0);
+        assert_eq!(report.hits, expected.hits);
+    }
+
+    #[test]
+    fn test_search_report_stats() {
+        let table = mini_table(0);
+        let report = search_seeds_with_report(needle_values_from_seed(3, 417), 417, &table, 0);
+
+        assert_eq!(report.tables.len(), 1);
+        let stats = &report.tables[0];
+        assert_eq!(stats.table_id, 0);
+        assert_eq!(stats.hits, report.hits.len());
+        assert!(stats.probes > 0);
+        assert_eq!(
+            stats.chains_replayed,
+            stats.false_alarms + report.hits.len() as u64
+        );
+        assert_eq!(report.false_alarms(), stats.false_alarms);
+    }
+
+    #[test]
+    fn test_search_report_deterministic_order() {
+        let table = mini_table(0);
+        let needle_values = needle_values_from_seed(3, 417);
+
+        let first = search_seeds_with_report(needle_values, 417, &table, 0);
+        let second = search_seeds_with_report(needle_values, 417, &table, 0);
+
+        assert_eq!(first.hits, second.hits);
+        assert!(first.hits.windows(2).all(|w| w[0] < w[1]));
+
+        let seeds = search_seeds(needle_values, 417, &table, 0);
+        assert!(seeds.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_search_with_options_reports_progress() {
+        let table = mini_table(0);
+        let calls = AtomicU32::new(0);
+        let last = AtomicU32::new(0);
+
+        let report = search_seeds_with_options(
+            needle_values_from_seed(3, 417),
+            417,
+            &table,
+            0,
+            SearchOptions::default().with_progress(|done, total| {
+                assert_eq!(total, MAX_CHAIN_LENGTH);
+                calls.fetch_add(1, Ordering::Relaxed);
+                last.fetch_max(done, Ordering::Relaxed);
+            }),
+        );
+
+        assert_eq!(report.status, SearchStatus::Completed);
+        assert_eq!(report.columns_searched, MAX_CHAIN_LENGTH);
+        assert_eq!(calls.load(Ordering::Relaxed), MAX_CHAIN_LENGTH);
+        assert_eq!(last.load(Ordering::Relaxed), MAX_CHAIN_LENGTH);
+        assert!(report.seeds().contains(&3));
+    }
+
+    #[test]
+    fn test_search_with_options_cancelled() {
+        let table = mini_table(0);
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let report = search_seeds_with_options(
+            needle_values_from_seed(3, 417),
+            417,
+            &table,
+            0,
+            SearchOptions::default().with_cancel(token),
+        );
+
+        assert_eq!(report.status, SearchStatus::Cancelled);
+        assert_eq!(report.columns_searched, 0);
+        assert!(report.hits.is_empty());
+    }
+
+    #[test]
+    fn test_search_with_options_deadline_passed() {
+        let table = mini_table(0);
+
+        let report = search_seeds_with_options(
+            needle_values_from_seed(3, 417),
+            417,
+            &table,
+            0,
+            SearchOptions::default().with_deadline(Instant::now()),
+        );
+
+        assert_eq!(report.status, SearchStatus::DeadlineExceeded);
+        assert_eq!(report.columns_searched, 0);
+    }
+
+    #[test]
+    fn test_search_with_options_stop_on_hit() {
+        let table = mini_table(0);
+
+        let report = search_seeds_with_options(
+            needle_values_from_seed(3, 417),
+            417,
+            &table,
+            0,
+            SearchOptions::default().with_stop_on_hit(true),
+        );
+
+        assert!(!report.hits.is_empty());
+        match report.status {
+            SearchStatus::StoppedOnHit => assert!(report.columns_searched < MAX_CHAIN_LENGTH),
+            SearchStatus::Completed => assert_eq!(report.columns_searched, MAX_CHAIN_LENGTH),
+            status => panic!("unexpected status: {:?}", status),
+        }
+    }
+
+    #[test]
+    fn test_search_missing_seeds_tolerant() {
+        let missing: Vec<u32> = (1000..1037).collect();
+        let observed = misread(needle_values_from_seed(1030, 417), 7);
+
+        assert!(search_missing_seeds(observed, 417, &missing).is_empty());
+
+        let hits =
+            search_missing_seeds_tolerant(observed, 417, &missing, &NeedleTolerance::off_by_one());
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].seed, 1030);
+        assert_eq!(hits[0].corrections[0].position, 7);
+    }
+
     #[cfg(feature = "multi-sfmt")]
     #[test]
     fn test_search_seeds_x16_empty_tables() {
@@ -272,10 +1875,124 @@ mod tests {
 
     #[cfg(feature = "multi-sfmt")]
     #[test]
-    fn test_search_column_x16_empty_tables() {
-        let empty: Vec<ChainEntry> = vec![];
-        let tables: [&[ChainEntry]; 16] = std::array::from_fn(|_| empty.as_slice());
-        let results = search_column_x16(417, 12345, 0, &tables);
-        assert!(results.is_empty());
+    fn test_walk_to_chain_end_x16_matches_single() {
+        let hash = gen_hash(needle_values_from_seed(3, 417));
+        let salts = table_salts_x16(TABLE_IDS_X16);
+        let hashes =
+            walk_to_chain_end_x16(hash, MAX_CHAIN_LENGTH - 3, MAX_CHAIN_LENGTH, 417, &salts);
+        for (table_id, &end_hash) in hashes.iter().enumerate() {
+            assert_eq!(
+                end_hash,
+                walk_to_chain_end(
+                    hash,
+                    MAX_CHAIN_LENGTH - 3,
+                    MAX_CHAIN_LENGTH,
+                    417,
+                    table_id as u32,
+                )
+            );
+        }
+    }
+
+    #[cfg(feature = "multi-sfmt")]
+    #[test]
+    fn test_search_x16_matches_single_table_search() {
+        let tables: Vec<Vec<ChainEntry>> = (0..16).map(mini_table).collect();
+        let refs: [&[ChainEntry]; 16] = std::array::from_fn(|i| tables[i].as_slice());
+        let needle_values = needle_values_from_seed(seed_at_column(4, 3, 5), 417);
+
+        let x16 = search_seeds_x16(needle_values, 417, refs);
+        let single: Vec<(u32, u32)> = (0..16u32)
+            .flat_map(|table_id| {
+                search_seeds(needle_values, 417, refs[table_id as usize], table_id)
+                    .into_iter()
+                    .map(move |seed| (table_id, seed))
+            })
+            .collect();
+
+        assert_eq!(x16, single);
+        assert!(x16.contains(&(5, seed_at_column(4, 3, 5))));
+    }
+
+    #[test]
+    fn test_search_multi_table_no_tables() {
+        let report =
+            search_seeds_multi_table_with_report(needle_values_from_seed(3, 417), 417, &[]);
+        assert!(report.hits.is_empty());
+        assert!(report.tables.is_empty());
+        assert_eq!(report.status, SearchStatus::Completed);
+    }
+
+    #[test]
+    fn test_search_multi_table_matches_single_table_search() {
+        // 17 tables: one full group of 16 and a padded remainder with multi-sfmt
+        let tables: Vec<Vec<ChainEntry>> = (0..17).map(mini_table).collect();
+        let refs: Vec<TableRef> = tables.iter().map(|t| TableRef::new(t)).collect();
+        let seed = seed_at_column(4, 3, 16);
+        let needle_values = needle_values_from_seed(seed, 417);
+
+        let multi = search_seeds_multi_table(needle_values, 417, &refs);
+        let single: Vec<(u32, u32)> = (0..17u32)
+            .flat_map(|table_id| {
+                search_seeds(needle_values, 417, refs[table_id as usize], table_id)
+                    .into_iter()
+                    .map(move |seed| (table_id, seed))
+            })
+            .collect();
+
+        assert_eq!(multi, single);
+        assert!(multi.contains(&(16, seed)));
+    }
+
+    #[test]
+    fn test_search_multi_table_report_and_progress() {
+        let tables: Vec<Vec<ChainEntry>> = (0..17).map(mini_table).collect();
+        let refs: Vec<TableRef> = tables.iter().map(|t| TableRef::new(t)).collect();
+        let calls = AtomicU32::new(0);
+        let last = AtomicU32::new(0);
+        let reported_total = AtomicU32::new(0);
+
+        let report = search_seeds_multi_table_with_options(
+            needle_values_from_seed(3, 417),
+            417,
+            &refs,
+            SearchOptions::default().with_progress(|done, total| {
+                reported_total.store(total, Ordering::Relaxed);
+                calls.fetch_add(1, Ordering::Relaxed);
+                last.fetch_max(done, Ordering::Relaxed);
+            }),
+        );
+
+        let total = reported_total.load(Ordering::Relaxed);
+        assert_eq!(report.status, SearchStatus::Completed);
+        assert_eq!(report.columns_searched, MAX_CHAIN_LENGTH);
+        assert_eq!(calls.load(Ordering::Relaxed), total);
+        assert_eq!(last.load(Ordering::Relaxed), total);
+        let table_ids: Vec<u32> = report.tables.iter().map(|t| t.table_id).collect();
+        assert_eq!(table_ids, (0..17).collect::<Vec<u32>>());
+        assert!(
+            report
+                .hits
+                .iter()
+                .any(|hit| hit.seed == 3 && hit.table_id == 0)
+        );
+    }
+
+    #[test]
+    fn test_search_multi_table_cancelled() {
+        let tables: Vec<Vec<ChainEntry>> = (0..3).map(mini_table).collect();
+        let refs: Vec<TableRef> = tables.iter().map(|t| TableRef::new(t)).collect();
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let report = search_seeds_multi_table_with_options(
+            needle_values_from_seed(3, 417),
+            417,
+            &refs,
+            SearchOptions::default().with_cancel(token),
+        );
+
+        assert_eq!(report.status, SearchStatus::Cancelled);
+        assert_eq!(report.columns_searched, 0);
     }
 }
@@ -0,0 +1,680 @@
+//! Table verification
+//!
+//! Spot-checks the contents of a table: a table can have the right size and
+//! sort order and still hold wrong chains (a different reduction function, a
+//! generator bug or a partially corrupted file). Sampled chains are
+//! recomputed, the sort order is checked against recomputed end hashes, and
+//! seeds taken from random chains are searched for again.
+
+use crate::app::searcher::search_seeds_multi_table;
+use crate::domain::chain::{TableData, TableRef, enumerate_chain_seeds};
+use crate::domain::hash::gen_hash_from_seed;
+use crate::domain::needle::gen_needle_values;
+use crate::domain::sfmt::Sfmt;
+use rayon::prelude::*;
+use std::collections::BTreeSet;
+
+#[cfg(not(feature = "multi-sfmt"))]
+use crate::domain::chain::compute_chain;
+#[cfg(feature = "multi-sfmt")]
+use crate::domain::chain::compute_chains_x16;
+
+/// Default number of chains recomputed per table
+pub const DEFAULT_VERIFY_SAMPLES: usize = 256;
+
+/// Default number of seed → needles → search round trips
+pub const DEFAULT_ROUND_TRIPS: usize = 16;
+
+/// Options for table verification
+#[derive(Clone, Debug)]
+pub struct VerifyOptions {
+    /// RNG consumption value of the table
+    pub consumption: i32,
+    /// Number of chains recomputed per table (default: DEFAULT_VERIFY_SAMPLES;
+    /// every chain if the table is smaller)
+    pub samples: usize,
+    /// Number of round trips over all tables (default: DEFAULT_ROUND_TRIPS)
+    pub round_trips: usize,
+    /// Check the sort order of every chain (default: true)
+    pub check_order: bool,
+    /// Whether the table is sorted (default: true); the sort order check and
+    /// the round trips are skipped for unsorted tables, which cannot be searched
+    pub sorted: bool,
+    /// Seed of the RNG choosing the chains; the same seed checks the same chains
+    pub seed: u32,
+}
+
+impl VerifyOptions {
+    /// Create options for the given consumption
+    pub fn new(consumption: i32) -> Self {
+        Self {
+            consumption,
+            samples: DEFAULT_VERIFY_SAMPLES,
+            round_trips: DEFAULT_ROUND_TRIPS,
+            check_order: true,
+            sorted: true,
+            seed: 0,
+        }
+    }
+
+    /// Set the number of chains recomputed per table
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Set the number of round trips
+    pub fn with_round_trips(mut self, round_trips: usize) -> Self {
+        self.round_trips = round_trips;
+        self
+    }
+
+    /// Enable or disable the sort order check
+    pub fn with_check_order(mut self, check_order: bool) -> Self {
+        self.check_order = check_order;
+        self
+    }
+
+    /// Set whether the table is sorted (`TableHeader::is_sorted`)
+    pub fn with_sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Set the RNG seed
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// A check that failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyFailure {
+    /// Recomputing the chain from its start seed does not reach its end
+    ChainMismatch {
+        table_id: u32,
+        index: usize,
+        start_seed: u32,
+    },
+    /// A stored end hash differs from the hash of the end seed
+    /// (`index` is the first such chain)
+    EndHashMismatch {
+        table_id: u32,
+        index: usize,
+        count: usize,
+    },
+    /// A chain has a smaller end key than the chain before it
+    /// (`index` is the first such chain)
+    OutOfOrder {
+        table_id: u32,
+        index: usize,
+        count: usize,
+    },
+    /// A seed on a chain was not found by searching its needles
+    RoundTripMissed {
+        table_id: u32,
+        index: usize,
+        column: u32,
+        seed: u32,
+    },
+}
+
+impl VerifyFailure {
+    /// Table the failure was found in
+    pub fn table_id(&self) -> u32 {
+        match *self {
+            Self::ChainMismatch { table_id, .. }
+            | Self::EndHashMismatch { table_id, .. }
+            | Self::OutOfOrder { table_id, .. }
+            | Self::RoundTripMissed { table_id, .. } => table_id,
+        }
+    }
+
+    /// Index of the (first) failing chain
+    pub fn index(&self) -> usize {
+        match *self {
+            Self::ChainMismatch { index, .. }
+            | Self::EndHashMismatch { index, .. }
+            | Self::OutOfOrder { index, .. }
+            | Self::RoundTripMissed { index, .. } => index,
+        }
+    }
+}
+
+impl std::fmt::Display for VerifyFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::ChainMismatch {
+                table_id,
+                index,
+                start_seed,
+            } => write!(
+                f,
+                "Chain {}:{} (start seed {:08X}) does not reach its end",
+                table_id, index, start_seed
+            ),
+            Self::EndHashMismatch {
+                table_id,
+                index,
+                count,
+            } => write!(
+                f,
+                "Table {}: {} stored end hashes do not match their end seeds (first: chain {})",
+                table_id, count, index
+            ),
+            Self::OutOfOrder {
+                table_id,
+                index,
+                count,
+            } => write!(
+                f,
+                "Table {}: {} chains are out of order (first: chain {})",
+                table_id, count, index
+            ),
+            Self::RoundTripMissed {
+                table_id,
+                index,
+                column,
+                seed,
+            } => write!(
+                f,
+                "Seed {:08X} at column {} of chain {}:{} was not found by search",
+                seed, column, table_id, index
+            ),
+        }
+    }
+}
+
+/// Verification result of a single table
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableVerifyReport {
+    /// Table the report is for
+    pub table_id: u32,
+    /// Number of chains recomputed
+    pub chains_checked: usize,
+    /// Whether the sort order of every chain was checked
+    pub order_checked: bool,
+    /// Failed checks
+    pub failures: Vec<VerifyFailure>,
+}
+
+impl TableVerifyReport {
+    /// Check if every check passed
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Result of the round trips
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoundTripReport {
+    /// Number of seeds searched
+    pub checked: usize,
+    /// Whether the round trips were skipped because the table is not sorted
+    pub skipped: bool,
+    /// Seeds that were not found
+    pub failures: Vec<VerifyFailure>,
+}
+
+impl RoundTripReport {
+    /// Check if every seed was found
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Verification result of all tables of a file
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// Reports per table, indexed by table_id
+    pub tables: Vec<TableVerifyReport>,
+    /// Round trips over all tables
+    pub round_trips: RoundTripReport,
+}
+
+impl VerifyReport {
+    /// Check if every check passed
+    pub fn passed(&self) -> bool {
+        self.tables.iter().all(TableVerifyReport::passed) && self.round_trips.passed()
+    }
+
+    /// All failed checks, table checks first
+    pub fn failures(&self) -> impl Iterator<Item = &VerifyFailure> {
+        self.tables
+            .iter()
+            .flat_map(|table| &table.failures)
+            .chain(&self.round_trips.failures)
+    }
+}
+
+/// Verify all tables of a file
+///
+/// `tables` are indexed by table_id, as returned by `table_refs`. Runs
+/// `verify_table` on every table, then `verify_round_trips`.
+pub fn verify_tables(tables: &[TableRef<'_>], options: &VerifyOptions) -> VerifyReport {
+    VerifyReport {
+        tables: tables
+            .iter()
+            .enumerate()
+            .map(|(table_id, table)| verify_table(table, table_id as u32, options))
+            .collect(),
+        round_trips: verify_round_trips(tables, options),
+    }
+}
+
+/// Verify a single table
+///
+/// Recomputes `options.samples` random chains with the salt of `table_id`
+/// and compares them with the stored end seeds (or, for compact tables, the
+/// stored end hashes). With `check_order`, the end hash of every chain is
+/// recomputed with `gen_hash_from_seed` to check the sort order and any
+/// stored end hashes. The sort order is not checked for unsorted tables.
+pub fn verify_table(
+    table: &TableRef<'_>,
+    table_id: u32,
+    options: &VerifyOptions,
+) -> TableVerifyReport {
+    let indices = sample_indices(table.len(), options.samples, options.seed, table_id);
+    let mut failures = verify_chains(table, options.consumption, table_id, &indices);
+    let order_checked = options.check_order && options.sorted;
+    if order_checked {
+        failures.extend(verify_order(table, options.consumption, table_id));
+    }
+
+    TableVerifyReport {
+        table_id,
+        chains_checked: indices.len(),
+        order_checked,
+        failures,
+    }
+}
+
+/// Recompute the chains at `indices` of a table
+///
+/// Returns a `ChainMismatch` for every chain that does not reach its end.
+pub fn verify_chains(
+    table: &TableRef<'_>,
+    consumption: i32,
+    table_id: u32,
+    indices: &[usize],
+) -> Vec<VerifyFailure> {
+    let start_seeds: Vec<u32> = indices.iter().map(|&i| table.start_seed(i)).collect();
+    let end_seeds = recompute_end_seeds(&start_seeds, consumption, table_id, table.chain_length);
+
+    indices
+        .iter()
+        .zip(start_seeds.iter().zip(&end_seeds))
+        .filter(|&(&index, (_, &end_seed))| match table.end_seed(index) {
+            Some(stored) => stored != end_seed,
+            None => {
+                let key = table
+                    .sort_key
+                    .key(gen_hash_from_seed(end_seed, consumption));
+                key != table.sort_key.key(table.end_hash(index, consumption))
+            }
+        })
+        .map(|(&index, (&start_seed, _))| VerifyFailure::ChainMismatch {
+            table_id,
+            index,
+            start_seed,
+        })
+        .collect()
+}
+
+/// Check the sort order of a table against recomputed end hashes
+///
+/// End hashes are recomputed from the end seeds with `gen_hash_from_seed`
+/// (compact tables store end hashes only, which are checked as stored).
+/// Stored end hashes are compared with the recomputed ones.
+pub fn verify_order(table: &TableRef<'_>, consumption: i32, table_id: u32) -> Vec<VerifyFailure> {
+    let hashes: Vec<u64> = (0..table.len())
+        .into_par_iter()
+        .map(|index| match table.end_seed(index) {
+            Some(end_seed) => gen_hash_from_seed(end_seed, consumption),
+            None => table.end_hash(index, consumption),
+        })
+        .collect();
+
+    let mut failures = Vec::new();
+
+    let stores_end_seeds_and_hashes = matches!(
+        table.data,
+        TableData::Native {
+            end_hashes: Some(_),
+            ..
+        } | TableData::LittleEndian {
+            end_hashes: Some(_),
+            ..
+        }
+    );
+    if stores_end_seeds_and_hashes {
+        let mismatched: Vec<usize> = (0..hashes.len())
+            .into_par_iter()
+            .filter(|&index| table.end_hash(index, consumption) != hashes[index])
+            .collect();
+        if let Some(&index) = mismatched.first() {
+            failures.push(VerifyFailure::EndHashMismatch {
+                table_id,
+                index,
+                count: mismatched.len(),
+            });
+        }
+    }
+
+    let unordered: Vec<usize> = (1..hashes.len())
+        .into_par_iter()
+        .filter(|&index| table.sort_key.key(hashes[index]) < table.sort_key.key(hashes[index - 1]))
+        .collect();
+    if let Some(&index) = unordered.first() {
+        failures.push(VerifyFailure::OutOfOrder {
+            table_id,
+            index,
+            count: unordered.len(),
+        });
+    }
+
+    failures
+}
+
+/// Search for seeds taken from random chains
+///
+/// Each round trip picks a random chain and column, computes the needles of
+/// the seed at that column and searches all tables with
+/// `search_seeds_multi_table`. The seed must be found in the table of its
+/// chain. Empty tables are skipped, and unsorted tables are not searched
+/// (the report is marked as skipped).
+pub fn verify_round_trips(tables: &[TableRef<'_>], options: &VerifyOptions) -> RoundTripReport {
+    if !options.sorted {
+        return RoundTripReport {
+            skipped: true,
+            ..RoundTripReport::default()
+        };
+    }
+
+    let table_ids: Vec<u32> = (0..tables.len() as u32)
+        .filter(|&table_id| {
+            let table = &tables[table_id as usize];
+            !table.is_empty() && table.chain_length > 0
+        })
+        .collect();
+    if table_ids.is_empty() {
+        return RoundTripReport::default();
+    }
+
+    let mut rng = Sfmt::new(options.seed ^ ROUND_TRIP_SALT);
+    let mut report = RoundTripReport::default();
+    for _ in 0..options.round_trips {
+        let table_id = table_ids[random_below(&mut rng, table_ids.len() as u64) as usize];
+        let table = &tables[table_id as usize];
+        let index = random_below(&mut rng, table.len() as u64) as usize;
+        let column = random_below(&mut rng, table.chain_length as u64) as u32;
+
+        let seeds = enumerate_chain_seeds(
+            table.start_seed(index),
+            options.consumption,
+            table_id,
+            table.chain_length,
+        );
+        let seed = seeds[column as usize];
+        let needles: [u64; 8] = gen_needle_values(seed, options.consumption, 8)
+            .try_into()
+            .unwrap();
+
+        let results = search_seeds_multi_table(needles, options.consumption, tables);
+        report.checked += 1;
+        if !results.contains(&(table_id, seed)) {
+            report.failures.push(VerifyFailure::RoundTripMissed {
+                table_id,
+                index,
+                column,
+                seed,
+            });
+        }
+    }
+    report
+}
+
+/// Mixed into the RNG seed so that round trips and chain samples differ
+const ROUND_TRIP_SALT: u32 = 0x8000_0000;
+
+/// Uniform-enough random value below `bound` (`bound` > 0)
+fn random_below(rng: &mut Sfmt, bound: u64) -> u64 {
+    rng.gen_rand_u64() % bound
+}
+
+/// Choose `samples` distinct chain indices, in ascending order
+///
+/// Every index is returned if the table has no more than `samples` chains.
+fn sample_indices(len: usize, samples: usize, seed: u32, table_id: u32) -> Vec<usize> {
+    if len <= samples {
+        return (0..len).collect();
+    }
+
+    let mut rng = Sfmt::new(seed.wrapping_add(table_id));
+    let mut indices = BTreeSet::new();
+    while indices.len() < samples {
+        indices.insert(random_below(&mut rng, len as u64) as usize);
+    }
+    indices.into_iter().collect()
+}
+
+/// Recompute the end seeds of chains starting at `start_seeds`
+#[cfg(feature = "multi-sfmt")]
+fn recompute_end_seeds(
+    start_seeds: &[u32],
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+) -> Vec<u32> {
+    start_seeds
+        .par_chunks(16)
+        .flat_map_iter(|chunk| {
+            let mut seeds = [0u32; 16];
+            seeds[..chunk.len()].copy_from_slice(chunk);
+            compute_chains_x16(seeds, consumption, table_id, chain_length)
+                .into_iter()
+                .take(chunk.len())
+                .map(|entry| entry.end_seed)
+        })
+        .collect()
+}
+
+/// Recompute the end seeds of chains starting at `start_seeds`
+#[cfg(not(feature = "multi-sfmt"))]
+fn recompute_end_seeds(
+    start_seeds: &[u32],
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+) -> Vec<u32> {
+    start_seeds
+        .par_iter()
+        .map(|&start_seed| compute_chain(start_seed, consumption, table_id, chain_length).end_seed)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chain::{ChainEntry, compute_chain};
+    use crate::domain::compact::{CompactTable, CompactTableRef};
+    use crate::infra::table_sort::sort_table_parallel;
+
+    const CONSUMPTION: i32 = 417;
+    const CHAIN_LENGTH: u32 = 8;
+
+    fn sorted_table(table_id: u32) -> Vec<ChainEntry> {
+        let mut entries: Vec<ChainEntry> = (0..64)
+            .map(|seed| compute_chain(seed * 97 + table_id, CONSUMPTION, table_id, CHAIN_LENGTH))
+            .collect();
+        sort_table_parallel(&mut entries, CONSUMPTION);
+        entries
+    }
+
+    fn options() -> VerifyOptions {
+        VerifyOptions::new(CONSUMPTION)
+            .with_samples(16)
+            .with_round_trips(8)
+            .with_seed(7)
+    }
+
+    #[test]
+    fn test_verify_tables_pass() {
+        let tables = [sorted_table(0), sorted_table(1)];
+        let refs: Vec<TableRef> = tables
+            .iter()
+            .map(|t| TableRef::new(t).with_chain_length(CHAIN_LENGTH))
+            .collect();
+
+        let report = verify_tables(&refs, &options());
+        assert!(report.passed(), "{:?}", report);
+        assert_eq!(report.tables.len(), 2);
+        assert!(report.tables.iter().all(|t| t.order_checked));
+        assert!(report.tables.iter().all(|t| t.chains_checked == 16));
+        assert_eq!(report.round_trips.checked, 8);
+
+        // Same seed, same samples
+        assert_eq!(verify_tables(&refs, &options()), report);
+    }
+
+    #[test]
+    fn test_verify_chains_reports_corrupted_chain() {
+        let mut entries = sorted_table(1);
+        entries[5].start_seed ^= 1;
+        let table = TableRef::new(&entries).with_chain_length(CHAIN_LENGTH);
+
+        let indices: Vec<usize> = (0..entries.len()).collect();
+        assert_eq!(
+            verify_chains(&table, CONSUMPTION, 1, &indices),
+            vec![VerifyFailure::ChainMismatch {
+                table_id: 1,
+                index: 5,
+                start_seed: entries[5].start_seed,
+            }]
+        );
+
+        // Wrong salt: every chain fails
+        assert_eq!(
+            verify_chains(&table, CONSUMPTION, 2, &indices).len(),
+            entries.len()
+        );
+
+        // Small tables are checked in full
+        let report = verify_table(&table, 1, &options().with_samples(100));
+        assert_eq!(report.chains_checked, entries.len());
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].index(), 5);
+    }
+
+    #[test]
+    fn test_verify_chains_compact() {
+        let entries = sorted_table(0);
+        let end_hashes: Vec<u64> = entries
+            .iter()
+            .map(|e| gen_hash_from_seed(e.end_seed, CONSUMPTION))
+            .collect();
+        let compact = CompactTable::encode(&entries, &end_hashes);
+        let bytes = compact.to_le_bytes();
+        let table =
+            TableRef::from_compact(CompactTableRef::new(*compact.descriptor(), &bytes).unwrap())
+                .with_chain_length(CHAIN_LENGTH);
+
+        let report = verify_table(&table, 0, &options().with_samples(100));
+        assert!(report.passed(), "{:?}", report);
+        assert!(!verify_table(&table, 1, &options()).passed());
+    }
+
+    #[test]
+    fn test_verify_order() {
+        let mut entries = sorted_table(0);
+        entries.swap(10, 11);
+        let table = TableRef::new(&entries).with_chain_length(CHAIN_LENGTH);
+        assert_eq!(
+            verify_order(&table, CONSUMPTION, 0),
+            vec![VerifyFailure::OutOfOrder {
+                table_id: 0,
+                index: 11,
+                count: 1,
+            }]
+        );
+
+        // Stored end hashes that do not match the end seeds
+        let entries = sorted_table(0);
+        let mut end_hashes: Vec<u64> = entries
+            .iter()
+            .map(|e| gen_hash_from_seed(e.end_seed, CONSUMPTION))
+            .collect();
+        end_hashes[3] ^= 1;
+        let table = TableRef::new(&entries).with_end_hashes(&end_hashes);
+        assert_eq!(
+            verify_order(&table, CONSUMPTION, 0),
+            vec![VerifyFailure::EndHashMismatch {
+                table_id: 0,
+                index: 3,
+                count: 1,
+            }]
+        );
+
+        let report = verify_table(&table, 0, &options().with_check_order(false));
+        assert!(!report.order_checked);
+    }
+
+    #[test]
+    fn test_verify_round_trips_reports_miss() {
+        // Table 1 was generated with the wrong salt, so its seeds are not found
+        let tables = [sorted_table(0), sorted_table(0)];
+        let refs: Vec<TableRef> = tables
+            .iter()
+            .map(|t| TableRef::new(t).with_chain_length(CHAIN_LENGTH))
+            .collect();
+
+        let report = verify_round_trips(&refs, &options().with_round_trips(32));
+        assert_eq!(report.checked, 32);
+        assert!(!report.failures.is_empty());
+        assert!(report.failures.iter().all(|f| f.table_id() == 1));
+
+        assert_eq!(
+            verify_round_trips(&[], &options()),
+            RoundTripReport::default()
+        );
+    }
+
+    #[test]
+    fn test_verify_tables_unsorted() {
+        let tables: Vec<Vec<ChainEntry>> = (0..2)
+            .map(|table_id| {
+                (0..64)
+                    .map(|seed| compute_chain(seed * 97, CONSUMPTION, table_id, CHAIN_LENGTH))
+                    .collect()
+            })
+            .collect();
+        let refs: Vec<TableRef> = tables
+            .iter()
+            .map(|t| TableRef::new(t).with_chain_length(CHAIN_LENGTH))
+            .collect();
+
+        // Checked as sorted, the generation order fails the sort order check
+        let report = verify_tables(&refs, &options());
+        assert!(
+            report
+                .failures()
+                .any(|f| matches!(f, VerifyFailure::OutOfOrder { .. }))
+        );
+
+        let report = verify_tables(&refs, &options().with_sorted(false));
+        assert!(report.passed(), "{:?}", report);
+        assert!(report.tables.iter().all(|t| !t.order_checked));
+        assert!(report.tables.iter().all(|t| t.chains_checked == 16));
+        assert!(report.round_trips.skipped);
+        assert_eq!(report.round_trips.checked, 0);
+
+        // Corrupted chains are still found
+        let mut entries = tables[1].clone();
+        entries[5].start_seed ^= 1;
+        let table = TableRef::new(&entries).with_chain_length(CHAIN_LENGTH);
+        let report = verify_table(&table, 1, &options().with_samples(100).with_sorted(false));
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].index(), 5);
+    }
+}
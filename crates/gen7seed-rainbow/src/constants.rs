@@ -67,21 +67,62 @@ pub const TABLE_MAGIC: [u8; 8] = *b"G7RBOW\x00\x00";
 /// "G7MISS\x00\x00" in ASCII
 pub const MISSING_MAGIC: [u8; 8] = *b"G7MISS\x00\x00";
 
+/// Magic number for generation checkpoint files
+/// "G7CKPT\x00\x00" in ASCII
+pub const CHECKPOINT_MAGIC: [u8; 8] = *b"G7CKPT\x00\x00";
+
 /// Current file format version (shared by table and missing seeds)
 pub const FILE_FORMAT_VERSION: u16 = 1;
 
+/// Format version of compact rainbow tables (bit-packed start seeds and
+/// Elias–Fano coded end hashes, see `domain::compact`)
+pub const COMPACT_FORMAT_VERSION: u16 = 2;
+
+/// Format version of sectioned rainbow tables (section directory after the
+/// header, see `domain::section_format`)
+pub const SECTIONED_FORMAT_VERSION: u16 = 3;
+
+/// Format version of compressed missing seeds files (delta/varint or bitmap
+/// coded blocks, see `domain::missing_blocks`)
+pub const MISSING_COMPRESSED_FORMAT_VERSION: u16 = 2;
+
 /// Header size in bytes (shared by table and missing seeds)
 pub const FILE_HEADER_SIZE: usize = 64;
 
+/// Environment variable fixing the creation timestamp recorded in new files
+/// (Unix epoch seconds, as in reproducible builds)
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
 /// File extension for rainbow table
 pub const TABLE_FILE_EXTENSION: &str = "g7rt";
 
 /// File extension for missing seeds
 pub const MISSING_FILE_EXTENSION: &str = "g7ms";
 
+/// File extension for generation checkpoints
+pub const CHECKPOINT_FILE_EXTENSION: &str = "g7cp";
+
+/// Number of chains generated per checkpoint chunk
+pub const CHECKPOINT_CHUNK_SIZE: u32 = 1 << 16; // 65,536
+
 // =============================================================================
 // Table flags
 // =============================================================================
 
 /// Flag: Table is sorted by end_seed hash
 pub const FLAG_SORTED: u32 = 1 << 0;
+
+/// Flag: Sort key is the full 33-bit end hash (otherwise its lower 32 bits)
+pub const FLAG_FULL_HASH_SORT: u32 = 1 << 1;
+
+/// Flag: File contains a section of precomputed end hashes after the chains
+pub const FLAG_END_HASH_KEYS: u32 = 1 << 2;
+
+/// Flag: Chain start seeds were chosen from seeds not covered by earlier chains
+pub const FLAG_COVERAGE_GUIDED: u32 = 1 << 3;
+
+/// Flag: Every chain of a table has a distinct end hash (merged chains were replaced)
+pub const FLAG_PERFECT: u32 = 1 << 4;
+
+/// Byte size of a precomputed end hash
+pub const END_HASH_SIZE: usize = 8;
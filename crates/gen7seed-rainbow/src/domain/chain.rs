@@ -3,7 +3,8 @@
 //! This module provides chain entry structure and functions for
 //! chain generation and verification in rainbow table operations.
 
-use crate::constants::MAX_CHAIN_LENGTH;
+use crate::constants::{CHAIN_ENTRY_SIZE, END_HASH_SIZE, MAX_CHAIN_LENGTH};
+use crate::domain::compact::CompactTableRef;
 use crate::domain::hash::{gen_hash_from_seed, reduce_hash_with_salt};
 
 #[cfg(feature = "multi-sfmt")]
@@ -15,7 +16,7 @@ use crate::domain::hash::reduce_hash_x16_with_salt;
 /// Chain entry structure
 ///
 /// File format: (start_seed, end_seed)
-/// Sort order: gen_hash_from_seed(end_seed, consumption) as u32 ascending
+/// Sort order: gen_hash_from_seed(end_seed, consumption) ascending (see `EndHashKey`)
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ChainEntry {
@@ -35,19 +36,415 @@ impl ChainEntry {
     }
 }
 
+/// Sort key of the end hashes in a sorted table
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EndHashKey {
+    /// Full 33-bit end hash (tables with `FLAG_FULL_HASH_SORT`)
+    #[default]
+    Full,
+    /// Lower 32 bits of the end hash (tables created before `FLAG_FULL_HASH_SORT`)
+    Truncated,
+}
+
+impl EndHashKey {
+    /// Apply the key to an end hash
+    #[inline]
+    pub fn key(self, end_hash: u64) -> u64 {
+        match self {
+            Self::Full => end_hash,
+            Self::Truncated => end_hash as u32 as u64,
+        }
+    }
+}
+
+/// Read access to the chain entries of a table
+///
+/// Implemented by in-memory entries and by `LeChainEntries`, so the same
+/// code can process loaded tables and memory-mapped files.
+pub trait ChainTable: Sync {
+    /// Number of chains
+    fn len(&self) -> usize;
+
+    /// Entry at `index`
+    fn entry(&self, index: usize) -> ChainEntry;
+
+    /// Check if the table has no chains
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Start seed of the entry at `index`
+    #[inline]
+    fn start_seed(&self, index: usize) -> u32 {
+        self.entry(index).start_seed
+    }
+}
+
+impl ChainTable for [ChainEntry] {
+    fn len(&self) -> usize {
+        <[ChainEntry]>::len(self)
+    }
+
+    #[inline]
+    fn entry(&self, index: usize) -> ChainEntry {
+        self[index]
+    }
+}
+
+impl ChainTable for Vec<ChainEntry> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    #[inline]
+    fn entry(&self, index: usize) -> ChainEntry {
+        self[index]
+    }
+}
+
+/// Chain entries stored as little-endian bytes
+///
+/// Zero-copy view of the entry section of a table file (`start_seed`,
+/// `end_seed` as little-endian u32 pairs). Entries are decoded on access,
+/// so the view works on any target endianness and with any alignment.
+#[derive(Clone, Copy, Debug)]
+pub struct LeChainEntries<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> LeChainEntries<'a> {
+    /// Create a view of `bytes`
+    ///
+    /// Returns `None` if the length is not a multiple of the entry size.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        bytes
+            .len()
+            .is_multiple_of(CHAIN_ENTRY_SIZE)
+            .then_some(Self { bytes })
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.bytes.len() / CHAIN_ENTRY_SIZE
+    }
+
+    /// Check if the view has no entries
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Decode the entry at `index`
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    #[inline]
+    pub fn get(&self, index: usize) -> ChainEntry {
+        let offset = index * CHAIN_ENTRY_SIZE;
+        let bytes = &self.bytes[offset..offset + CHAIN_ENTRY_SIZE];
+        ChainEntry::new(
+            u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            u32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+        )
+    }
+
+    /// Iterate over the decoded entries
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = ChainEntry> + 'a {
+        let bytes = self.bytes;
+        (0..self.len()).map(move |index| Self { bytes }.get(index))
+    }
+
+    /// Decode all entries into a vector
+    pub fn to_vec(&self) -> Vec<ChainEntry> {
+        self.iter().collect()
+    }
+}
+
+impl ChainTable for LeChainEntries<'_> {
+    fn len(&self) -> usize {
+        LeChainEntries::len(self)
+    }
+
+    #[inline]
+    fn entry(&self, index: usize) -> ChainEntry {
+        self.get(index)
+    }
+}
+
+/// Precomputed end hashes stored as little-endian bytes
+///
+/// Zero-copy view of the end hash section of a table file, decoded on
+/// access like `LeChainEntries`.
+#[derive(Clone, Copy, Debug)]
+pub struct LeEndHashes<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> LeEndHashes<'a> {
+    /// Create a view of `bytes`
+    ///
+    /// Returns `None` if the length is not a multiple of the hash size.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        bytes
+            .len()
+            .is_multiple_of(END_HASH_SIZE)
+            .then_some(Self { bytes })
+    }
+
+    /// Number of end hashes
+    pub fn len(&self) -> usize {
+        self.bytes.len() / END_HASH_SIZE
+    }
+
+    /// Check if the view has no end hashes
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Decode the end hash at `index`
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    #[inline]
+    pub fn get(&self, index: usize) -> u64 {
+        let offset = index * END_HASH_SIZE;
+        u64::from_le_bytes(
+            self.bytes[offset..offset + END_HASH_SIZE]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    /// Decode all end hashes into a vector
+    pub fn to_vec(&self) -> Vec<u64> {
+        (0..self.len()).map(|index| self.get(index)).collect()
+    }
+}
+
+/// Storage of the chains of a `TableRef`
+#[derive(Clone, Copy, Debug)]
+pub enum TableData<'a> {
+    /// In-memory entries with optional precomputed end hashes (same order)
+    Native {
+        entries: &'a [ChainEntry],
+        end_hashes: Option<&'a [u64]>,
+    },
+    /// Little-endian entries and end hashes, e.g. a memory-mapped file
+    LittleEndian {
+        entries: LeChainEntries<'a>,
+        end_hashes: Option<LeEndHashes<'a>>,
+    },
+    /// Compact table holding the start seeds and end hashes
+    Compact(CompactTableRef<'a>),
+}
+
+/// Sorted chain table prepared for search
+///
+/// Bundles the chains with their sort key, their chain length and,
+/// optionally, the precomputed end hash of every entry so that search does
+/// not need to recompute them. The chains are either in memory, little-endian
+/// bytes decoded on access, or a compact table (see `TableData`).
+#[derive(Clone, Copy, Debug)]
+pub struct TableRef<'a> {
+    /// Chains sorted by end hash
+    pub data: TableData<'a>,
+    /// Sort key of the chains
+    pub sort_key: EndHashKey,
+    /// Chain length the entries were generated with
+    pub chain_length: u32,
+}
+
+impl<'a> TableRef<'a> {
+    /// Create a table sorted by the full end hash, without precomputed hashes
+    ///
+    /// The chain length defaults to `MAX_CHAIN_LENGTH`.
+    pub fn new(entries: &'a [ChainEntry]) -> Self {
+        Self::from_data(TableData::Native {
+            entries,
+            end_hashes: None,
+        })
+    }
+
+    /// Create a view of little-endian entries (see `TableRef::new`)
+    pub fn from_le(entries: LeChainEntries<'a>) -> Self {
+        Self::from_data(TableData::LittleEndian {
+            entries,
+            end_hashes: None,
+        })
+    }
+
+    /// Create a view of a compact table (sorted by the full end hash)
+    ///
+    /// The chain length defaults to `MAX_CHAIN_LENGTH`.
+    pub fn from_compact(compact: CompactTableRef<'a>) -> Self {
+        Self::from_data(TableData::Compact(compact))
+    }
+
+    fn from_data(data: TableData<'a>) -> Self {
+        Self {
+            data,
+            sort_key: EndHashKey::Full,
+            chain_length: MAX_CHAIN_LENGTH,
+        }
+    }
+
+    /// Set the precomputed end hashes
+    ///
+    /// # Panics
+    /// Panics if the table is not in memory or if the length does not match
+    /// the number of entries.
+    pub fn with_end_hashes(mut self, hashes: &'a [u64]) -> Self {
+        let TableData::Native { entries, .. } = self.data else {
+            panic!("end hashes must be in memory like the entries");
+        };
+        assert_eq!(
+            hashes.len(),
+            entries.len(),
+            "end hash count must match entry count"
+        );
+        self.data = TableData::Native {
+            entries,
+            end_hashes: Some(hashes),
+        };
+        self
+    }
+
+    /// Set little-endian precomputed end hashes
+    ///
+    /// # Panics
+    /// Panics if the entries are not little-endian bytes or if the length
+    /// does not match the number of entries.
+    pub fn with_le_end_hashes(mut self, hashes: LeEndHashes<'a>) -> Self {
+        let TableData::LittleEndian { entries, .. } = self.data else {
+            panic!("end hashes must be little-endian like the entries");
+        };
+        assert_eq!(
+            hashes.len(),
+            entries.len(),
+            "end hash count must match entry count"
+        );
+        self.data = TableData::LittleEndian {
+            entries,
+            end_hashes: Some(hashes),
+        };
+        self
+    }
+
+    /// Set the sort key
+    pub fn with_sort_key(mut self, sort_key: EndHashKey) -> Self {
+        self.sort_key = sort_key;
+        self
+    }
+
+    /// Set the chain length
+    pub fn with_chain_length(mut self, chain_length: u32) -> Self {
+        self.chain_length = chain_length;
+        self
+    }
+
+    /// Number of chains
+    pub fn len(&self) -> usize {
+        match self.data {
+            TableData::Native { entries, .. } => entries.len(),
+            TableData::LittleEndian { entries, .. } => entries.len(),
+            TableData::Compact(compact) => compact.len(),
+        }
+    }
+
+    /// Check if the table has no chains
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the end hashes are available without recomputing them
+    pub fn has_end_hashes(&self) -> bool {
+        match self.data {
+            TableData::Native { end_hashes, .. } => end_hashes.is_some(),
+            TableData::LittleEndian { end_hashes, .. } => end_hashes.is_some(),
+            TableData::Compact(_) => true,
+        }
+    }
+
+    /// Start seed of the entry at `index`
+    #[inline]
+    pub fn start_seed(&self, index: usize) -> u32 {
+        match self.data {
+            TableData::Native { entries, .. } => entries[index].start_seed,
+            TableData::LittleEndian { entries, .. } => entries.get(index).start_seed,
+            TableData::Compact(compact) => compact.start_seed(index),
+        }
+    }
+
+    /// End seed of the entry at `index` (None for a compact table, which
+    /// stores end hashes only)
+    #[inline]
+    pub fn end_seed(&self, index: usize) -> Option<u32> {
+        match self.data {
+            TableData::Native { entries, .. } => Some(entries[index].end_seed),
+            TableData::LittleEndian { entries, .. } => Some(entries.get(index).end_seed),
+            TableData::Compact(_) => None,
+        }
+    }
+
+    /// Full end hash of the entry at `index` (computed if not precomputed)
+    #[inline]
+    pub fn end_hash(&self, index: usize, consumption: i32) -> u64 {
+        match self.data {
+            TableData::Native {
+                end_hashes: Some(end_hashes),
+                ..
+            } => end_hashes[index],
+            TableData::Native { entries, .. } => {
+                gen_hash_from_seed(entries[index].end_seed, consumption)
+            }
+            TableData::LittleEndian {
+                end_hashes: Some(end_hashes),
+                ..
+            } => end_hashes.get(index),
+            TableData::LittleEndian { entries, .. } => {
+                gen_hash_from_seed(entries.get(index).end_seed, consumption)
+            }
+            TableData::Compact(compact) => compact.end_hash(index),
+        }
+    }
+}
+
+impl<'a> From<&'a [ChainEntry]> for TableRef<'a> {
+    fn from(entries: &'a [ChainEntry]) -> Self {
+        Self::new(entries)
+    }
+}
+
+impl<'a> From<&'a Vec<ChainEntry>> for TableRef<'a> {
+    fn from(entries: &'a Vec<ChainEntry>) -> Self {
+        Self::new(entries)
+    }
+}
+
+impl<'a> From<LeChainEntries<'a>> for TableRef<'a> {
+    fn from(entries: LeChainEntries<'a>) -> Self {
+        Self::from_le(entries)
+    }
+}
+
 /// Compute a single chain
 ///
-/// Starting from start_seed, repeat hash → reduce MAX_CHAIN_LENGTH times
+/// Starting from start_seed, repeat hash → reduce `chain_length` times
 /// using the salted reduction function.
 ///
 /// # Arguments
 /// * `start_seed` - The starting seed of the chain
 /// * `consumption` - The RNG consumption value
-/// * `table_id` - The table identifier (0 to NUM_TABLES-1), used as salt
-pub fn compute_chain(start_seed: u32, consumption: i32, table_id: u32) -> ChainEntry {
+/// * `table_id` - The table identifier (0 to num_tables-1), used as salt
+/// * `chain_length` - The chain length (see `TableParams`)
+pub fn compute_chain(
+    start_seed: u32,
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+) -> ChainEntry {
     let mut current_seed = start_seed;
 
-    for n in 0..MAX_CHAIN_LENGTH {
+    for n in 0..chain_length {
         let hash = gen_hash_from_seed(current_seed, consumption);
         current_seed = reduce_hash_with_salt(hash, n, table_id);
     }
@@ -68,7 +465,7 @@ pub fn compute_chain(start_seed: u32, consumption: i32, table_id: u32) -> ChainE
 /// * `column` - The column position to verify
 /// * `target_hash` - The expected hash value
 /// * `consumption` - The RNG consumption value
-/// * `table_id` - The table identifier (0 to NUM_TABLES-1), used as salt
+/// * `table_id` - The table identifier (0 to num_tables-1), used as salt
 ///
 /// # Returns
 /// `Some(seed)` if the hash matches, `None` otherwise
@@ -109,16 +506,18 @@ pub fn verify_chain(
 /// # Arguments
 /// * `start_seeds` - 16 starting seeds
 /// * `consumption` - The RNG consumption value
-/// * `table_id` - The table identifier (0 to NUM_TABLES-1), used as salt
+/// * `table_id` - The table identifier (0 to num_tables-1), used as salt
+/// * `chain_length` - The chain length (see `TableParams`)
 #[cfg(feature = "multi-sfmt")]
 pub fn compute_chains_x16(
     start_seeds: [u32; 16],
     consumption: i32,
     table_id: u32,
+    chain_length: u32,
 ) -> [ChainEntry; 16] {
     let mut current_seeds = start_seeds;
 
-    for n in 0..MAX_CHAIN_LENGTH {
+    for n in 0..chain_length {
         // Calculate 16 hashes simultaneously
         let hashes = gen_hash_from_seed_x16(current_seeds, consumption);
 
@@ -136,22 +535,28 @@ pub fn compute_chains_x16(
 
 /// Enumerate all seeds in a chain
 ///
-/// Starting from start_seed, repeat hash → reduce MAX_CHAIN_LENGTH times,
+/// Starting from start_seed, repeat hash → reduce `chain_length` times,
 /// collecting all seeds along the path.
 ///
 /// Returns a vector containing start_seed and all subsequent seeds
-/// (MAX_CHAIN_LENGTH + 1 elements total).
+/// (`chain_length` + 1 elements total).
 ///
 /// # Arguments
 /// * `start_seed` - The starting seed of the chain
 /// * `consumption` - The RNG consumption value
-/// * `table_id` - The table identifier (0 to NUM_TABLES-1), used as salt
-pub fn enumerate_chain_seeds(start_seed: u32, consumption: i32, table_id: u32) -> Vec<u32> {
-    let mut seeds = Vec::with_capacity(MAX_CHAIN_LENGTH as usize + 1);
+/// * `table_id` - The table identifier (0 to num_tables-1), used as salt
+/// * `chain_length` - The chain length (see `TableParams`)
+pub fn enumerate_chain_seeds(
+    start_seed: u32,
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+) -> Vec<u32> {
+    let mut seeds = Vec::with_capacity(chain_length as usize + 1);
     let mut current = start_seed;
     seeds.push(current);
 
-    for n in 0..MAX_CHAIN_LENGTH {
+    for n in 0..chain_length {
         let hash = gen_hash_from_seed(current, consumption);
         current = reduce_hash_with_salt(hash, n, table_id);
         seeds.push(current);
@@ -168,13 +573,15 @@ pub fn enumerate_chain_seeds(start_seed: u32, consumption: i32, table_id: u32) -
 /// # Arguments
 /// * `start_seeds` - 16 starting seeds
 /// * `consumption` - consumption value
-/// * `table_id` - The table identifier (0 to NUM_TABLES-1), used as salt
+/// * `table_id` - The table identifier (0 to num_tables-1), used as salt
+/// * `chain_length` - The chain length (see `TableParams`)
 /// * `on_seeds` - callback invoked at each step with 16 seeds
 #[cfg(feature = "multi-sfmt")]
 pub fn enumerate_chain_seeds_x16<F>(
     start_seeds: [u32; 16],
     consumption: i32,
     table_id: u32,
+    chain_length: u32,
     mut on_seeds: F,
 ) where
     F: FnMut([u32; 16]),
@@ -182,7 +589,7 @@ pub fn enumerate_chain_seeds_x16<F>(
     let mut current_seeds = start_seeds;
     on_seeds(current_seeds); // Report initial seeds
 
-    for n in 0..MAX_CHAIN_LENGTH {
+    for n in 0..chain_length {
         let hashes = gen_hash_from_seed_x16(current_seeds, consumption);
         current_seeds = reduce_hash_x16_with_salt(hashes, n, table_id);
         on_seeds(current_seeds);
@@ -193,24 +600,135 @@ pub fn enumerate_chain_seeds_x16<F>(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_end_hash_key() {
+        let hash = (1u64 << 32) | 5;
+        assert_eq!(EndHashKey::Full.key(hash), hash);
+        assert_eq!(EndHashKey::Truncated.key(hash), 5);
+    }
+
+    #[test]
+    fn test_table_ref_end_hash() {
+        let entries = vec![ChainEntry::new(1, 100), ChainEntry::new(2, 200)];
+        let computed = TableRef::from(&entries);
+        assert_eq!(computed.end_hash(1, 417), gen_hash_from_seed(200, 417));
+
+        let end_hashes = [11u64, 22];
+        let precomputed = TableRef::new(&entries).with_end_hashes(&end_hashes);
+        assert_eq!(precomputed.end_hash(1, 417), 22);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_table_ref_end_hash_length_mismatch() {
+        let entries = vec![ChainEntry::new(1, 100)];
+        let _ = TableRef::new(&entries).with_end_hashes(&[]);
+    }
+
+    #[test]
+    fn test_table_ref_from_compact() {
+        use crate::domain::compact::CompactTable;
+
+        let entries = vec![ChainEntry::new(7, 100), ChainEntry::new(3, 200)];
+        let end_hashes = [11u64, 22];
+        let table = CompactTable::encode(&entries, &end_hashes);
+        let bytes = table.to_le_bytes();
+        let compact = CompactTableRef::new(*table.descriptor(), &bytes).unwrap();
+
+        let table_ref = TableRef::from_compact(compact);
+        assert_eq!(table_ref.len(), 2);
+        assert_eq!(table_ref.start_seed(1), 3);
+        assert_eq!(table_ref.end_seed(1), None);
+        assert_eq!(table_ref.end_hash(1, 417), 22);
+    }
+
+    fn le_bytes(entries: &[ChainEntry]) -> Vec<u8> {
+        entries
+            .iter()
+            .flat_map(|e| [e.start_seed.to_le_bytes(), e.end_seed.to_le_bytes()])
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn test_le_chain_entries_decode() {
+        let entries = vec![
+            ChainEntry::new(0x0102_0304, 0xa0b0_c0d0),
+            ChainEntry::new(7, u32::MAX),
+        ];
+
+        // Unaligned view of the little-endian fixture
+        let mut bytes = vec![0u8];
+        bytes.extend(le_bytes(&entries));
+        let view = LeChainEntries::new(&bytes[1..]).unwrap();
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.to_vec(), entries);
+        assert_eq!(ChainTable::start_seed(&view, 1), 7);
+
+        // A byte-swapped (big-endian) fixture decodes to swapped values on any host
+        let swapped: Vec<u8> = entries
+            .iter()
+            .flat_map(|e| [e.start_seed.to_be_bytes(), e.end_seed.to_be_bytes()])
+            .flatten()
+            .collect();
+        let view = LeChainEntries::new(&swapped).unwrap();
+        assert_eq!(view.get(0), ChainEntry::new(0x0403_0201, 0xd0c0_b0a0));
+
+        assert!(LeChainEntries::new(&bytes[..7]).is_none());
+    }
+
+    #[test]
+    fn test_le_end_hashes_decode() {
+        let hashes = [0x1_0203_0405u64, 42];
+        let bytes: Vec<u8> = hashes.iter().flat_map(|h| h.to_le_bytes()).collect();
+        let view = LeEndHashes::new(&bytes).unwrap();
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.get(0), 0x1_0203_0405);
+        assert_eq!(view.to_vec(), hashes);
+
+        assert!(LeEndHashes::new(&bytes[..12]).is_none());
+    }
+
+    #[test]
+    fn test_table_ref_from_le_matches_native() {
+        let entries = vec![ChainEntry::new(1, 100), ChainEntry::new(2, 200)];
+        let bytes = le_bytes(&entries);
+        let view = LeChainEntries::new(&bytes).unwrap();
+
+        let native = TableRef::new(&entries);
+        let le = TableRef::from(view);
+        assert_eq!(le.len(), native.len());
+        for (i, entry) in entries.iter().enumerate() {
+            assert_eq!(le.start_seed(i), native.start_seed(i));
+            assert_eq!(le.end_seed(i), Some(entry.end_seed));
+            assert_eq!(le.end_hash(i, 417), native.end_hash(i, 417));
+        }
+        assert!(!le.has_end_hashes());
+
+        let hash_bytes: Vec<u8> = [11u64, 22].iter().flat_map(|h| h.to_le_bytes()).collect();
+        let le = le.with_le_end_hashes(LeEndHashes::new(&hash_bytes).unwrap());
+        assert!(le.has_end_hashes());
+        assert_eq!(le.end_hash(1, 417), 22);
+    }
+
     #[test]
     fn test_compute_chain_deterministic() {
-        let entry1 = compute_chain(12345, 417, 0);
-        let entry2 = compute_chain(12345, 417, 0);
+        let entry1 = compute_chain(12345, 417, 0, MAX_CHAIN_LENGTH);
+        let entry2 = compute_chain(12345, 417, 0, MAX_CHAIN_LENGTH);
         assert_eq!(entry1, entry2);
     }
 
     #[test]
     fn test_compute_chain_different_seeds() {
-        let entry1 = compute_chain(12345, 417, 0);
-        let entry2 = compute_chain(54321, 417, 0);
+        let entry1 = compute_chain(12345, 417, 0, MAX_CHAIN_LENGTH);
+        let entry2 = compute_chain(54321, 417, 0, MAX_CHAIN_LENGTH);
         assert_ne!(entry1.end_seed, entry2.end_seed);
     }
 
     #[test]
     fn test_compute_chain_different_consumption() {
-        let entry1 = compute_chain(12345, 417, 0);
-        let entry2 = compute_chain(12345, 477, 0);
+        let entry1 = compute_chain(12345, 417, 0, MAX_CHAIN_LENGTH);
+        let entry2 = compute_chain(12345, 477, 0, MAX_CHAIN_LENGTH);
         assert_ne!(entry1.end_seed, entry2.end_seed);
     }
 
@@ -220,9 +738,9 @@ mod tests {
         let consumption = 417;
 
         // Different table_ids should produce different end_seeds
-        let entry0 = compute_chain(seed, consumption, 0);
-        let entry1 = compute_chain(seed, consumption, 1);
-        let entry2 = compute_chain(seed, consumption, 2);
+        let entry0 = compute_chain(seed, consumption, 0, MAX_CHAIN_LENGTH);
+        let entry1 = compute_chain(seed, consumption, 1, MAX_CHAIN_LENGTH);
+        let entry2 = compute_chain(seed, consumption, 2, MAX_CHAIN_LENGTH);
 
         assert_ne!(
             entry0.end_seed, entry1.end_seed,
@@ -238,6 +756,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_chain_uses_chain_length() {
+        let seeds = enumerate_chain_seeds(12345, 417, 0, 10);
+        let entry = compute_chain(12345, 417, 0, 5);
+        assert_eq!(entry.end_seed, seeds[5]);
+    }
+
     #[test]
     fn test_chain_entry_size() {
         assert_eq!(std::mem::size_of::<ChainEntry>(), 8);
@@ -321,10 +846,10 @@ mod tests {
         let consumption = 417;
         let table_id = 0;
 
-        let multi_results = compute_chains_x16(seeds, consumption, table_id);
+        let multi_results = compute_chains_x16(seeds, consumption, table_id, MAX_CHAIN_LENGTH);
 
         for (i, seed) in seeds.iter().enumerate() {
-            let single_result = compute_chain(*seed, consumption, table_id);
+            let single_result = compute_chain(*seed, consumption, table_id, MAX_CHAIN_LENGTH);
             assert_eq!(
                 multi_results[i], single_result,
                 "Mismatch at index {} for seed {}",
@@ -339,8 +864,8 @@ mod tests {
         let seeds: [u32; 16] = std::array::from_fn(|i| 12345 + i as u32);
         let consumption = 417;
 
-        let results1 = compute_chains_x16(seeds, consumption, 0);
-        let results2 = compute_chains_x16(seeds, consumption, 0);
+        let results1 = compute_chains_x16(seeds, consumption, 0, MAX_CHAIN_LENGTH);
+        let results2 = compute_chains_x16(seeds, consumption, 0, MAX_CHAIN_LENGTH);
 
         assert_eq!(results1, results2);
     }
@@ -350,8 +875,8 @@ mod tests {
     fn test_compute_chains_x16_different_consumption() {
         let seeds: [u32; 16] = std::array::from_fn(|i| i as u32);
 
-        let results_417 = compute_chains_x16(seeds, 417, 0);
-        let results_477 = compute_chains_x16(seeds, 477, 0);
+        let results_417 = compute_chains_x16(seeds, 417, 0, MAX_CHAIN_LENGTH);
+        let results_477 = compute_chains_x16(seeds, 477, 0, MAX_CHAIN_LENGTH);
 
         for i in 0..16 {
             assert_ne!(
@@ -368,8 +893,8 @@ mod tests {
         let seeds: [u32; 16] = std::array::from_fn(|i| 100 + i as u32);
         let consumption = 417;
 
-        let results0 = compute_chains_x16(seeds, consumption, 0);
-        let results1 = compute_chains_x16(seeds, consumption, 1);
+        let results0 = compute_chains_x16(seeds, consumption, 0, MAX_CHAIN_LENGTH);
+        let results1 = compute_chains_x16(seeds, consumption, 1, MAX_CHAIN_LENGTH);
 
         // At least some entries should differ between tables
         let differ_count = results0
@@ -385,14 +910,14 @@ mod tests {
 
     #[test]
     fn test_enumerate_chain_seeds_length() {
-        let seeds = enumerate_chain_seeds(12345, 417, 0);
+        let seeds = enumerate_chain_seeds(12345, 417, 0, MAX_CHAIN_LENGTH);
         assert_eq!(seeds.len(), MAX_CHAIN_LENGTH as usize + 1);
     }
 
     #[test]
     fn test_enumerate_chain_seeds_starts_with_start_seed() {
         let start_seed = 12345u32;
-        let seeds = enumerate_chain_seeds(start_seed, 417, 0);
+        let seeds = enumerate_chain_seeds(start_seed, 417, 0, MAX_CHAIN_LENGTH);
         assert_eq!(seeds[0], start_seed);
     }
 
@@ -402,16 +927,16 @@ mod tests {
         let consumption = 417;
         let table_id = 0;
 
-        let seeds = enumerate_chain_seeds(start_seed, consumption, table_id);
-        let entry = compute_chain(start_seed, consumption, table_id);
+        let seeds = enumerate_chain_seeds(start_seed, consumption, table_id, MAX_CHAIN_LENGTH);
+        let entry = compute_chain(start_seed, consumption, table_id, MAX_CHAIN_LENGTH);
 
         assert_eq!(*seeds.last().unwrap(), entry.end_seed);
     }
 
     #[test]
     fn test_enumerate_chain_seeds_deterministic() {
-        let seeds1 = enumerate_chain_seeds(12345, 417, 0);
-        let seeds2 = enumerate_chain_seeds(12345, 417, 0);
+        let seeds1 = enumerate_chain_seeds(12345, 417, 0, MAX_CHAIN_LENGTH);
+        let seeds2 = enumerate_chain_seeds(12345, 417, 0, MAX_CHAIN_LENGTH);
         assert_eq!(seeds1, seeds2);
     }
 
@@ -424,15 +949,22 @@ mod tests {
 
         // Collect seeds from x16 version
         let mut x16_all_seeds: Vec<Vec<u32>> = vec![Vec::new(); 16];
-        enumerate_chain_seeds_x16(start_seeds, consumption, table_id, |seeds| {
-            for (i, &seed) in seeds.iter().enumerate() {
-                x16_all_seeds[i].push(seed);
-            }
-        });
+        enumerate_chain_seeds_x16(
+            start_seeds,
+            consumption,
+            table_id,
+            MAX_CHAIN_LENGTH,
+            |seeds| {
+                for (i, &seed) in seeds.iter().enumerate() {
+                    x16_all_seeds[i].push(seed);
+                }
+            },
+        );
 
         // Compare with single version
         for (i, &start_seed) in start_seeds.iter().enumerate() {
-            let single_seeds = enumerate_chain_seeds(start_seed, consumption, table_id);
+            let single_seeds =
+                enumerate_chain_seeds(start_seed, consumption, table_id, MAX_CHAIN_LENGTH);
             assert_eq!(
                 x16_all_seeds[i], single_seeds,
                 "Mismatch at index {} for seed {}",
@@ -447,7 +979,7 @@ mod tests {
         let start_seeds: [u32; 16] = std::array::from_fn(|i| i as u32);
         let mut callback_count = 0u32;
 
-        enumerate_chain_seeds_x16(start_seeds, 417, 0, |_| {
+        enumerate_chain_seeds_x16(start_seeds, 417, 0, MAX_CHAIN_LENGTH, |_| {
             callback_count += 1;
         });
 
@@ -0,0 +1,205 @@
+//! Generation checkpoint file format definitions
+//!
+//! A checkpoint holds the chains generated for one range of one table, so that
+//! an interrupted generation can be resumed. The header records the parameters
+//! the chains were generated with.
+
+use crate::constants::{
+    CHAIN_ENTRY_SIZE, CHECKPOINT_MAGIC, END_HASH_SIZE, FILE_FORMAT_VERSION, FILE_HEADER_SIZE,
+    FLAG_COVERAGE_GUIDED, FLAG_END_HASH_KEYS, FLAG_FULL_HASH_SORT, FLAG_PERFECT, FLAG_SORTED,
+};
+use crate::domain::table_format::{
+    StartPoints, TableFormatError, TableHeader, TableParams, creation_timestamp,
+};
+use std::ops::Range;
+
+/// Checkpoint file header metadata
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckpointHeader {
+    /// File format version
+    pub version: u16,
+    /// RNG consumption value
+    pub consumption: i32,
+    /// Chain length (steps per chain)
+    pub chain_length: u32,
+    /// Number of chains per table
+    pub chains_per_table: u32,
+    /// Number of tables in the final file
+    pub num_tables: u32,
+    /// Flags (same meaning as in `TableHeader`)
+    pub flags: u32,
+    /// Table the chains belong to
+    pub table_id: u32,
+    /// First start seed in this checkpoint (inclusive)
+    pub start: u32,
+    /// Last start seed in this checkpoint (exclusive)
+    pub end: u32,
+    /// Creation timestamp (Unix epoch seconds)
+    pub created_at: u64,
+}
+
+impl CheckpointHeader {
+    /// Create a new unsorted header for the given table parameters
+    pub fn new(consumption: i32, params: TableParams, table_id: u32, range: Range<u32>) -> Self {
+        let created_at = creation_timestamp();
+
+        Self {
+            version: FILE_FORMAT_VERSION,
+            consumption,
+            chain_length: params.chain_length,
+            chains_per_table: params.chains_per_table,
+            num_tables: params.num_tables,
+            flags: 0,
+            table_id,
+            start: range.start,
+            end: range.end,
+            created_at,
+        }
+    }
+
+    /// Table parameters of the final file
+    pub fn params(&self) -> TableParams {
+        TableParams::new(self.chain_length, self.chains_per_table, self.num_tables)
+    }
+
+    /// Range of start seeds covered by this checkpoint
+    pub fn range(&self) -> Range<u32> {
+        self.start..self.end
+    }
+
+    /// Number of chains in this checkpoint
+    pub fn chain_count(&self) -> u32 {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Check if the chains are sorted
+    pub fn is_sorted(&self) -> bool {
+        self.flags & FLAG_SORTED != 0
+    }
+
+    /// Set sorted flag (sorted by the full end hash)
+    pub fn set_sorted(&mut self, sorted: bool) {
+        if sorted {
+            self.flags |= FLAG_SORTED | FLAG_FULL_HASH_SORT;
+        } else {
+            self.flags &= !(FLAG_SORTED | FLAG_FULL_HASH_SORT);
+        }
+    }
+
+    /// Check if the file contains precomputed end hashes
+    pub fn has_end_hashes(&self) -> bool {
+        self.flags & FLAG_END_HASH_KEYS != 0
+    }
+
+    /// Set the precomputed end hashes flag
+    pub fn set_end_hashes(&mut self, present: bool) {
+        if present {
+            self.flags |= FLAG_END_HASH_KEYS;
+        } else {
+            self.flags &= !FLAG_END_HASH_KEYS;
+        }
+    }
+
+    /// Check if every chain of a table has a distinct end hash
+    pub fn is_perfect(&self) -> bool {
+        self.flags & FLAG_PERFECT != 0
+    }
+
+    /// Set the perfect table flag
+    pub fn set_perfect(&mut self, perfect: bool) {
+        if perfect {
+            self.flags |= FLAG_PERFECT;
+        } else {
+            self.flags &= !FLAG_PERFECT;
+        }
+    }
+
+    /// How the start seeds of the chains were chosen
+    pub fn start_points(&self) -> StartPoints {
+        StartPoints::from_flags(self.flags)
+    }
+
+    /// Record how the start seeds of the chains were chosen
+    pub fn set_start_points(&mut self, start_points: StartPoints) {
+        self.flags = (self.flags & !FLAG_COVERAGE_GUIDED) | start_points.flags();
+    }
+
+    /// Table header with the same parameters (for validation)
+    pub fn table_header(&self) -> TableHeader {
+        TableHeader {
+            version: self.version,
+            consumption: self.consumption,
+            chain_length: self.chain_length,
+            chains_per_table: self.chains_per_table,
+            num_tables: self.num_tables,
+            flags: self.flags,
+            created_at: self.created_at,
+            checksum: None,
+            chains_checksum: None,
+        }
+    }
+
+    /// Serialize header to bytes (64 bytes)
+    pub fn to_bytes(&self) -> [u8; FILE_HEADER_SIZE] {
+        let mut buf = [0u8; FILE_HEADER_SIZE];
+
+        buf[0..8].copy_from_slice(&CHECKPOINT_MAGIC);
+        buf[8..10].copy_from_slice(&self.version.to_le_bytes());
+        // 10..12 reserved
+        buf[12..16].copy_from_slice(&self.consumption.to_le_bytes());
+        buf[16..20].copy_from_slice(&self.chain_length.to_le_bytes());
+        buf[20..24].copy_from_slice(&self.chains_per_table.to_le_bytes());
+        buf[24..28].copy_from_slice(&self.num_tables.to_le_bytes());
+        buf[28..32].copy_from_slice(&self.flags.to_le_bytes());
+        buf[32..36].copy_from_slice(&self.table_id.to_le_bytes());
+        buf[36..40].copy_from_slice(&self.start.to_le_bytes());
+        buf[40..44].copy_from_slice(&self.end.to_le_bytes());
+        // 44..48 reserved
+        buf[48..56].copy_from_slice(&self.created_at.to_le_bytes());
+        // 56..64 reserved
+
+        buf
+    }
+
+    /// Deserialize header from bytes
+    pub fn from_bytes(buf: &[u8; FILE_HEADER_SIZE]) -> Result<Self, TableFormatError> {
+        if buf[0..8] != CHECKPOINT_MAGIC {
+            return Err(TableFormatError::InvalidMagic);
+        }
+
+        let version = u16::from_le_bytes([buf[8], buf[9]]);
+        if version != FILE_FORMAT_VERSION {
+            return Err(TableFormatError::UnsupportedVersion(version));
+        }
+
+        let u32_at = |i: usize| u32::from_le_bytes([buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]);
+
+        Ok(Self {
+            version,
+            consumption: i32::from_le_bytes([buf[12], buf[13], buf[14], buf[15]]),
+            chain_length: u32_at(16),
+            chains_per_table: u32_at(20),
+            num_tables: u32_at(24),
+            flags: u32_at(28),
+            table_id: u32_at(32),
+            start: u32_at(36),
+            end: u32_at(40),
+            created_at: u64::from_le_bytes([
+                buf[48], buf[49], buf[50], buf[51], buf[52], buf[53], buf[54], buf[55],
+            ]),
+        })
+    }
+}
+
+/// Calculate expected checkpoint file size from header
+///
+/// Layout: header, chains, then (if `FLAG_END_HASH_KEYS`) their end hashes.
+pub fn expected_checkpoint_file_size(header: &CheckpointHeader) -> u64 {
+    let chains = header.chain_count() as u64;
+    let key_size = if header.has_end_hashes() {
+        chains * END_HASH_SIZE as u64
+    } else {
+        0
+    };
+    FILE_HEADER_SIZE as u64 + chains * CHAIN_ENTRY_SIZE as u64 + key_size
+}
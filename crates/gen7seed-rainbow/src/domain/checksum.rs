@@ -0,0 +1,151 @@
+//! Data section checksum
+//!
+//! CRC-64/XZ over the bytes after the file header, stored in the header of
+//! `.g7rt` and `.g7ms` files so that truncated or corrupted downloads can be
+//! detected. The checksum is computed incrementally, so files can be
+//! verified while streaming them.
+
+/// Reflected CRC-64/XZ (ECMA-182) polynomial
+const POLY: u64 = 0xc96c_5795_d787_0f42;
+
+/// Slice-by-8 lookup tables
+const TABLES: [[u64; 256]; 8] = build_tables();
+
+const fn build_tables() -> [[u64; 256]; 8] {
+    let mut tables = [[0u64; 256]; 8];
+
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        tables[0][i] = crc;
+        i += 1;
+    }
+
+    let mut t = 1;
+    while t < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = tables[t - 1][i];
+            tables[t][i] = (prev >> 8) ^ tables[0][(prev & 0xff) as usize];
+            i += 1;
+        }
+        t += 1;
+    }
+
+    tables
+}
+
+/// Incremental data checksum
+#[derive(Clone, Copy, Debug)]
+pub struct Checksum {
+    crc: u64,
+}
+
+impl Default for Checksum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum {
+    /// Create a checksum of no data
+    pub fn new() -> Self {
+        Self { crc: !0 }
+    }
+
+    /// Add bytes to the checksum
+    pub fn update(&mut self, bytes: &[u8]) {
+        let mut crc = self.crc;
+
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let v = crc ^ u64::from_le_bytes(chunk.try_into().unwrap());
+            crc = TABLES[7][(v & 0xff) as usize]
+                ^ TABLES[6][((v >> 8) & 0xff) as usize]
+                ^ TABLES[5][((v >> 16) & 0xff) as usize]
+                ^ TABLES[4][((v >> 24) & 0xff) as usize]
+                ^ TABLES[3][((v >> 32) & 0xff) as usize]
+                ^ TABLES[2][((v >> 40) & 0xff) as usize]
+                ^ TABLES[1][((v >> 48) & 0xff) as usize]
+                ^ TABLES[0][(v >> 56) as usize];
+        }
+        for &byte in chunks.remainder() {
+            crc = (crc >> 8) ^ TABLES[0][((crc ^ byte as u64) & 0xff) as usize];
+        }
+
+        self.crc = crc;
+    }
+
+    /// CRC-64/XZ of the bytes added so far
+    pub fn crc(&self) -> u64 {
+        !self.crc
+    }
+
+    /// Value stored in file headers
+    ///
+    /// Headers use 0 for "no checksum recorded", so a CRC of 0 is stored as 1.
+    pub fn finish(&self) -> u64 {
+        self.crc().max(1)
+    }
+}
+
+/// Checksum of a byte slice (see `Checksum::finish`)
+pub fn data_checksum(bytes: &[u8]) -> u64 {
+    let mut checksum = Checksum::new();
+    checksum.update(bytes);
+    checksum.finish()
+}
+
+/// Result of verifying a file checksum
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    /// The data matches the checksum recorded in the header
+    Verified(u64),
+    /// The header has no checksum (file written before checksums were recorded)
+    NotRecorded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc64_xz_check_value() {
+        let mut checksum = Checksum::new();
+        checksum.update(b"123456789");
+        assert_eq!(checksum.crc(), 0x995d_c9bb_df19_39fa);
+    }
+
+    #[test]
+    fn test_incremental_matches_single_update() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 31 % 251) as u8).collect();
+
+        let mut checksum = Checksum::new();
+        for chunk in data.chunks(13) {
+            checksum.update(chunk);
+        }
+        assert_eq!(checksum.finish(), data_checksum(&data));
+    }
+
+    #[test]
+    fn test_detects_bit_flip() {
+        let mut data = vec![0u8; 4096];
+        let original = data_checksum(&data);
+        data[2000] ^= 0x80;
+        assert_ne!(data_checksum(&data), original);
+    }
+
+    #[test]
+    fn test_empty_data_is_recorded() {
+        assert_ne!(data_checksum(&[]), 0);
+    }
+}
@@ -0,0 +1,510 @@
+//! Compact table encoding
+//!
+//! A compact table stores each sorted table in two parts instead of 8-byte
+//! `ChainEntry` values:
+//! - the start seeds, bit-packed with the width of the largest start seed
+//!   (20 bits for the default 647,168 chains), in end hash order;
+//! - the full end hashes, Elias–Fano coded: the low bits of every hash are
+//!   bit-packed, the high bits are stored as a unary-coded bit vector, and the
+//!   position of every `SELECT_SAMPLE`-th hash in that bit vector is sampled.
+//!
+//! Every part is a sequence of little-endian u64 words, so a table can be read
+//! in place (e.g. from a memory-mapped file) without decoding it first.
+
+use crate::domain::chain::ChainEntry;
+
+/// Byte size of a compact table descriptor
+pub const COMPACT_DESCRIPTOR_SIZE: usize = 64;
+
+/// Number of end hashes per sampled position in the high bit vector
+pub const SELECT_SAMPLE: usize = 256;
+
+/// Sizes of the parts of one compact table
+///
+/// The parts follow each other in this order: start seeds, low bits, high
+/// bits, samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompactTableDescriptor {
+    /// Number of chains
+    pub len: u64,
+    /// Bits per start seed
+    pub start_bits: u32,
+    /// Low bits per end hash
+    pub low_bits: u32,
+    /// Words of bit-packed start seeds
+    pub start_words: u64,
+    /// Words of bit-packed low bits
+    pub low_words: u64,
+    /// Words of the high bit vector
+    pub high_words: u64,
+    /// Words of sampled positions
+    pub sample_words: u64,
+}
+
+impl CompactTableDescriptor {
+    /// Total number of words of the table
+    ///
+    /// Saturates at `u64::MAX` for descriptors that are not consistent.
+    pub fn total_words(&self) -> u64 {
+        self.start_words
+            .saturating_add(self.low_words)
+            .saturating_add(self.high_words)
+            .saturating_add(self.sample_words)
+    }
+
+    /// Total number of bytes of the table
+    ///
+    /// Saturates at `u64::MAX` for descriptors that are not consistent.
+    pub fn byte_size(&self) -> u64 {
+        self.total_words().saturating_mul(8)
+    }
+
+    /// Check that the part sizes match the number of chains and bit widths
+    pub fn is_consistent(&self) -> bool {
+        self.start_bits <= 32
+            && self.low_bits < 64
+            && packed_words(self.len, self.start_bits) == Some(self.start_words)
+            && packed_words(self.len, self.low_bits) == Some(self.low_words)
+            && self
+                .high_words
+                .checked_mul(64)
+                .is_some_and(|bits| bits > self.len)
+            && self.sample_words == self.len.div_ceil(SELECT_SAMPLE as u64)
+            && self.byte_size() < u64::MAX
+    }
+
+    /// Serialize the descriptor to bytes (64 bytes)
+    pub fn to_bytes(&self) -> [u8; COMPACT_DESCRIPTOR_SIZE] {
+        let mut buf = [0u8; COMPACT_DESCRIPTOR_SIZE];
+
+        buf[0..8].copy_from_slice(&self.len.to_le_bytes());
+        buf[8..12].copy_from_slice(&self.start_bits.to_le_bytes());
+        buf[12..16].copy_from_slice(&self.low_bits.to_le_bytes());
+        buf[16..24].copy_from_slice(&self.start_words.to_le_bytes());
+        buf[24..32].copy_from_slice(&self.low_words.to_le_bytes());
+        buf[32..40].copy_from_slice(&self.high_words.to_le_bytes());
+        buf[40..48].copy_from_slice(&self.sample_words.to_le_bytes());
+        // 48..64 reserved
+
+        buf
+    }
+
+    /// Deserialize the descriptor from bytes
+    pub fn from_bytes(buf: &[u8; COMPACT_DESCRIPTOR_SIZE]) -> Self {
+        let u32_at = |i: usize| u32::from_le_bytes([buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]);
+
+        Self {
+            len: read_word(buf, 0),
+            start_bits: u32_at(8),
+            low_bits: u32_at(12),
+            start_words: read_word(buf, 2),
+            low_words: read_word(buf, 3),
+            high_words: read_word(buf, 4),
+            sample_words: read_word(buf, 5),
+        }
+    }
+}
+
+/// Encoded compact table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompactTable {
+    descriptor: CompactTableDescriptor,
+    words: Vec<u64>,
+}
+
+impl CompactTable {
+    /// Encode a sorted table
+    ///
+    /// `end_hashes` are the full end hashes of `entries`, in ascending order
+    /// (see `sort_table_with_end_hashes`).
+    ///
+    /// # Panics
+    /// Panics if the lengths differ or the end hashes are not sorted.
+    pub fn encode(entries: &[ChainEntry], end_hashes: &[u64]) -> Self {
+        assert_eq!(
+            entries.len(),
+            end_hashes.len(),
+            "end hash count must match entry count"
+        );
+        assert!(
+            end_hashes.windows(2).all(|w| w[0] <= w[1]),
+            "end hashes must be sorted"
+        );
+
+        let len = entries.len() as u64;
+        let max_start = entries.iter().map(|e| e.start_seed).max().unwrap_or(0);
+        let start_bits = u32::BITS - max_start.leading_zeros();
+        let max_hash = end_hashes.last().copied().unwrap_or(0);
+        let low_bits = if len == 0 {
+            0
+        } else {
+            (max_hash / len).max(1).ilog2()
+        };
+
+        let start = pack_bits(entries.iter().map(|e| e.start_seed as u64), len, start_bits);
+        let low = pack_bits(end_hashes.iter().copied(), len, low_bits);
+
+        // Hash i sets bit (hash >> low_bits) + i of the high bit vector
+        let high_bits = (max_hash >> low_bits) + len + 1;
+        let mut high = vec![0u64; high_bits.div_ceil(64) as usize];
+        let mut samples = Vec::with_capacity(len.div_ceil(SELECT_SAMPLE as u64) as usize);
+        for (i, &hash) in end_hashes.iter().enumerate() {
+            let position = (hash >> low_bits) + i as u64;
+            high[(position / 64) as usize] |= 1 << (position % 64);
+            if i % SELECT_SAMPLE == 0 {
+                samples.push(position);
+            }
+        }
+
+        let descriptor = CompactTableDescriptor {
+            len,
+            start_bits,
+            low_bits,
+            start_words: start.len() as u64,
+            low_words: low.len() as u64,
+            high_words: high.len() as u64,
+            sample_words: samples.len() as u64,
+        };
+        let mut words = start;
+        words.extend(low);
+        words.extend(high);
+        words.extend(samples);
+
+        Self { descriptor, words }
+    }
+
+    /// Sizes of the parts
+    pub fn descriptor(&self) -> &CompactTableDescriptor {
+        &self.descriptor
+    }
+
+    /// All parts as little-endian bytes
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        self.words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+}
+
+/// Compact table read in place from little-endian bytes
+#[derive(Clone, Copy, Debug)]
+pub struct CompactTableRef<'a> {
+    descriptor: CompactTableDescriptor,
+    start: &'a [u8],
+    low: &'a [u8],
+    high: &'a [u8],
+    samples: &'a [u8],
+}
+
+impl<'a> CompactTableRef<'a> {
+    /// Read a table from its bytes (see `CompactTable::to_le_bytes`)
+    ///
+    /// Returns `None` if the descriptor is inconsistent, the byte count does
+    /// not match it, or the high bit vector and samples do not index one end
+    /// hash per chain. The high bit vector is scanned once for the check.
+    pub fn new(descriptor: CompactTableDescriptor, bytes: &'a [u8]) -> Option<Self> {
+        if !descriptor.is_consistent() || bytes.len() as u64 != descriptor.byte_size() {
+            return None;
+        }
+
+        let (start, rest) = bytes.split_at(descriptor.start_words as usize * 8);
+        let (low, rest) = rest.split_at(descriptor.low_words as usize * 8);
+        let (high, samples) = rest.split_at(descriptor.high_words as usize * 8);
+
+        let table = Self {
+            descriptor,
+            start,
+            low,
+            high,
+            samples,
+        };
+        table.is_indexed().then_some(table)
+    }
+
+    /// Check that the high bit vector has one set bit per chain and that the
+    /// samples hold the position of every `SELECT_SAMPLE`-th set bit
+    ///
+    /// `select_high` relies on this to stay within the high bit vector.
+    fn is_indexed(&self) -> bool {
+        let len = self.descriptor.len;
+        let mut count = 0u64;
+        for word_index in 0..self.descriptor.high_words as usize {
+            let mut word = read_word(self.high, word_index);
+            let ones = word.count_ones() as u64;
+
+            // A word holds at most one sampled bit
+            let sampled = count.next_multiple_of(SELECT_SAMPLE as u64);
+            if sampled < count + ones {
+                if sampled >= len {
+                    return false;
+                }
+                for _ in count..sampled {
+                    word &= word - 1;
+                }
+                let position = word_index as u64 * 64 + word.trailing_zeros() as u64;
+                if read_word(self.samples, (sampled / SELECT_SAMPLE as u64) as usize) != position {
+                    return false;
+                }
+            }
+
+            count += ones;
+            if count > len {
+                return false;
+            }
+        }
+        count == len
+    }
+
+    /// Number of chains
+    pub fn len(&self) -> usize {
+        self.descriptor.len as usize
+    }
+
+    /// Check if the table has no chains
+    pub fn is_empty(&self) -> bool {
+        self.descriptor.len == 0
+    }
+
+    /// Start seed of the chain at `index`
+    #[inline]
+    pub fn start_seed(&self, index: usize) -> u32 {
+        read_bits(self.start, index as u64, self.descriptor.start_bits) as u32
+    }
+
+    /// Full end hash of the chain at `index`
+    #[inline]
+    pub fn end_hash(&self, index: usize) -> u64 {
+        let low_bits = self.descriptor.low_bits;
+        let high = self.select_high(index) - index as u64;
+        (high << low_bits) | read_bits(self.low, index as u64, low_bits)
+    }
+
+    /// Position of the `index`-th set bit of the high bit vector
+    fn select_high(&self, index: usize) -> u64 {
+        let position = read_word(self.samples, index / SELECT_SAMPLE);
+        let mut remaining = (index % SELECT_SAMPLE) as u32;
+
+        let mut word_index = (position / 64) as usize;
+        let mut word = read_word(self.high, word_index) & (u64::MAX << (position % 64));
+        loop {
+            let ones = word.count_ones();
+            if remaining < ones {
+                for _ in 0..remaining {
+                    word &= word - 1;
+                }
+                return word_index as u64 * 64 + word.trailing_zeros() as u64;
+            }
+            remaining -= ones;
+            word_index += 1;
+            word = read_word(self.high, word_index);
+        }
+    }
+}
+
+/// Number of words needed for `count` values of `bits` bits
+///
+/// Returns `None` if the number of bits overflows.
+fn packed_words(count: u64, bits: u32) -> Option<u64> {
+    Some(count.checked_mul(bits as u64)?.div_ceil(64))
+}
+
+/// Bit-pack the low `bits` bits of each value
+fn pack_bits(values: impl Iterator<Item = u64>, count: u64, bits: u32) -> Vec<u64> {
+    let size = packed_words(count, bits).expect("packed values overflow u64 bits");
+    let mut words = vec![0u64; size as usize];
+    if bits == 0 {
+        return words;
+    }
+
+    let mask = u64::MAX >> (64 - bits);
+    for (i, value) in values.enumerate() {
+        let bit = i as u64 * bits as u64;
+        let (index, offset) = ((bit / 64) as usize, bit % 64);
+        let value = value & mask;
+        words[index] |= value << offset;
+        if offset + bits as u64 > 64 {
+            words[index + 1] |= value >> (64 - offset);
+        }
+    }
+    words
+}
+
+/// Read the `index`-th value of `bits` bits from bit-packed little-endian words
+#[inline]
+fn read_bits(bytes: &[u8], index: u64, bits: u32) -> u64 {
+    if bits == 0 {
+        return 0;
+    }
+
+    let bit = index * bits as u64;
+    let (word, offset) = ((bit / 64) as usize, bit % 64);
+    let mut value = read_word(bytes, word) >> offset;
+    if offset + bits as u64 > 64 {
+        value |= read_word(bytes, word + 1) << (64 - offset);
+    }
+    value & (u64::MAX >> (64 - bits))
+}
+
+/// Read the `index`-th little-endian u64 word
+#[inline]
+fn read_word(bytes: &[u8], index: usize) -> u64 {
+    let start = index * 8;
+    u64::from_le_bytes(bytes[start..start + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::table_sort::sort_table_with_end_hashes;
+
+    fn encode_and_view(entries: &[ChainEntry], end_hashes: &[u64]) -> (CompactTable, Vec<u8>) {
+        let table = CompactTable::encode(entries, end_hashes);
+        let bytes = table.to_le_bytes();
+        (table, bytes)
+    }
+
+    #[test]
+    fn test_pack_and_read_bits() {
+        for bits in [0, 1, 7, 20, 33, 63] {
+            let values: Vec<u64> = (0..100u64)
+                .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+                .collect();
+            let words = pack_bits(values.iter().copied(), 100, bits);
+            let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+            let mask = if bits == 0 {
+                0
+            } else {
+                u64::MAX >> (64 - bits)
+            };
+            for (i, &value) in values.iter().enumerate() {
+                assert_eq!(read_bits(&bytes, i as u64, bits), value & mask);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact_table_roundtrip() {
+        let mut entries: Vec<ChainEntry> = (0..1000u32)
+            .map(|i| ChainEntry::new(i, i.wrapping_mul(2_654_435_761)))
+            .collect();
+        let end_hashes = sort_table_with_end_hashes(&mut entries, 417);
+
+        let (table, bytes) = encode_and_view(&entries, &end_hashes);
+        let view = CompactTableRef::new(*table.descriptor(), &bytes).unwrap();
+
+        assert_eq!(view.len(), 1000);
+        assert_eq!(table.descriptor().start_bits, 10);
+        for (i, entry) in entries.iter().enumerate() {
+            assert_eq!(view.start_seed(i), entry.start_seed);
+            assert_eq!(view.end_hash(i), end_hashes[i]);
+        }
+    }
+
+    #[test]
+    fn test_compact_table_duplicate_and_extreme_hashes() {
+        let entries: Vec<ChainEntry> = (0..600).map(|i| ChainEntry::new(i * 7, 0)).collect();
+        let end_hashes: Vec<u64> = (0..600u64)
+            .map(|i| match i {
+                0..=299 => 5,
+                _ => (1 << 33) - 600 + i,
+            })
+            .collect();
+
+        let (table, bytes) = encode_and_view(&entries, &end_hashes);
+        let view = CompactTableRef::new(*table.descriptor(), &bytes).unwrap();
+
+        for (i, &hash) in end_hashes.iter().enumerate() {
+            assert_eq!(view.end_hash(i), hash);
+            assert_eq!(view.start_seed(i), i as u32 * 7);
+        }
+    }
+
+    #[test]
+    fn test_compact_table_empty() {
+        let (table, bytes) = encode_and_view(&[], &[]);
+        let view = CompactTableRef::new(*table.descriptor(), &bytes).unwrap();
+        assert!(view.is_empty());
+    }
+
+    #[test]
+    fn test_compact_table_smaller_than_entries() {
+        let mut entries: Vec<ChainEntry> = (0..4096u32)
+            .map(|i| ChainEntry::new(i, i.wrapping_mul(2_654_435_761)))
+            .collect();
+        let end_hashes = sort_table_with_end_hashes(&mut entries, 417);
+
+        let table = CompactTable::encode(&entries, &end_hashes);
+        // 12-bit start seeds, about 20 low bits and 2 high bits per end hash
+        assert!(table.descriptor().byte_size() < 4096 * 8 * 6 / 10);
+    }
+
+    #[test]
+    fn test_descriptor_serialization() {
+        let mut entries: Vec<ChainEntry> = (0..300).map(|i| ChainEntry::new(i, i)).collect();
+        let end_hashes = sort_table_with_end_hashes(&mut entries, 417);
+        let descriptor = *CompactTable::encode(&entries, &end_hashes).descriptor();
+
+        let restored = CompactTableDescriptor::from_bytes(&descriptor.to_bytes());
+        assert_eq!(restored, descriptor);
+        assert!(restored.is_consistent());
+    }
+
+    #[test]
+    fn test_view_rejects_inconsistent_sizes() {
+        let entries: Vec<ChainEntry> = (0..10).map(|i| ChainEntry::new(i, i)).collect();
+        let end_hashes: Vec<u64> = (0..10).collect();
+        let (table, bytes) = encode_and_view(&entries, &end_hashes);
+
+        assert!(CompactTableRef::new(*table.descriptor(), &bytes[8..]).is_none());
+        let mut descriptor = *table.descriptor();
+        descriptor.start_bits = 40;
+        assert!(CompactTableRef::new(descriptor, &bytes).is_none());
+    }
+
+    #[test]
+    fn test_descriptor_overflow_is_inconsistent() {
+        let descriptor = CompactTableDescriptor {
+            len: u64::MAX / 4,
+            start_bits: 32,
+            low_bits: 32,
+            start_words: u64::MAX / 2,
+            low_words: u64::MAX / 2,
+            high_words: u64::MAX / 16,
+            sample_words: (u64::MAX / 4).div_ceil(SELECT_SAMPLE as u64),
+        };
+        assert!(!descriptor.is_consistent());
+        assert_eq!(descriptor.byte_size(), u64::MAX);
+        assert!(CompactTableRef::new(descriptor, &[]).is_none());
+
+        let descriptor = CompactTableDescriptor {
+            high_words: u64::MAX,
+            ..CompactTableDescriptor::default()
+        };
+        assert!(!descriptor.is_consistent());
+    }
+
+    #[test]
+    fn test_view_rejects_corrupted_index() {
+        let mut entries: Vec<ChainEntry> = (0..600).map(|i| ChainEntry::new(i, i)).collect();
+        let end_hashes = sort_table_with_end_hashes(&mut entries, 417);
+        let (table, bytes) = encode_and_view(&entries, &end_hashes);
+        let descriptor = *table.descriptor();
+        let high_start = ((descriptor.start_words + descriptor.low_words) * 8) as usize;
+        let samples_start = high_start + descriptor.high_words as usize * 8;
+
+        // A sample pointing past the high bit vector
+        let mut corrupted = bytes.clone();
+        corrupted[samples_start + 8..samples_start + 16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(CompactTableRef::new(descriptor, &corrupted).is_none());
+
+        // A high bit vector with a bit missing or added
+        for bit in [0, 7] {
+            let mut corrupted = bytes.clone();
+            corrupted[high_start] ^= 1 << bit;
+            assert!(CompactTableRef::new(descriptor, &corrupted).is_none());
+        }
+
+        // An empty high bit vector
+        let mut corrupted = bytes.clone();
+        corrupted[high_start..samples_start].fill(0);
+        assert!(CompactTableRef::new(descriptor, &corrupted).is_none());
+    }
+}
@@ -51,6 +51,24 @@ impl SeedBitmap {
         (self.bits[index].load(Ordering::Relaxed) & bit) != 0
     }
 
+    /// Find the lowest missing seed at or after `from`
+    ///
+    /// Returns `None` if every seed from `from` to `u32::MAX` is reachable.
+    pub fn next_missing(&self, from: u32) -> Option<u32> {
+        let first = (from as usize) / 64;
+        // Treat the bits below `from` in its block as set
+        let mut below = (1u64 << (from % 64)) - 1;
+
+        for (i, atomic) in self.bits.iter().enumerate().skip(first) {
+            let missing = !(atomic.load(Ordering::Relaxed) | below);
+            if missing != 0 {
+                return Some((i * 64) as u32 + missing.trailing_zeros());
+            }
+            below = 0;
+        }
+        None
+    }
+
     /// Extract all missing seeds (seeds with bit = 0)
     ///
     /// Returns a vector of all seeds that are not reachable from the table.
@@ -140,6 +158,24 @@ mod tests {
         assert!(bitmap.is_set(u32::MAX));
     }
 
+    #[test]
+    #[serial]
+    fn test_bitmap_next_missing() {
+        let bitmap = SeedBitmap::new();
+        assert_eq!(bitmap.next_missing(0), Some(0));
+
+        for seed in 0..130 {
+            bitmap.set(seed);
+        }
+        bitmap.set(131);
+        assert_eq!(bitmap.next_missing(0), Some(130));
+        assert_eq!(bitmap.next_missing(131), Some(132));
+        assert_eq!(bitmap.next_missing(u32::MAX), Some(u32::MAX));
+
+        bitmap.set(u32::MAX);
+        assert_eq!(bitmap.next_missing(u32::MAX), None);
+    }
+
     #[test]
     #[serial]
     fn test_bitmap_set_batch() {
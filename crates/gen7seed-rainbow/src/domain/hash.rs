@@ -152,10 +152,26 @@ const MULTI_TABLE_SALTS: [u64; 16] = {
 #[cfg(feature = "multi-sfmt")]
 #[inline]
 pub fn reduce_hash_x16_multi_table(hashes: [u64; 16], column: u32) -> [u32; 16] {
+    reduce_hash_x16_with_salts(hashes, column, &MULTI_TABLE_SALTS)
+}
+
+/// Compute the salts of 16 arbitrary tables for `reduce_hash_x16_with_salts`
+#[cfg(feature = "multi-sfmt")]
+pub fn table_salts_x16(table_ids: [u32; 16]) -> [u64; 16] {
+    table_ids.map(|table_id| (table_id as u64).wrapping_mul(0x9e3779b97f4a7c15))
+}
+
+/// Reduce 16 hashes for 16 arbitrary tables simultaneously
+///
+/// Generalization of `reduce_hash_x16_multi_table` to any table IDs, with
+/// salts precomputed by `table_salts_x16`.
+#[cfg(feature = "multi-sfmt")]
+#[inline]
+pub fn reduce_hash_x16_with_salts(hashes: [u64; 16], column: u32, salts: &[u64; 16]) -> [u32; 16] {
     use std::simd::Simd;
 
     let h = Simd::from_array(hashes);
-    let salts = Simd::from_array(MULTI_TABLE_SALTS);
+    let salts = Simd::from_array(*salts);
     let col = Simd::splat(column as u64);
     let c1 = Simd::splat(0xbf58476d1ce4e5b9u64);
     let c2 = Simd::splat(0x94d049bb133111ebu64);
@@ -606,6 +622,24 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "multi-sfmt")]
+    #[test]
+    fn test_reduce_hash_x16_with_salts_matches_individual() {
+        let hashes: [u64; 16] = std::array::from_fn(|i| 0x123456789u64 * (i as u64 + 1));
+        let table_ids: [u32; 16] = std::array::from_fn(|i| 16 + 3 * i as u32);
+        let salts = table_salts_x16(table_ids);
+
+        for column in [0, 7, 4095] {
+            let results = reduce_hash_x16_with_salts(hashes, column, &salts);
+            for lane in 0..16 {
+                assert_eq!(
+                    results[lane],
+                    reduce_hash_with_salt(hashes[lane], column, table_ids[lane])
+                );
+            }
+        }
+    }
+
     #[cfg(feature = "multi-sfmt")]
     #[test]
     fn test_reduce_hash_x16_multi_table_deterministic() {
@@ -0,0 +1,395 @@
+//! Blocked missing seeds encoding
+//!
+//! Compressed data section of `.g7ms` files (`MISSING_COMPRESSED_FORMAT_VERSION`).
+//! The seed space is split into blocks of 2^16 seeds, and each block is
+//! stored either as a list of varint-coded deltas (sparse blocks) or as a
+//! bitmap (dense blocks), whichever is smaller.
+//!
+//! Layout (little-endian):
+//! - Directory: `MISSING_BLOCK_COUNT + 1` u32 offsets of each block in the
+//!   block data; the last offset is the length of the block data
+//! - Block data: the encoded blocks in seed order
+//!
+//! A block of `MISSING_BITMAP_BLOCK_SIZE` bytes is a bitmap (bit `i % 8` of
+//! byte `i / 8` is set if the `i`-th seed of the block is missing). A shorter
+//! block lists the lower 16 bits of its seeds in ascending order as LEB128
+//! varints: the first value, then the difference to the previous one.
+
+use std::ops::{Bound, RangeBounds};
+
+/// Number of seed bits selecting the seed within a block
+pub const MISSING_BLOCK_BITS: u32 = 16;
+
+/// Number of blocks covering the seed space
+pub const MISSING_BLOCK_COUNT: usize = 1 << (32 - MISSING_BLOCK_BITS);
+
+/// Size of a bitmap block in bytes
+pub const MISSING_BITMAP_BLOCK_SIZE: usize = (1 << MISSING_BLOCK_BITS) / 8;
+
+/// Size of the block directory in bytes
+pub const MISSING_DIRECTORY_SIZE: usize = (MISSING_BLOCK_COUNT + 1) * 4;
+
+/// Encode ascending, distinct seeds into the blocked format
+///
+/// # Panics
+/// Panics if the seeds are not strictly ascending.
+pub fn encode_missing_blocks(seeds: &[u32]) -> Vec<u8> {
+    assert!(
+        seeds.windows(2).all(|w| w[0] < w[1]),
+        "missing seeds must be strictly ascending"
+    );
+
+    let mut directory = Vec::with_capacity(MISSING_DIRECTORY_SIZE);
+    let mut data = Vec::new();
+    let mut rest = seeds;
+
+    for block in 0..MISSING_BLOCK_COUNT {
+        directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+
+        let count = rest
+            .iter()
+            .take_while(|&&seed| (seed >> MISSING_BLOCK_BITS) as usize == block)
+            .count();
+        let (block_seeds, next) = rest.split_at(count);
+        rest = next;
+        encode_block(block_seeds, &mut data);
+    }
+    directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+
+    directory.extend_from_slice(&data);
+    directory
+}
+
+/// Append one block, choosing the smaller of the varint and bitmap encodings
+fn encode_block(seeds: &[u32], data: &mut Vec<u8>) {
+    let start = data.len();
+    let mut prev = 0u32;
+    for &seed in seeds {
+        let low = seed & 0xffff;
+        write_varint(low - prev, data);
+        prev = low;
+
+        if data.len() - start >= MISSING_BITMAP_BLOCK_SIZE {
+            break;
+        }
+    }
+
+    if data.len() - start >= MISSING_BITMAP_BLOCK_SIZE {
+        data.truncate(start);
+        data.resize(start + MISSING_BITMAP_BLOCK_SIZE, 0);
+        for &seed in seeds {
+            let low = (seed & 0xffff) as usize;
+            data[start + low / 8] |= 1 << (low % 8);
+        }
+    }
+}
+
+fn write_varint(mut value: u32, data: &mut Vec<u8>) {
+    while value >= 0x80 {
+        data.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    data.push(value as u8);
+}
+
+/// Zero-copy view of blocked missing seeds
+#[derive(Clone, Copy, Debug)]
+pub struct MissingBlocksRef<'a> {
+    directory: &'a [u8],
+    data: &'a [u8],
+}
+
+impl<'a> MissingBlocksRef<'a> {
+    /// Create a view of the encoded bytes
+    ///
+    /// Returns `None` if the directory is truncated, its offsets are not
+    /// ascending, a block is larger than a bitmap, or the length of the
+    /// block data does not match the directory.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < MISSING_DIRECTORY_SIZE {
+            return None;
+        }
+        let (directory, data) = bytes.split_at(MISSING_DIRECTORY_SIZE);
+        let view = Self { directory, data };
+
+        let mut prev = 0;
+        for index in 1..=MISSING_BLOCK_COUNT {
+            let offset = view.offset(index);
+            if offset < prev || offset - prev > MISSING_BITMAP_BLOCK_SIZE {
+                return None;
+            }
+            prev = offset;
+        }
+        (view.offset(0) == 0 && prev == data.len()).then_some(view)
+    }
+
+    /// Total size of the encoded bytes
+    pub fn byte_size(&self) -> usize {
+        self.directory.len() + self.data.len()
+    }
+
+    #[inline]
+    fn offset(&self, index: usize) -> usize {
+        let bytes = &self.directory[index * 4..index * 4 + 4];
+        u32::from_le_bytes(bytes.try_into().unwrap()) as usize
+    }
+
+    #[inline]
+    fn block(&self, index: usize) -> &'a [u8] {
+        &self.data[self.offset(index)..self.offset(index + 1)]
+    }
+
+    /// Check if `seed` is listed
+    pub fn contains(&self, seed: u32) -> bool {
+        let block = self.block((seed >> MISSING_BLOCK_BITS) as usize);
+        let low = seed & 0xffff;
+        if block.len() == MISSING_BITMAP_BLOCK_SIZE {
+            return block[low as usize / 8] & (1 << (low % 8)) != 0;
+        }
+        self.block_seeds(seed >> MISSING_BLOCK_BITS)
+            .take_while(|&s| s <= seed)
+            .any(|s| s == seed)
+    }
+
+    /// Iterate over the seeds of the block with the given index in ascending order
+    ///
+    /// # Panics
+    /// Panics if `block` is not below `MISSING_BLOCK_COUNT`.
+    pub fn block_seeds(&self, block: u32) -> BlockSeeds<'a> {
+        let bytes = self.block(block as usize);
+        let base = block << MISSING_BLOCK_BITS;
+        if bytes.len() == MISSING_BITMAP_BLOCK_SIZE {
+            BlockSeeds::Bitmap {
+                bytes,
+                base,
+                next: 0,
+            }
+        } else {
+            BlockSeeds::Varint {
+                bytes,
+                base,
+                pos: 0,
+                prev: 0,
+            }
+        }
+    }
+
+    /// Iterate over all seeds in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = u32> + 'a {
+        let view = *self;
+        (0..MISSING_BLOCK_COUNT as u32).flat_map(move |block| view.block_seeds(block))
+    }
+
+    /// Iterate over the seeds within `range` in ascending order
+    pub fn range(&self, range: impl RangeBounds<u32>) -> impl Iterator<Item = u32> + 'a {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s as u64,
+            Bound::Excluded(&s) => s as u64 + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e as u64 + 1,
+            Bound::Excluded(&e) => e as u64,
+            Bound::Unbounded => 1 << 32,
+        };
+
+        let view = *self;
+        let blocks = if start < end {
+            (start >> MISSING_BLOCK_BITS) as u32..((end - 1) >> MISSING_BLOCK_BITS) as u32 + 1
+        } else {
+            0..0
+        };
+        blocks
+            .flat_map(move |block| view.block_seeds(block))
+            .skip_while(move |&seed| (seed as u64) < start)
+            .take_while(move |&seed| (seed as u64) < end)
+    }
+
+    /// Count the seeds (decodes every block)
+    pub fn count(&self) -> u64 {
+        (0..MISSING_BLOCK_COUNT as u32)
+            .map(|block| match self.block_seeds(block) {
+                BlockSeeds::Bitmap { bytes, .. } => bytes
+                    .iter()
+                    .map(|byte| byte.count_ones() as u64)
+                    .sum::<u64>(),
+                seeds => seeds.count() as u64,
+            })
+            .sum()
+    }
+}
+
+/// Seeds of one block, see `MissingBlocksRef::block_seeds`
+#[derive(Clone, Debug)]
+pub enum BlockSeeds<'a> {
+    /// Bitmap block
+    Bitmap {
+        bytes: &'a [u8],
+        base: u32,
+        next: usize,
+    },
+    /// Varint-coded deltas
+    Varint {
+        bytes: &'a [u8],
+        base: u32,
+        pos: usize,
+        prev: u32,
+    },
+}
+
+impl Iterator for BlockSeeds<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        match self {
+            Self::Bitmap { bytes, base, next } => {
+                while *next < bytes.len() * 8 {
+                    let byte = bytes[*next / 8] >> (*next % 8);
+                    if byte == 0 {
+                        *next = (*next / 8 + 1) * 8;
+                        continue;
+                    }
+                    let low = *next + byte.trailing_zeros() as usize;
+                    *next = low + 1;
+                    return Some(*base | low as u32);
+                }
+                None
+            }
+            Self::Varint {
+                bytes,
+                base,
+                pos,
+                prev,
+            } => {
+                if *pos >= bytes.len() {
+                    return None;
+                }
+                let mut value = 0u32;
+                let mut shift = 0;
+                loop {
+                    let byte = *bytes.get(*pos)?;
+                    *pos += 1;
+                    value |= ((byte & 0x7f) as u32) << shift;
+                    if byte & 0x80 == 0 {
+                        break;
+                    }
+                    shift += 7;
+                    if shift > 14 {
+                        // Deltas are below 2^16; stop at malformed data
+                        *pos = bytes.len();
+                        return None;
+                    }
+                }
+                let low = *prev + value;
+                *prev = low;
+                Some(*base | (low & 0xffff))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_seeds() -> Vec<u32> {
+        let mut seeds = vec![0, 1, 127, 128, 300, 65535, 65536, 0x0123_4567, u32::MAX];
+        // A dense block stored as a bitmap
+        seeds.extend((0..20_000).map(|i| (7 << MISSING_BLOCK_BITS) | (i * 3)));
+        seeds.sort_unstable();
+        seeds.dedup();
+        seeds
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let seeds = sample_seeds();
+        let bytes = encode_missing_blocks(&seeds);
+        let view = MissingBlocksRef::new(&bytes).unwrap();
+
+        assert_eq!(view.byte_size(), bytes.len());
+        assert_eq!(view.iter().collect::<Vec<_>>(), seeds);
+        assert_eq!(view.count(), seeds.len() as u64);
+    }
+
+    #[test]
+    fn test_chooses_encoding_by_density() {
+        let seeds = sample_seeds();
+        let bytes = encode_missing_blocks(&seeds);
+        let view = MissingBlocksRef::new(&bytes).unwrap();
+
+        assert!(matches!(view.block_seeds(7), BlockSeeds::Bitmap { .. }));
+        assert!(matches!(view.block_seeds(0), BlockSeeds::Varint { .. }));
+        // Empty blocks take no space
+        assert_eq!(view.block(2).len(), 0);
+    }
+
+    #[test]
+    fn test_contains() {
+        let seeds = sample_seeds();
+        let bytes = encode_missing_blocks(&seeds);
+        let view = MissingBlocksRef::new(&bytes).unwrap();
+
+        for &seed in &seeds {
+            assert!(view.contains(seed), "seed {seed} should be listed");
+        }
+        for seed in [
+            2,
+            129,
+            65534,
+            65537,
+            (7 << MISSING_BLOCK_BITS) | 1,
+            u32::MAX - 1,
+        ] {
+            assert!(!view.contains(seed), "seed {seed} should not be listed");
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let seeds = sample_seeds();
+        let bytes = encode_missing_blocks(&seeds);
+        let view = MissingBlocksRef::new(&bytes).unwrap();
+
+        let range = 128..=65536;
+        let expected: Vec<u32> = seeds
+            .iter()
+            .copied()
+            .filter(|s| range.contains(s))
+            .collect();
+        assert_eq!(view.range(range).collect::<Vec<_>>(), expected);
+
+        assert_eq!(view.range(u32::MAX..).collect::<Vec<_>>(), vec![u32::MAX]);
+        assert_eq!(view.range(5..5).count(), 0);
+    }
+
+    #[test]
+    fn test_smaller_than_raw_list() {
+        // Sparse seeds spread over the seed space, as left by a large table
+        let seeds: Vec<u32> = (0..1u32 << 20).map(|i| i << 12 | (i % 4001)).collect();
+        let bytes = encode_missing_blocks(&seeds);
+        assert!(bytes.len() < seeds.len() * 4 * 2 / 3);
+    }
+
+    #[test]
+    fn test_empty() {
+        let bytes = encode_missing_blocks(&[]);
+        assert_eq!(bytes.len(), MISSING_DIRECTORY_SIZE);
+        let view = MissingBlocksRef::new(&bytes).unwrap();
+        assert_eq!(view.iter().count(), 0);
+        assert!(!view.contains(0));
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        let bytes = encode_missing_blocks(&sample_seeds());
+        assert!(MissingBlocksRef::new(&bytes[..bytes.len() - 1]).is_none());
+        assert!(MissingBlocksRef::new(&bytes[..MISSING_DIRECTORY_SIZE - 4]).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unsorted_seeds() {
+        encode_missing_blocks(&[5, 3]);
+    }
+}
@@ -3,9 +3,10 @@
 //! This module defines the file format for missing seeds,
 //! including header structure and validation against source table.
 
-use crate::constants::{FILE_FORMAT_VERSION, FILE_HEADER_SIZE, MISSING_MAGIC};
-use crate::domain::table_format::TableHeader;
-use std::time::{SystemTime, UNIX_EPOCH};
+use crate::constants::{
+    FILE_FORMAT_VERSION, FILE_HEADER_SIZE, MISSING_COMPRESSED_FORMAT_VERSION, MISSING_MAGIC,
+};
+use crate::domain::table_format::{TableHeader, creation_timestamp};
 
 const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
 const FNV_PRIME: u64 = 0x100000001b3;
@@ -29,15 +30,14 @@ pub struct MissingSeedsHeader {
     pub source_checksum: u64,
     /// Creation timestamp (Unix epoch seconds)
     pub created_at: u64,
+    /// Checksum of the seeds after the header (None = not recorded)
+    pub checksum: Option<u64>,
 }
 
 impl MissingSeedsHeader {
     /// Create a new header from source table header
     pub fn new(source: &TableHeader, missing_count: u64) -> Self {
-        let created_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
+        let created_at = creation_timestamp();
 
         Self {
             version: FILE_FORMAT_VERSION,
@@ -48,9 +48,24 @@ impl MissingSeedsHeader {
             missing_count,
             source_checksum: calculate_source_checksum(source),
             created_at,
+            checksum: None,
         }
     }
 
+    /// Check if the seeds are stored in the compressed format (see `domain::missing_blocks`)
+    pub fn is_compressed(&self) -> bool {
+        self.version == MISSING_COMPRESSED_FORMAT_VERSION
+    }
+
+    /// Select the compressed format (or the raw u32 list)
+    pub fn set_compressed(&mut self, compressed: bool) {
+        self.version = if compressed {
+            MISSING_COMPRESSED_FORMAT_VERSION
+        } else {
+            FILE_FORMAT_VERSION
+        };
+    }
+
     /// Serialize header to bytes (64 bytes)
     pub fn to_bytes(&self) -> [u8; FILE_HEADER_SIZE] {
         let mut buf = [0u8; FILE_HEADER_SIZE];
@@ -66,7 +81,7 @@ impl MissingSeedsHeader {
         buf[32..40].copy_from_slice(&self.missing_count.to_le_bytes());
         buf[40..48].copy_from_slice(&self.source_checksum.to_le_bytes());
         buf[48..56].copy_from_slice(&self.created_at.to_le_bytes());
-        // 56..64 reserved
+        buf[56..64].copy_from_slice(&self.checksum.unwrap_or(0).to_le_bytes());
 
         buf
     }
@@ -78,7 +93,7 @@ impl MissingSeedsHeader {
         }
 
         let version = u16::from_le_bytes([buf[8], buf[9]]);
-        if version != FILE_FORMAT_VERSION {
+        if version != FILE_FORMAT_VERSION && version != MISSING_COMPRESSED_FORMAT_VERSION {
             return Err(MissingFormatError::UnsupportedVersion(version));
         }
 
@@ -97,6 +112,10 @@ impl MissingSeedsHeader {
             created_at: u64::from_le_bytes([
                 buf[48], buf[49], buf[50], buf[51], buf[52], buf[53], buf[54], buf[55],
             ]),
+            checksum: Some(u64::from_le_bytes([
+                buf[56], buf[57], buf[58], buf[59], buf[60], buf[61], buf[62], buf[63],
+            ]))
+            .filter(|&checksum| checksum != 0),
         })
     }
 
@@ -113,7 +132,16 @@ impl MissingSeedsHeader {
     }
 }
 
-/// Calculate source checksum from table header (FNV-1a based)
+/// Calculate the checksum binding a missing seeds file to its source table (FNV-1a based)
+///
+/// The table parameters and the checksum of the chain entries recorded in
+/// the header are hashed, so the binding follows the chains: tables with the
+/// same chains share missing seeds files regardless of when they were
+/// created or whether they store end hashes. Tables without a chain checksum
+/// (compact tables) fall back to the data checksum, and older tables without
+/// any checksum to their `created_at`. Pass the header returned by
+/// `SingleTableView::bound_header` to bind to the checksum of the data
+/// rather than the value recorded in the header.
 pub fn calculate_source_checksum(header: &TableHeader) -> u64 {
     let mut h: u64 = FNV_OFFSET_BASIS;
 
@@ -125,8 +153,16 @@ pub fn calculate_source_checksum(header: &TableHeader) -> u64 {
     h = h.wrapping_mul(FNV_PRIME);
     h ^= header.num_tables as u64;
     h = h.wrapping_mul(FNV_PRIME);
-    h ^= header.created_at;
-    h = h.wrapping_mul(FNV_PRIME);
+    match header.chains_checksum.or(header.checksum) {
+        Some(checksum) => {
+            h ^= checksum;
+            h = h.wrapping_mul(FNV_PRIME);
+        }
+        None => {
+            h ^= header.created_at;
+            h = h.wrapping_mul(FNV_PRIME);
+        }
+    }
 
     h
 }
@@ -144,6 +180,10 @@ pub enum MissingFormatError {
     SourceMismatch { expected: u64, found: u64 },
     /// File size does not match expected size
     InvalidFileSize { expected: u64, found: u64 },
+    /// Seeds do not match the checksum recorded in the header
+    ChecksumMismatch { expected: u64, found: u64 },
+    /// Compressed seeds are malformed or do not match the header
+    InvalidBlocks,
     /// I/O error
     Io(String),
 }
@@ -170,6 +210,12 @@ impl std::fmt::Display for MissingFormatError {
                 "Invalid file size: expected {} bytes, found {} bytes",
                 expected, found
             ),
+            Self::ChecksumMismatch { expected, found } => write!(
+                f,
+                "Checksum mismatch: expected {:016x}, found {:016x}",
+                expected, found
+            ),
+            Self::InvalidBlocks => write!(f, "Invalid compressed missing seeds data"),
             Self::Io(msg) => write!(f, "I/O error: {}", msg),
         }
     }
@@ -184,6 +230,9 @@ impl From<std::io::Error> for MissingFormatError {
 }
 
 /// Calculate expected file size from header
+///
+/// Only applies to uncompressed files; compressed files are sized by their
+/// block directory (see `MissingBlocksRef::new`).
 pub fn expected_missing_file_size(header: &MissingSeedsHeader) -> u64 {
     FILE_HEADER_SIZE as u64 + header.missing_count * 4
 }
@@ -3,8 +3,15 @@
 //! This module contains pure functions and algorithms without I/O dependencies.
 
 pub mod chain;
+pub mod checkpoint_format;
+pub mod checksum;
+pub mod compact;
 pub mod coverage;
 pub mod hash;
+pub mod missing_blocks;
 pub mod missing_format;
+pub mod needle;
+pub mod original_format;
+pub mod section_format;
 pub mod sfmt;
 pub mod table_format;
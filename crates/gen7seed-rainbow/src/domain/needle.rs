@@ -0,0 +1,381 @@
+//! Needle reading correction
+//!
+//! This module enumerates needle vectors near an observed reading, for
+//! searching when some clock hands may have been misread.
+//!
+//! Needle positions are arranged on a dial, so a correction wraps around
+//! (16 and 0 are adjacent).
+
+use crate::constants::{NEEDLE_COUNT, NEEDLE_STATES};
+use crate::domain::sfmt::Sfmt;
+
+/// Largest circular distance between two needle states (17 states → 8)
+pub const MAX_NEEDLE_DELTA: u64 = NEEDLE_STATES / 2;
+
+/// Largest number of candidate readings a tolerance should expand to
+///
+/// Every candidate is searched like a reading of its own. Two unknown needles
+/// (7,297 candidates) are within the limit; larger tolerances yield no
+/// candidates (see `NeedleTolerance::candidate_count`).
+pub const MAX_NEEDLE_CANDIDATES: u64 = 10_000;
+
+/// Allowed deviation from the observed needle reading
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NeedleTolerance {
+    /// Maximum number of needles that may be corrected (default: 1)
+    pub max_corrections: usize,
+    /// Maximum circular distance per corrected needle (default: 1)
+    ///
+    /// `MAX_NEEDLE_DELTA` or more means the corrected needle may take any value.
+    pub max_delta: u64,
+}
+
+impl Default for NeedleTolerance {
+    fn default() -> Self {
+        Self::off_by_one()
+    }
+}
+
+impl NeedleTolerance {
+    /// One needle may be off by ±1
+    pub fn off_by_one() -> Self {
+        Self {
+            max_corrections: 1,
+            max_delta: 1,
+        }
+    }
+
+    /// One needle may be unknown (any value)
+    pub fn one_unknown() -> Self {
+        Self {
+            max_corrections: 1,
+            max_delta: MAX_NEEDLE_DELTA,
+        }
+    }
+
+    /// Set the maximum number of corrected needles
+    pub fn with_max_corrections(mut self, max_corrections: usize) -> Self {
+        self.max_corrections = max_corrections;
+        self
+    }
+
+    /// Set the maximum circular distance per corrected needle
+    pub fn with_max_delta(mut self, max_delta: u64) -> Self {
+        self.max_delta = max_delta;
+        self
+    }
+
+    /// Number of candidates `enumerate_needle_candidates` yields, including the reading
+    pub fn candidate_count(&self) -> u64 {
+        let alternatives = 2 * self.max_delta.min(MAX_NEEDLE_DELTA);
+        let max_corrections = self.max_corrections.min(NEEDLE_COUNT) as u64;
+
+        // Sum of C(NEEDLE_COUNT, k) × alternatives^k
+        let mut combinations = 1u64;
+        let mut count = 1u64;
+        for k in 1..=max_corrections {
+            combinations = combinations * (NEEDLE_COUNT as u64 - k + 1) / k;
+            count += combinations * alternatives.pow(k as u32);
+        }
+        count
+    }
+
+    /// Check if the tolerance expands to no more than `MAX_NEEDLE_CANDIDATES`
+    pub fn is_within_limit(&self) -> bool {
+        self.candidate_count() <= MAX_NEEDLE_CANDIDATES
+    }
+}
+
+/// A single corrected needle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NeedleCorrection {
+    /// Needle position (0-based)
+    pub position: usize,
+    /// Observed value
+    pub observed: u64,
+    /// Corrected value
+    pub corrected: u64,
+}
+
+impl NeedleCorrection {
+    /// Circular distance between the observed and corrected values
+    pub fn delta(&self) -> u64 {
+        needle_distance(self.observed, self.corrected)
+    }
+}
+
+/// A needle vector derived from the observed reading
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NeedleCandidate {
+    /// Corrected needle values
+    pub needle_values: [u64; NEEDLE_COUNT],
+    /// Corrections applied to the observed reading (empty for the reading itself)
+    pub corrections: Vec<NeedleCorrection>,
+}
+
+impl NeedleCandidate {
+    /// Sum of the circular distances of all corrections
+    pub fn total_delta(&self) -> u64 {
+        self.corrections.iter().map(NeedleCorrection::delta).sum()
+    }
+}
+
+/// Generate needle values from a seed
+///
+/// Skips `consumption` random numbers and returns the next `count` values mod 17.
+/// The first `NEEDLE_COUNT` values are the ones used for the table hash; any
+/// further values are the needles read after them.
+pub fn gen_needle_values(seed: u32, consumption: i32, count: usize) -> Vec<u64> {
+    let mut sfmt = Sfmt::new(seed);
+    sfmt.skip(consumption as usize);
+    (0..count)
+        .map(|_| sfmt.gen_rand_u64() % NEEDLE_STATES)
+        .collect()
+}
+
+/// Circular distance between two needle values
+pub fn needle_distance(a: u64, b: u64) -> u64 {
+    let d = (a % NEEDLE_STATES).abs_diff(b % NEEDLE_STATES);
+    d.min(NEEDLE_STATES - d)
+}
+
+/// Enumerate all needle vectors within the tolerance
+///
+/// The observed reading comes first, followed by candidates with one correction,
+/// two corrections, and so on. Within the same number of corrections, positions
+/// are in ascending order and smaller deltas come first.
+///
+/// Returns no candidates if the tolerance expands to more than
+/// `MAX_NEEDLE_CANDIDATES` (see `NeedleTolerance::is_within_limit`).
+pub fn enumerate_needle_candidates(
+    needle_values: [u64; NEEDLE_COUNT],
+    tolerance: &NeedleTolerance,
+) -> Vec<NeedleCandidate> {
+    if !tolerance.is_within_limit() {
+        return Vec::new();
+    }
+
+    let observed: [u64; NEEDLE_COUNT] = std::array::from_fn(|i| needle_values[i] % NEEDLE_STATES);
+    let max_delta = tolerance.max_delta.min(MAX_NEEDLE_DELTA);
+    let max_corrections = tolerance.max_corrections.min(NEEDLE_COUNT);
+
+    let mut candidates = vec![NeedleCandidate {
+        needle_values: observed,
+        corrections: Vec::new(),
+    }];
+    if max_delta == 0 {
+        return candidates;
+    }
+
+    // Alternatives for each needle: +1, -1, +2, -2, ... (distinct since NEEDLE_STATES is odd)
+    let alternatives: Vec<Vec<u64>> = observed
+        .iter()
+        .map(|&v| {
+            (1..=max_delta)
+                .flat_map(|d| {
+                    [
+                        (v + d) % NEEDLE_STATES,
+                        (v + NEEDLE_STATES - d) % NEEDLE_STATES,
+                    ]
+                })
+                .collect()
+        })
+        .collect();
+
+    for count in 1..=max_corrections {
+        let mut positions: Vec<usize> = (0..count).collect();
+        loop {
+            push_corrections(&observed, &alternatives, &positions, &mut candidates);
+            if !next_combination(&mut positions, NEEDLE_COUNT) {
+                break;
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Push every candidate that corrects exactly the given positions
+fn push_corrections(
+    observed: &[u64; NEEDLE_COUNT],
+    alternatives: &[Vec<u64>],
+    positions: &[usize],
+    candidates: &mut Vec<NeedleCandidate>,
+) {
+    let mut choice = vec![0usize; positions.len()];
+    loop {
+        let mut needle_values = *observed;
+        let corrections = positions
+            .iter()
+            .zip(&choice)
+            .map(|(&position, &c)| {
+                let corrected = alternatives[position][c];
+                needle_values[position] = corrected;
+                NeedleCorrection {
+                    position,
+                    observed: observed[position],
+                    corrected,
+                }
+            })
+            .collect();
+        candidates.push(NeedleCandidate {
+            needle_values,
+            corrections,
+        });
+
+        // Advance the mixed-radix counter (last position varies fastest)
+        let mut i = choice.len();
+        loop {
+            if i == 0 {
+                return;
+            }
+            i -= 1;
+            choice[i] += 1;
+            if choice[i] < alternatives[positions[i]].len() {
+                break;
+            }
+            choice[i] = 0;
+        }
+    }
+}
+
+/// Advance to the next k-combination of 0..n in lexicographic order
+fn next_combination(positions: &mut [usize], n: usize) -> bool {
+    let k = positions.len();
+    for i in (0..k).rev() {
+        if positions[i] < n - k + i {
+            positions[i] += 1;
+            for j in i + 1..k {
+                positions[j] = positions[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const READING: [u64; NEEDLE_COUNT] = [5, 12, 3, 8, 14, 1, 9, 0];
+
+    #[test]
+    fn test_gen_needle_values_matches_hash() {
+        use crate::domain::hash::{gen_hash, gen_hash_from_seed};
+
+        let values = gen_needle_values(12345, 417, NEEDLE_COUNT);
+        let needle_values: [u64; NEEDLE_COUNT] = values.try_into().unwrap();
+        assert_eq!(gen_hash(needle_values), gen_hash_from_seed(12345, 417));
+    }
+
+    #[test]
+    fn test_gen_needle_values_extends_prefix() {
+        let short = gen_needle_values(12345, 417, NEEDLE_COUNT);
+        let long = gen_needle_values(12345, 417, NEEDLE_COUNT + 4);
+        assert_eq!(&long[..NEEDLE_COUNT], short.as_slice());
+        assert!(long.iter().all(|&v| v < NEEDLE_STATES));
+    }
+
+    #[test]
+    fn test_needle_distance_wraps() {
+        assert_eq!(needle_distance(0, 16), 1);
+        assert_eq!(needle_distance(16, 0), 1);
+        assert_eq!(needle_distance(3, 5), 2);
+        assert_eq!(needle_distance(0, 8), 8);
+        assert_eq!(needle_distance(0, 9), 8);
+    }
+
+    #[test]
+    fn test_no_tolerance_yields_reading_only() {
+        let tolerance = NeedleTolerance::off_by_one().with_max_corrections(0);
+        let candidates = enumerate_needle_candidates(READING, &tolerance);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].needle_values, READING);
+        assert!(candidates[0].corrections.is_empty());
+    }
+
+    #[test]
+    fn test_off_by_one_candidates() {
+        let candidates = enumerate_needle_candidates(READING, &NeedleTolerance::off_by_one());
+
+        // Reading itself + 8 needles × (+1, -1)
+        assert_eq!(candidates.len(), 1 + 16);
+        for candidate in &candidates[1..] {
+            assert_eq!(candidate.corrections.len(), 1);
+            assert_eq!(candidate.total_delta(), 1);
+        }
+
+        // Needle 8 reads 0, so -1 wraps to 16
+        assert!(candidates.iter().any(|c| c.needle_values[7] == 16));
+    }
+
+    #[test]
+    fn test_one_unknown_candidates() {
+        let candidates = enumerate_needle_candidates(READING, &NeedleTolerance::one_unknown());
+
+        assert_eq!(candidates.len(), 1 + 8 * 16);
+        let unique: HashSet<_> = candidates.iter().map(|c| c.needle_values).collect();
+        assert_eq!(unique.len(), candidates.len());
+
+        // Every value of the first needle should be tried
+        let first_values: HashSet<u64> = candidates.iter().map(|c| c.needle_values[0]).collect();
+        assert_eq!(first_values.len(), NEEDLE_STATES as usize);
+    }
+
+    #[test]
+    fn test_two_corrections_ordered_by_count() {
+        let tolerance = NeedleTolerance::off_by_one().with_max_corrections(2);
+        let candidates = enumerate_needle_candidates(READING, &tolerance);
+
+        // 1 + 8×2 + C(8,2)×2×2
+        assert_eq!(candidates.len(), 1 + 16 + 28 * 4);
+        assert!(
+            candidates
+                .windows(2)
+                .all(|w| w[0].corrections.len() <= w[1].corrections.len())
+        );
+    }
+
+    #[test]
+    fn test_candidate_count() {
+        let tolerances = [
+            NeedleTolerance::off_by_one(),
+            NeedleTolerance::one_unknown(),
+            NeedleTolerance::off_by_one().with_max_corrections(0),
+            NeedleTolerance::off_by_one().with_max_corrections(2),
+            NeedleTolerance::one_unknown().with_max_corrections(2),
+            NeedleTolerance::off_by_one().with_max_delta(0),
+            NeedleTolerance::off_by_one().with_max_delta(100),
+        ];
+        for tolerance in tolerances {
+            assert_eq!(
+                tolerance.candidate_count(),
+                enumerate_needle_candidates(READING, &tolerance).len() as u64,
+                "{:?}",
+                tolerance
+            );
+            assert!(tolerance.is_within_limit());
+        }
+
+        // Every needle unknown: 17^8 readings
+        let all_unknown = NeedleTolerance::one_unknown().with_max_corrections(100);
+        assert_eq!(all_unknown.candidate_count(), NEEDLE_STATES.pow(8));
+        assert!(!all_unknown.is_within_limit());
+        assert!(enumerate_needle_candidates(READING, &all_unknown).is_empty());
+        assert!(
+            !NeedleTolerance::one_unknown()
+                .with_max_corrections(3)
+                .is_within_limit()
+        );
+    }
+
+    #[test]
+    fn test_reading_is_normalized() {
+        let raw = READING.map(|v| v + NEEDLE_STATES * 3);
+        let candidates = enumerate_needle_candidates(raw, &NeedleTolerance::off_by_one());
+        assert_eq!(candidates[0].needle_values, READING);
+    }
+}
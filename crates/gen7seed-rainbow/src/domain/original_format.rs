@@ -0,0 +1,181 @@
+//! Table layout of the original implementation (fujidig/sfmt-rainbow)
+//!
+//! The original stores a table as a headerless file of little-endian
+//! `(start_seed, end_seed)` u32 pairs, the same layout as the chains of a
+//! `.g7rt` file. Its chains use the additive reduction
+//! `(hash + column) mod 2^32` without a per-table salt, while this crate
+//! reduces with a salted SplitMix64 mix (see `reduce_hash_with_salt`), so
+//! chains generated by one cannot be searched by the other.
+//!
+//! The sorted files of the original (`.sorted.bin`) replace the end seed with
+//! the lower 32 bits of the end hash, which cannot be turned back into an end
+//! seed. No table generated by the original implementation can therefore be
+//! imported: only tables in this layout that use this crate's reduction
+//! (e.g. exported with `export_original_tables`).
+
+use crate::domain::chain::{ChainEntry, compute_chain};
+use crate::domain::hash::gen_hash_from_seed;
+
+/// Chain length used by the original implementation
+pub const ORIGINAL_CHAIN_LENGTH: u32 = 3000;
+
+/// Default number of chains recomputed to identify the reduction function
+pub const DEFAULT_REDUCTION_SAMPLES: usize = 16;
+
+/// Reduction function of the original implementation
+pub fn original_reduce_hash(hash: u64, column: u32) -> u32 {
+    (hash.wrapping_add(column as u64) & 0xFFFF_FFFF) as u32
+}
+
+/// Compute a single chain with the reduction function of the original implementation
+pub fn compute_original_chain(start_seed: u32, consumption: i32, chain_length: u32) -> ChainEntry {
+    let mut current_seed = start_seed;
+
+    for n in 0..chain_length {
+        let hash = gen_hash_from_seed(current_seed, consumption);
+        current_seed = original_reduce_hash(hash, n);
+    }
+
+    ChainEntry {
+        start_seed,
+        end_seed: current_seed,
+    }
+}
+
+/// Reduction function a set of chains was generated with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reduction {
+    /// Salted reduction of this crate (see `reduce_hash_with_salt`)
+    Salted,
+    /// Additive reduction of the original implementation (see `original_reduce_hash`)
+    Original,
+}
+
+/// Identify the reduction function of a table by recomputing sample chains
+///
+/// Recomputes up to `samples` chains spread evenly over `entries` with both
+/// reduction functions. Returns `None` if the end seeds match neither, e.g.
+/// because the consumption or chain length is wrong or the file does not
+/// hold end seeds. Empty tables are reported as `Salted`.
+pub fn detect_reduction(
+    entries: &[ChainEntry],
+    consumption: i32,
+    table_id: u32,
+    chain_length: u32,
+    samples: usize,
+) -> Option<Reduction> {
+    let samples = samples.clamp(1, entries.len().max(1));
+    let step = (entries.len() / samples).max(1);
+    let sampled: Vec<&ChainEntry> = entries.iter().step_by(step).take(samples).collect();
+
+    let matches = |compute: &dyn Fn(u32) -> ChainEntry| {
+        sampled
+            .iter()
+            .all(|&&entry| compute(entry.start_seed) == entry)
+    };
+
+    if matches(&|seed| compute_chain(seed, consumption, table_id, chain_length)) {
+        Some(Reduction::Salted)
+    } else if matches(&|seed| compute_original_chain(seed, consumption, chain_length)) {
+        Some(Reduction::Original)
+    } else {
+        None
+    }
+}
+
+/// Check if a table is a sorted table of the original implementation
+///
+/// Recomputes up to `samples` chains spread evenly over `entries` with the
+/// original reduction and compares the lower 32 bits of their end hashes
+/// with the stored values. Empty tables are never reported.
+pub fn holds_truncated_end_hashes(
+    entries: &[ChainEntry],
+    consumption: i32,
+    chain_length: u32,
+    samples: usize,
+) -> bool {
+    let samples = samples.clamp(1, entries.len().max(1));
+    let step = (entries.len() / samples).max(1);
+
+    !entries.is_empty()
+        && entries.iter().step_by(step).take(samples).all(|entry| {
+            let end_seed =
+                compute_original_chain(entry.start_seed, consumption, chain_length).end_seed;
+            gen_hash_from_seed(end_seed, consumption) as u32 == entry.end_seed
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN_LENGTH: u32 = 8;
+
+    #[test]
+    fn test_original_reduce_hash() {
+        assert_eq!(original_reduce_hash(0x1_0000_0005, 3), 8);
+        assert_eq!(original_reduce_hash(0xFFFF_FFFF, 1), 0);
+    }
+
+    #[test]
+    fn test_original_chain_differs_from_salted() {
+        let original = compute_original_chain(12345, 417, CHAIN_LENGTH);
+        let salted = compute_chain(12345, 417, 0, CHAIN_LENGTH);
+        assert_eq!(original.start_seed, salted.start_seed);
+        assert_ne!(original.end_seed, salted.end_seed);
+    }
+
+    #[test]
+    fn test_detect_reduction() {
+        let salted: Vec<ChainEntry> = (0..40)
+            .map(|seed| compute_chain(seed, 417, 2, CHAIN_LENGTH))
+            .collect();
+        let original: Vec<ChainEntry> = (0..40)
+            .map(|seed| compute_original_chain(seed, 417, CHAIN_LENGTH))
+            .collect();
+
+        assert_eq!(
+            detect_reduction(&salted, 417, 2, CHAIN_LENGTH, 4),
+            Some(Reduction::Salted)
+        );
+        assert_eq!(
+            detect_reduction(&original, 417, 2, CHAIN_LENGTH, 4),
+            Some(Reduction::Original)
+        );
+        // Wrong salt, consumption or chain length
+        assert_eq!(detect_reduction(&salted, 417, 3, CHAIN_LENGTH, 4), None);
+        assert_eq!(detect_reduction(&salted, 477, 2, CHAIN_LENGTH, 4), None);
+        assert_eq!(
+            detect_reduction(&original, 417, 0, CHAIN_LENGTH + 1, 4),
+            None
+        );
+    }
+
+    #[test]
+    fn test_holds_truncated_end_hashes() {
+        let original: Vec<ChainEntry> = (0..40)
+            .map(|seed| compute_original_chain(seed, 417, CHAIN_LENGTH))
+            .collect();
+        let sorted: Vec<ChainEntry> = original
+            .iter()
+            .map(|entry| ChainEntry {
+                start_seed: entry.start_seed,
+                end_seed: gen_hash_from_seed(entry.end_seed, 417) as u32,
+            })
+            .collect();
+
+        assert!(holds_truncated_end_hashes(&sorted, 417, CHAIN_LENGTH, 4));
+        assert_eq!(detect_reduction(&sorted, 417, 0, CHAIN_LENGTH, 4), None);
+        assert!(!holds_truncated_end_hashes(&original, 417, CHAIN_LENGTH, 4));
+        assert!(!holds_truncated_end_hashes(&sorted, 477, CHAIN_LENGTH, 4));
+        assert!(!holds_truncated_end_hashes(&[], 417, CHAIN_LENGTH, 4));
+    }
+
+    #[test]
+    fn test_detect_reduction_empty() {
+        assert_eq!(
+            detect_reduction(&[], 417, 0, CHAIN_LENGTH, 4),
+            Some(Reduction::Salted)
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! Sectioned table file format definitions
+//!
+//! Files of format version `SECTIONED_FORMAT_VERSION` store a section
+//! directory after the header instead of a fixed data layout. Each entry
+//! records the type, layout version, byte range and checksum of one section,
+//! so optional data (end hashes, missing seeds, metadata, checkpoints) can be
+//! stored in the same file and readers skip sections they do not know.
+//!
+//! Layout after the 64-byte header:
+//! - section count (u32) and 4 reserved bytes
+//! - one 32-byte `SectionEntry` per section
+//! - the sections, each starting at a multiple of `SECTION_ALIGNMENT` bytes
+
+use crate::constants::FILE_HEADER_SIZE;
+use crate::domain::table_format::TableFormatError;
+
+/// Byte size of a section directory entry
+pub const SECTION_ENTRY_SIZE: usize = 32;
+
+/// Byte size of the section count before the entries
+pub const SECTION_DIRECTORY_HEADER_SIZE: usize = 8;
+
+/// Alignment of section offsets (so chains and end hashes can be viewed in place)
+pub const SECTION_ALIGNMENT: u64 = 8;
+
+/// Layout version of the sections written by this version
+pub const SECTION_LAYOUT_VERSION: u16 = 1;
+
+/// Type of a section
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SectionKind {
+    /// Chain entries of all tables, as little-endian (start, end) pairs
+    ChainData,
+    /// Precomputed end hashes of all tables (u64, little-endian), in chain order
+    KeyCache,
+    /// Missing seeds of the table, stored as a complete `.g7ms` image
+    MissingSeeds,
+    /// Free-form metadata (e.g. UTF-8 text)
+    Metadata,
+    /// Generation checkpoint data
+    Checkpoint,
+    /// Section type unknown to this version (skipped by readers)
+    Other(u16),
+}
+
+impl SectionKind {
+    /// Type code stored in the directory
+    pub fn code(self) -> u16 {
+        match self {
+            Self::ChainData => 1,
+            Self::KeyCache => 2,
+            Self::MissingSeeds => 3,
+            Self::Metadata => 4,
+            Self::Checkpoint => 5,
+            Self::Other(code) => code,
+        }
+    }
+
+    /// Section type of a stored type code
+    pub fn from_code(code: u16) -> Self {
+        match code {
+            1 => Self::ChainData,
+            2 => Self::KeyCache,
+            3 => Self::MissingSeeds,
+            4 => Self::Metadata,
+            5 => Self::Checkpoint,
+            code => Self::Other(code),
+        }
+    }
+}
+
+impl std::fmt::Display for SectionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ChainData => write!(f, "chain data"),
+            Self::KeyCache => write!(f, "key cache"),
+            Self::MissingSeeds => write!(f, "missing seeds"),
+            Self::Metadata => write!(f, "metadata"),
+            Self::Checkpoint => write!(f, "checkpoint"),
+            Self::Other(code) => write!(f, "unknown ({})", code),
+        }
+    }
+}
+
+/// Section directory entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SectionEntry {
+    /// Section type
+    pub kind: SectionKind,
+    /// Layout version of the section data
+    pub version: u16,
+    /// Byte offset of the section from the start of the file
+    pub offset: u64,
+    /// Byte length of the section
+    pub length: u64,
+    /// Checksum of the section data (see `domain::checksum`)
+    pub checksum: u64,
+}
+
+impl SectionEntry {
+    /// Byte offset of the end of the section
+    pub fn end(&self) -> u64 {
+        self.offset + self.length
+    }
+
+    /// Serialize entry to bytes (32 bytes)
+    pub fn to_bytes(&self) -> [u8; SECTION_ENTRY_SIZE] {
+        let mut buf = [0u8; SECTION_ENTRY_SIZE];
+
+        buf[0..2].copy_from_slice(&self.kind.code().to_le_bytes());
+        buf[2..4].copy_from_slice(&self.version.to_le_bytes());
+        // 4..8 reserved
+        buf[8..16].copy_from_slice(&self.offset.to_le_bytes());
+        buf[16..24].copy_from_slice(&self.length.to_le_bytes());
+        buf[24..32].copy_from_slice(&self.checksum.to_le_bytes());
+
+        buf
+    }
+
+    /// Deserialize entry from bytes
+    pub fn from_bytes(buf: &[u8; SECTION_ENTRY_SIZE]) -> Self {
+        let u64_at = |i: usize| u64::from_le_bytes(buf[i..i + 8].try_into().unwrap());
+
+        Self {
+            kind: SectionKind::from_code(u16::from_le_bytes([buf[0], buf[1]])),
+            version: u16::from_le_bytes([buf[2], buf[3]]),
+            offset: u64_at(8),
+            length: u64_at(16),
+            checksum: u64_at(24),
+        }
+    }
+}
+
+/// Section directory of a sectioned file
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SectionDirectory {
+    entries: Vec<SectionEntry>,
+}
+
+impl SectionDirectory {
+    /// Lay out sections of the given type, version, length and checksum
+    ///
+    /// Sections are placed in the given order after the directory, each at
+    /// the next multiple of `SECTION_ALIGNMENT` bytes.
+    pub fn layout(sections: &[(SectionKind, u16, u64, u64)]) -> Self {
+        let mut offset = directory_end(sections.len());
+        let entries = sections
+            .iter()
+            .map(|&(kind, version, length, checksum)| {
+                offset = offset.next_multiple_of(SECTION_ALIGNMENT);
+                let entry = SectionEntry {
+                    kind,
+                    version,
+                    offset,
+                    length,
+                    checksum,
+                };
+                offset += length;
+                entry
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Get the entries in file order
+    pub fn entries(&self) -> &[SectionEntry] {
+        &self.entries
+    }
+
+    /// Get the first section of the given type
+    pub fn find(&self, kind: SectionKind) -> Option<&SectionEntry> {
+        self.entries.iter().find(|entry| entry.kind == kind)
+    }
+
+    /// Byte size of the directory
+    pub fn byte_size(&self) -> u64 {
+        directory_end(self.entries.len()) - FILE_HEADER_SIZE as u64
+    }
+
+    /// Byte size of the file (end of the last section)
+    pub fn file_size(&self) -> u64 {
+        self.entries
+            .last()
+            .map_or(directory_end(0), SectionEntry::end)
+    }
+
+    /// Serialize the directory
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.byte_size() as usize);
+        buf.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&[0u8; 4]);
+        for entry in &self.entries {
+            buf.extend_from_slice(&entry.to_bytes());
+        }
+        buf
+    }
+
+    /// Read the section count from the start of the directory
+    pub fn section_count(buf: &[u8; SECTION_DIRECTORY_HEADER_SIZE]) -> u32 {
+        u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]])
+    }
+
+    /// Deserialize and check the directory of a file of `file_size` bytes
+    ///
+    /// `data` starts right after the header. Sections must be aligned, in
+    /// ascending order without overlap, and end exactly at the end of the file.
+    pub fn from_bytes(data: &[u8], file_size: u64) -> Result<Self, TableFormatError> {
+        let count_buf =
+            data.get(..SECTION_DIRECTORY_HEADER_SIZE)
+                .ok_or(TableFormatError::InvalidFileSize {
+                    expected: directory_end(0),
+                    found: file_size,
+                })?;
+        let count = Self::section_count(count_buf.try_into().unwrap()) as usize;
+
+        let end = directory_end(count);
+        if file_size < end || (data.len() as u64) < end - FILE_HEADER_SIZE as u64 {
+            return Err(TableFormatError::InvalidFileSize {
+                expected: end,
+                found: file_size,
+            });
+        }
+
+        let entries: Vec<SectionEntry> = data
+            [SECTION_DIRECTORY_HEADER_SIZE..(end - FILE_HEADER_SIZE as u64) as usize]
+            .chunks_exact(SECTION_ENTRY_SIZE)
+            .map(|buf| SectionEntry::from_bytes(buf.try_into().unwrap()))
+            .collect();
+
+        let mut position = end;
+        for entry in &entries {
+            if entry.offset < position
+                || entry.offset % SECTION_ALIGNMENT != 0
+                || entry.offset.checked_add(entry.length).is_none()
+            {
+                return Err(TableFormatError::InvalidSectionDirectory);
+            }
+            position = entry.end();
+        }
+
+        let directory = Self { entries };
+        if directory.file_size() != file_size {
+            return Err(TableFormatError::InvalidFileSize {
+                expected: directory.file_size(),
+                found: file_size,
+            });
+        }
+        Ok(directory)
+    }
+}
+
+/// Byte offset of the end of a directory with `count` entries
+fn directory_end(count: usize) -> u64 {
+    (FILE_HEADER_SIZE + SECTION_DIRECTORY_HEADER_SIZE + count * SECTION_ENTRY_SIZE) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_directory() -> SectionDirectory {
+        SectionDirectory::layout(&[
+            (SectionKind::ChainData, 1, 80, 0x1111),
+            (SectionKind::MissingSeeds, 1, 69, 0x2222),
+            (SectionKind::Other(0x7f00), 3, 5, 0x3333),
+        ])
+    }
+
+    #[test]
+    fn test_section_kind_codes() {
+        for kind in [
+            SectionKind::ChainData,
+            SectionKind::KeyCache,
+            SectionKind::MissingSeeds,
+            SectionKind::Metadata,
+            SectionKind::Checkpoint,
+            SectionKind::Other(42),
+        ] {
+            assert_eq!(SectionKind::from_code(kind.code()), kind);
+        }
+    }
+
+    #[test]
+    fn test_layout_aligns_sections() {
+        let directory = sample_directory();
+        let entries = directory.entries();
+
+        assert_eq!(entries[0].offset, 64 + 8 + 3 * 32);
+        assert_eq!(entries[1].offset, entries[0].end());
+        // 69 bytes of missing seeds are padded to the next multiple of 8
+        assert_eq!(entries[2].offset, entries[1].end() + 3);
+        assert_eq!(directory.file_size(), entries[2].end());
+        assert_eq!(
+            directory.find(SectionKind::MissingSeeds).unwrap().checksum,
+            0x2222
+        );
+        assert!(directory.find(SectionKind::KeyCache).is_none());
+    }
+
+    #[test]
+    fn test_directory_roundtrip() {
+        let directory = sample_directory();
+        let bytes = directory.to_bytes();
+        assert_eq!(bytes.len() as u64, directory.byte_size());
+
+        let restored = SectionDirectory::from_bytes(&bytes, directory.file_size()).unwrap();
+        assert_eq!(restored, directory);
+    }
+
+    #[test]
+    fn test_directory_rejects_bad_layout() {
+        let directory = sample_directory();
+        let bytes = directory.to_bytes();
+
+        assert!(matches!(
+            SectionDirectory::from_bytes(&bytes, directory.file_size() + 1),
+            Err(TableFormatError::InvalidFileSize { .. })
+        ));
+        assert!(matches!(
+            SectionDirectory::from_bytes(&bytes[..40], directory.file_size()),
+            Err(TableFormatError::InvalidFileSize { .. })
+        ));
+
+        // Overlapping sections
+        let mut entries = directory.entries().to_vec();
+        entries[1].offset = entries[0].offset;
+        let overlapping = SectionDirectory { entries }.to_bytes();
+        assert!(matches!(
+            SectionDirectory::from_bytes(&overlapping, directory.file_size()),
+            Err(TableFormatError::InvalidSectionDirectory)
+        ));
+    }
+
+    #[test]
+    fn test_empty_directory() {
+        let directory = SectionDirectory::layout(&[]);
+        assert_eq!(directory.file_size(), 72);
+        assert_eq!(
+            SectionDirectory::from_bytes(&directory.to_bytes(), 72).unwrap(),
+            directory
+        );
+    }
+}
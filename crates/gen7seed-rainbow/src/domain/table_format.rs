@@ -4,11 +4,114 @@
 //! including header structure and metadata.
 
 use crate::constants::{
-    CHAIN_ENTRY_SIZE, FILE_FORMAT_VERSION, FILE_HEADER_SIZE, FLAG_SORTED, MAX_CHAIN_LENGTH,
-    NUM_CHAINS, NUM_TABLES, TABLE_MAGIC,
+    CHAIN_ENTRY_SIZE, COMPACT_FORMAT_VERSION, END_HASH_SIZE, FILE_FORMAT_VERSION, FILE_HEADER_SIZE,
+    FLAG_COVERAGE_GUIDED, FLAG_END_HASH_KEYS, FLAG_FULL_HASH_SORT, FLAG_PERFECT, FLAG_SORTED,
+    MAX_CHAIN_LENGTH, NUM_CHAINS, NUM_TABLES, SECTIONED_FORMAT_VERSION, SOURCE_DATE_EPOCH,
+    TABLE_MAGIC,
 };
+use crate::domain::chain::EndHashKey;
+use crate::domain::compact::{COMPACT_DESCRIPTOR_SIZE, CompactTableDescriptor};
+use crate::domain::section_format::SectionKind;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Rainbow table parameters
+///
+/// Recorded in the file header, so that tables of any size can be generated,
+/// searched and analyzed without recompiling. The default is the
+/// compile-time constants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TableParams {
+    /// Chain length (steps per chain)
+    pub chain_length: u32,
+    /// Number of chains per table
+    pub chains_per_table: u32,
+    /// Number of tables
+    pub num_tables: u32,
+}
+
+impl Default for TableParams {
+    fn default() -> Self {
+        Self {
+            chain_length: MAX_CHAIN_LENGTH,
+            chains_per_table: NUM_CHAINS,
+            num_tables: NUM_TABLES,
+        }
+    }
+}
+
+impl TableParams {
+    /// Create parameters
+    pub fn new(chain_length: u32, chains_per_table: u32, num_tables: u32) -> Self {
+        Self {
+            chain_length,
+            chains_per_table,
+            num_tables,
+        }
+    }
+
+    /// Check that every parameter is non-zero
+    pub fn validate(&self) -> Result<(), TableFormatError> {
+        if self.chain_length == 0 || self.chains_per_table == 0 || self.num_tables == 0 {
+            return Err(TableFormatError::InvalidParams(*self));
+        }
+        Ok(())
+    }
+
+    /// Total number of chains in all tables
+    pub fn total_chains(&self) -> u64 {
+        self.chains_per_table as u64 * self.num_tables as u64
+    }
+}
+
+/// How the start seeds of the chains were chosen
+///
+/// Recorded in the file header with `FLAG_COVERAGE_GUIDED`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StartPoints {
+    /// Consecutive seeds `0..chains_per_table` in every table
+    #[default]
+    Sequential,
+    /// The lowest seeds not yet covered by earlier chains of any table
+    /// (see `generate_table_guided`)
+    CoverageGuided,
+}
+
+impl StartPoints {
+    /// Start point strategy recorded in the header flags
+    pub fn from_flags(flags: u32) -> Self {
+        if flags & FLAG_COVERAGE_GUIDED != 0 {
+            Self::CoverageGuided
+        } else {
+            Self::Sequential
+        }
+    }
+
+    /// Header flags for this strategy
+    pub fn flags(self) -> u32 {
+        match self {
+            Self::Sequential => 0,
+            Self::CoverageGuided => FLAG_COVERAGE_GUIDED,
+        }
+    }
+}
+
+/// Creation timestamp for new headers (Unix epoch seconds)
+///
+/// Uses `SOURCE_DATE_EPOCH` if it is set to a valid number, so that files
+/// generated independently from the same input can be compared byte for
+/// byte; otherwise the current time.
+pub fn creation_timestamp() -> u64 {
+    std::env::var(SOURCE_DATE_EPOCH)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        })
+}
+
 /// Table file header metadata
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TableHeader {
@@ -26,41 +129,137 @@ pub struct TableHeader {
     pub flags: u32,
     /// Creation timestamp (Unix epoch seconds)
     pub created_at: u64,
+    /// Checksum of the data after the header (None = not recorded)
+    pub checksum: Option<u64>,
+    /// Checksum of the chain entries alone (None = not recorded, e.g. compact tables)
+    ///
+    /// Missing seeds files are bound to it (see `calculate_source_checksum`).
+    pub chains_checksum: Option<u64>,
 }
 
 impl TableHeader {
-    /// Create a new header with current parameters
+    /// Create a new header with the default parameters
     pub fn new(consumption: i32, sorted: bool) -> Self {
-        let created_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
+        Self::with_params(consumption, TableParams::default(), sorted)
+    }
+
+    /// Create a new header with the given parameters
+    pub fn with_params(consumption: i32, params: TableParams, sorted: bool) -> Self {
+        let created_at = creation_timestamp();
 
         Self {
             version: FILE_FORMAT_VERSION,
             consumption,
-            chain_length: MAX_CHAIN_LENGTH,
-            chains_per_table: NUM_CHAINS,
-            num_tables: NUM_TABLES,
-            flags: if sorted { FLAG_SORTED } else { 0 },
+            chain_length: params.chain_length,
+            chains_per_table: params.chains_per_table,
+            num_tables: params.num_tables,
+            flags: if sorted {
+                FLAG_SORTED | FLAG_FULL_HASH_SORT
+            } else {
+                0
+            },
             created_at,
+            checksum: None,
+            chains_checksum: None,
         }
     }
 
+    /// Table parameters recorded in this header
+    pub fn params(&self) -> TableParams {
+        TableParams::new(self.chain_length, self.chains_per_table, self.num_tables)
+    }
+
     /// Check if table is sorted
     pub fn is_sorted(&self) -> bool {
         self.flags & FLAG_SORTED != 0
     }
 
-    /// Set sorted flag
+    /// Set sorted flag (sorted by the full end hash, as done by `sort_table_parallel`)
     pub fn set_sorted(&mut self, sorted: bool) {
         if sorted {
-            self.flags |= FLAG_SORTED;
+            self.flags |= FLAG_SORTED | FLAG_FULL_HASH_SORT;
         } else {
-            self.flags &= !FLAG_SORTED;
+            self.flags &= !(FLAG_SORTED | FLAG_FULL_HASH_SORT);
         }
     }
 
+    /// Sort key of the chains in this file
+    ///
+    /// Files without `FLAG_FULL_HASH_SORT` are sorted by the lower 32 bits of the end hash.
+    pub fn end_hash_key(&self) -> EndHashKey {
+        if self.flags & FLAG_FULL_HASH_SORT != 0 {
+            EndHashKey::Full
+        } else {
+            EndHashKey::Truncated
+        }
+    }
+
+    /// Check if the file contains precomputed end hashes
+    pub fn has_end_hashes(&self) -> bool {
+        self.flags & FLAG_END_HASH_KEYS != 0
+    }
+
+    /// Set the precomputed end hashes flag
+    pub fn set_end_hashes(&mut self, present: bool) {
+        if present {
+            self.flags |= FLAG_END_HASH_KEYS;
+        } else {
+            self.flags &= !FLAG_END_HASH_KEYS;
+        }
+    }
+
+    /// Check if every chain of a table has a distinct end hash
+    pub fn is_perfect(&self) -> bool {
+        self.flags & FLAG_PERFECT != 0
+    }
+
+    /// Set the perfect table flag
+    pub fn set_perfect(&mut self, perfect: bool) {
+        if perfect {
+            self.flags |= FLAG_PERFECT;
+        } else {
+            self.flags &= !FLAG_PERFECT;
+        }
+    }
+
+    /// How the start seeds of the chains were chosen
+    pub fn start_points(&self) -> StartPoints {
+        StartPoints::from_flags(self.flags)
+    }
+
+    /// Record how the start seeds of the chains were chosen
+    pub fn set_start_points(&mut self, start_points: StartPoints) {
+        self.flags = (self.flags & !FLAG_COVERAGE_GUIDED) | start_points.flags();
+    }
+
+    /// Check if the file uses the compact table format
+    pub fn is_compact(&self) -> bool {
+        self.version == COMPACT_FORMAT_VERSION
+    }
+
+    /// Select the compact table format (or the chain entry format)
+    pub fn set_compact(&mut self, compact: bool) {
+        self.version = if compact {
+            COMPACT_FORMAT_VERSION
+        } else {
+            FILE_FORMAT_VERSION
+        };
+    }
+
+    /// Check if the file uses the sectioned format (see `domain::section_format`)
+    pub fn is_sectioned(&self) -> bool {
+        self.version == SECTIONED_FORMAT_VERSION
+    }
+
+    /// Select the sectioned format (or the chain entry format)
+    pub fn set_sectioned(&mut self, sectioned: bool) {
+        self.version = if sectioned {
+            SECTIONED_FORMAT_VERSION
+        } else {
+            FILE_FORMAT_VERSION
+        };
+    }
+
     /// Serialize header to bytes (64 bytes)
     pub fn to_bytes(&self) -> [u8; FILE_HEADER_SIZE] {
         let mut buf = [0u8; FILE_HEADER_SIZE];
@@ -74,7 +273,9 @@ impl TableHeader {
         buf[24..28].copy_from_slice(&self.num_tables.to_le_bytes());
         buf[28..32].copy_from_slice(&self.flags.to_le_bytes());
         buf[32..40].copy_from_slice(&self.created_at.to_le_bytes());
-        // 40..64 reserved
+        buf[40..48].copy_from_slice(&self.checksum.unwrap_or(0).to_le_bytes());
+        buf[48..56].copy_from_slice(&self.chains_checksum.unwrap_or(0).to_le_bytes());
+        // 56..64 reserved
 
         buf
     }
@@ -86,7 +287,10 @@ impl TableHeader {
         }
 
         let version = u16::from_le_bytes([buf[8], buf[9]]);
-        if version != FILE_FORMAT_VERSION {
+        if version != FILE_FORMAT_VERSION
+            && version != COMPACT_FORMAT_VERSION
+            && version != SECTIONED_FORMAT_VERSION
+        {
             return Err(TableFormatError::UnsupportedVersion(version));
         }
 
@@ -100,6 +304,14 @@ impl TableHeader {
             created_at: u64::from_le_bytes([
                 buf[32], buf[33], buf[34], buf[35], buf[36], buf[37], buf[38], buf[39],
             ]),
+            checksum: Some(u64::from_le_bytes([
+                buf[40], buf[41], buf[42], buf[43], buf[44], buf[45], buf[46], buf[47],
+            ]))
+            .filter(|&checksum| checksum != 0),
+            chains_checksum: Some(u64::from_le_bytes([
+                buf[48], buf[49], buf[50], buf[51], buf[52], buf[53], buf[54], buf[55],
+            ]))
+            .filter(|&checksum| checksum != 0),
         })
     }
 }
@@ -111,20 +323,26 @@ pub struct ValidationOptions {
     pub expected_consumption: Option<i32>,
     /// Require sorted table
     pub require_sorted: bool,
-    /// Validate against compile-time constants
-    pub validate_constants: bool,
+    /// Expected table parameters (None = accept any valid parameters)
+    pub expected_params: Option<TableParams>,
 }
 
 impl ValidationOptions {
-    /// Create options for search (requires sorted, validates all)
+    /// Create options for search (requires sorted, accepts any parameters)
     pub fn for_search(consumption: i32) -> Self {
         Self {
             expected_consumption: Some(consumption),
             require_sorted: true,
-            validate_constants: true,
+            expected_params: None,
         }
     }
 
+    /// Require the given table parameters
+    pub fn with_params(mut self, params: TableParams) -> Self {
+        self.expected_params = Some(params);
+        self
+    }
+
     /// Create options for generation (no validation)
     pub fn for_generation() -> Self {
         Self::default()
@@ -146,10 +364,30 @@ pub enum TableFormatError {
     ChainCountMismatch { expected: u32, found: u32 },
     /// Number of tables mismatch
     TableCountMismatch { expected: u32, found: u32 },
+    /// Table parameters are zero
+    InvalidParams(TableParams),
     /// Table is not sorted (required for search)
     TableNotSorted,
     /// File size does not match expected size
     InvalidFileSize { expected: u64, found: u64 },
+    /// Data does not match the checksum recorded in the header
+    ChecksumMismatch { expected: u64, found: u64 },
+    /// Compact table descriptor does not match the header or the file
+    InvalidCompactTable { table_id: u32 },
+    /// Compact tables have no end seeds and cannot be loaded as chain entries
+    CompactNotSupported,
+    /// Section directory entries are misaligned, out of order or overlapping
+    InvalidSectionDirectory,
+    /// Required section is not in the section directory
+    MissingSection(SectionKind),
+    /// Section has an unsupported layout version or size
+    UnsupportedSection { kind: SectionKind, version: u16 },
+    /// Section data does not match the checksum recorded in the directory
+    SectionChecksumMismatch {
+        kind: SectionKind,
+        expected: u64,
+        found: u64,
+    },
     /// I/O error
     Io(String),
 }
@@ -181,12 +419,45 @@ impl std::fmt::Display for TableFormatError {
                 "Table count mismatch: expected {}, found {}",
                 expected, found
             ),
+            Self::InvalidParams(params) => write!(
+                f,
+                "Invalid table parameters: chain length {}, {} chains per table, {} tables",
+                params.chain_length, params.chains_per_table, params.num_tables
+            ),
             Self::TableNotSorted => write!(f, "Table is not sorted (required for search)"),
             Self::InvalidFileSize { expected, found } => write!(
                 f,
                 "Invalid file size: expected {} bytes, found {} bytes",
                 expected, found
             ),
+            Self::ChecksumMismatch { expected, found } => write!(
+                f,
+                "Checksum mismatch: expected {:016x}, found {:016x}",
+                expected, found
+            ),
+            Self::InvalidCompactTable { table_id } => {
+                write!(f, "Invalid compact table: table {}", table_id)
+            }
+            Self::CompactNotSupported => write!(
+                f,
+                "Compact tables cannot be loaded as chain entries (memory-map them instead)"
+            ),
+            Self::InvalidSectionDirectory => write!(f, "Invalid section directory"),
+            Self::MissingSection(kind) => write!(f, "Missing {} section", kind),
+            Self::UnsupportedSection { kind, version } => write!(
+                f,
+                "Unsupported {} section: version {} or size does not match the header",
+                kind, version
+            ),
+            Self::SectionChecksumMismatch {
+                kind,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Checksum mismatch in {} section: expected {:016x}, found {:016x}",
+                kind, expected, found
+            ),
             Self::Io(msg) => write!(f, "I/O error: {}", msg),
         }
     }
@@ -218,22 +489,24 @@ pub fn validate_header(
         return Err(TableFormatError::TableNotSorted);
     }
 
-    if options.validate_constants {
-        if header.chain_length != MAX_CHAIN_LENGTH {
+    header.params().validate()?;
+
+    if let Some(expected) = options.expected_params {
+        if header.chain_length != expected.chain_length {
             return Err(TableFormatError::ChainLengthMismatch {
-                expected: MAX_CHAIN_LENGTH,
+                expected: expected.chain_length,
                 found: header.chain_length,
             });
         }
-        if header.chains_per_table != NUM_CHAINS {
+        if header.chains_per_table != expected.chains_per_table {
             return Err(TableFormatError::ChainCountMismatch {
-                expected: NUM_CHAINS,
+                expected: expected.chains_per_table,
                 found: header.chains_per_table,
             });
         }
-        if header.num_tables != NUM_TABLES {
+        if header.num_tables != expected.num_tables {
             return Err(TableFormatError::TableCountMismatch {
-                expected: NUM_TABLES,
+                expected: expected.num_tables,
                 found: header.num_tables,
             });
         }
@@ -243,8 +516,36 @@ pub fn validate_header(
 }
 
 /// Calculate expected file size from header
+///
+/// Layout: header, chains of all tables, then (if `FLAG_END_HASH_KEYS`) the
+/// end hashes of all tables in the same order as the chains. For compact
+/// tables see `expected_compact_file_size`; sectioned files are sized by
+/// their section directory (see `SectionDirectory::file_size`).
 pub fn expected_file_size(header: &TableHeader) -> u64 {
-    let data_size =
-        header.chains_per_table as u64 * header.num_tables as u64 * CHAIN_ENTRY_SIZE as u64;
-    FILE_HEADER_SIZE as u64 + data_size
+    let num_chains = header.params().total_chains();
+    let key_size = if header.has_end_hashes() {
+        num_chains * END_HASH_SIZE as u64
+    } else {
+        0
+    };
+    FILE_HEADER_SIZE as u64 + num_chains * CHAIN_ENTRY_SIZE as u64 + key_size
+}
+
+/// Byte offset of the end hash section
+pub fn end_hash_section_offset(header: &TableHeader) -> u64 {
+    FILE_HEADER_SIZE as u64 + header.params().total_chains() * CHAIN_ENTRY_SIZE as u64
+}
+
+/// Calculate expected file size of a compact table file from its descriptors
+///
+/// Layout: header, one `CompactTableDescriptor` per table, then the parts of
+/// all tables in table order. Saturates at `u64::MAX` for descriptors that
+/// are not consistent.
+pub fn expected_compact_file_size(descriptors: &[CompactTableDescriptor]) -> u64 {
+    let header_size =
+        FILE_HEADER_SIZE as u64 + descriptors.len() as u64 * COMPACT_DESCRIPTOR_SIZE as u64;
+    descriptors
+        .iter()
+        .map(|d| d.byte_size())
+        .fold(header_size, u64::saturating_add)
 }
@@ -0,0 +1,261 @@
+//! Generation checkpoint I/O operations
+//!
+//! This module provides functions for reading and writing checkpoint files in
+//! a work directory. Checkpoints are written atomically, so a file that exists
+//! is always complete.
+
+use crate::constants::{CHECKPOINT_FILE_EXTENSION, FILE_HEADER_SIZE};
+use crate::domain::chain::ChainEntry;
+use crate::domain::checkpoint_format::{CheckpointHeader, expected_checkpoint_file_size};
+use crate::domain::table_format::{TableFormatError, ValidationOptions, validate_header};
+use crate::infra::table_io::{read_entries, write_atomic, write_end_hashes, write_entries};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Get the checkpoint path for a range of chains of one table
+///
+/// Format: `{work_dir}/{consumption}_t{table_id}_{start}-{end}.g7cp`
+pub fn get_chunk_checkpoint_path(
+    work_dir: impl AsRef<Path>,
+    consumption: i32,
+    table_id: u32,
+    range: Range<u32>,
+) -> PathBuf {
+    work_dir.as_ref().join(format!(
+        "{}_t{:02}_{:07}-{:07}.{}",
+        consumption, table_id, range.start, range.end, CHECKPOINT_FILE_EXTENSION
+    ))
+}
+
+/// Get the checkpoint path for a finished table
+///
+/// Format: `{work_dir}/{consumption}_t{table_id}.g7cp`
+pub fn get_table_checkpoint_path(
+    work_dir: impl AsRef<Path>,
+    consumption: i32,
+    table_id: u32,
+) -> PathBuf {
+    work_dir.as_ref().join(format!(
+        "{}_t{:02}.{}",
+        consumption, table_id, CHECKPOINT_FILE_EXTENSION
+    ))
+}
+
+/// List the checkpoint files for a consumption value in the work directory
+///
+/// Returns an empty list if the directory does not exist.
+pub fn find_checkpoints(
+    work_dir: impl AsRef<Path>,
+    consumption: i32,
+) -> std::io::Result<Vec<PathBuf>> {
+    let prefix = format!("{}_t", consumption);
+    let suffix = format!(".{}", CHECKPOINT_FILE_EXTENSION);
+
+    let entries = match fs::read_dir(work_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(&suffix));
+        if matches {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Save a checkpoint atomically
+///
+/// `end_hashes` must be given if and only if the header has the end hashes flag.
+pub fn save_checkpoint(
+    path: impl AsRef<Path>,
+    header: &CheckpointHeader,
+    entries: &[ChainEntry],
+    end_hashes: Option<&[u64]>,
+) -> Result<(), TableFormatError> {
+    if entries.len() != header.chain_count() as usize {
+        return Err(TableFormatError::ChainCountMismatch {
+            expected: header.chain_count(),
+            found: entries.len() as u32,
+        });
+    }
+    if let Some(hashes) = end_hashes
+        && hashes.len() != entries.len()
+    {
+        return Err(TableFormatError::ChainCountMismatch {
+            expected: header.chain_count(),
+            found: hashes.len() as u32,
+        });
+    }
+
+    let mut header = *header;
+    header.set_end_hashes(end_hashes.is_some());
+
+    write_atomic(path.as_ref(), |writer| {
+        writer.write_all(&header.to_bytes())?;
+        write_entries(writer, entries)?;
+        if let Some(hashes) = end_hashes {
+            write_end_hashes(writer, hashes)?;
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// Load a checkpoint with validation
+///
+/// The recorded parameters are validated with `options` (see `validate_header`).
+/// Returns the header, the chains and the end hashes (if stored).
+#[allow(clippy::type_complexity)]
+pub fn load_checkpoint(
+    path: impl AsRef<Path>,
+    options: &ValidationOptions,
+) -> Result<(CheckpointHeader, Vec<ChainEntry>, Option<Vec<u64>>), TableFormatError> {
+    let file = File::open(path.as_ref())?;
+    let metadata = file.metadata()?;
+
+    let mut reader = BufReader::new(file);
+    let mut header_buf = [0u8; FILE_HEADER_SIZE];
+    reader.read_exact(&mut header_buf)?;
+
+    let header = CheckpointHeader::from_bytes(&header_buf)?;
+    validate_header(&header.table_header(), options)?;
+
+    let expected_size = expected_checkpoint_file_size(&header);
+    if metadata.len() != expected_size {
+        return Err(TableFormatError::InvalidFileSize {
+            expected: expected_size,
+            found: metadata.len(),
+        });
+    }
+
+    let count = header.chain_count() as usize;
+    let entries = read_entries(&mut reader, count)?;
+    let end_hashes = if header.has_end_hashes() {
+        let mut hashes = Vec::with_capacity(count);
+        for _ in 0..count {
+            hashes.push(reader.read_u64::<LittleEndian>()?);
+        }
+        Some(hashes)
+    } else {
+        None
+    };
+
+    Ok((header, entries, end_hashes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::table_format::TableParams;
+    use tempfile::TempDir;
+
+    fn entries(range: Range<u32>) -> Vec<ChainEntry> {
+        range.map(|seed| ChainEntry::new(seed, seed * 3)).collect()
+    }
+
+    fn options(consumption: i32) -> ValidationOptions {
+        ValidationOptions {
+            expected_consumption: Some(consumption),
+            require_sorted: false,
+            expected_params: Some(TableParams::default()),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_checkpoint() {
+        let dir = TempDir::new().unwrap();
+        let path = get_chunk_checkpoint_path(dir.path(), 417, 3, 10..20);
+        let header = CheckpointHeader::new(417, TableParams::default(), 3, 10..20);
+
+        save_checkpoint(&path, &header, &entries(10..20), None).unwrap();
+        let (loaded_header, loaded, end_hashes) = load_checkpoint(&path, &options(417)).unwrap();
+
+        assert_eq!(loaded_header, header);
+        assert_eq!(loaded, entries(10..20));
+        assert!(end_hashes.is_none());
+        assert_eq!(find_checkpoints(dir.path(), 417).unwrap(), vec![path]);
+    }
+
+    #[test]
+    fn test_save_and_load_checkpoint_with_end_hashes() {
+        let dir = TempDir::new().unwrap();
+        let path = get_table_checkpoint_path(dir.path(), 417, 0);
+        let mut header = CheckpointHeader::new(417, TableParams::default(), 0, 0..4);
+        header.set_sorted(true);
+        let hashes = vec![1u64, 2, 3, 1 << 32];
+
+        save_checkpoint(&path, &header, &entries(0..4), Some(&hashes)).unwrap();
+        let (loaded_header, _, end_hashes) = load_checkpoint(&path, &options(417)).unwrap();
+
+        assert!(loaded_header.is_sorted());
+        assert!(loaded_header.has_end_hashes());
+        assert_eq!(end_hashes, Some(hashes));
+    }
+
+    #[test]
+    fn test_load_checkpoint_consumption_mismatch() {
+        let dir = TempDir::new().unwrap();
+        let path = get_chunk_checkpoint_path(dir.path(), 417, 0, 0..2);
+        save_checkpoint(
+            &path,
+            &CheckpointHeader::new(417, TableParams::default(), 0, 0..2),
+            &entries(0..2),
+            None,
+        )
+        .unwrap();
+
+        let result = load_checkpoint(&path, &options(477));
+        assert!(matches!(
+            result,
+            Err(TableFormatError::ConsumptionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_save_checkpoint_chain_count_mismatch() {
+        let dir = TempDir::new().unwrap();
+        let path = get_chunk_checkpoint_path(dir.path(), 417, 0, 0..5);
+        let result = save_checkpoint(
+            &path,
+            &CheckpointHeader::new(417, TableParams::default(), 0, 0..5),
+            &entries(0..4),
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(TableFormatError::ChainCountMismatch { .. })
+        ));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_find_checkpoints_filters_consumption() {
+        let dir = TempDir::new().unwrap();
+        for consumption in [417, 477] {
+            let path = get_table_checkpoint_path(dir.path(), consumption, 1);
+            let header = CheckpointHeader::new(consumption, TableParams::default(), 1, 0..1);
+            save_checkpoint(&path, &header, &entries(0..1), None).unwrap();
+        }
+
+        let found = find_checkpoints(dir.path(), 477).unwrap();
+        assert_eq!(found, vec![get_table_checkpoint_path(dir.path(), 477, 1)]);
+        assert!(
+            find_checkpoints(dir.path().join("missing"), 417)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
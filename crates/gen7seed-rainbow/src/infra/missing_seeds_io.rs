@@ -3,15 +3,25 @@
 //! This module provides functions for reading and writing missing seeds files.
 
 use crate::constants::{FILE_HEADER_SIZE, MISSING_FILE_EXTENSION};
+use crate::domain::checksum::ChecksumStatus;
+use crate::domain::missing_blocks::{MISSING_BLOCK_COUNT, MissingBlocksRef, encode_missing_blocks};
 use crate::domain::missing_format::{
     MissingFormatError, MissingSeedsHeader, expected_missing_file_size,
 };
+use crate::domain::section_format::SectionKind;
 use crate::domain::table_format::TableHeader;
+use crate::infra::table_io::{ChecksumReader, SingleTableView, write_with_checksum};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use memmap2::Mmap;
+use rayon::prelude::*;
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, BufWriter, Read, Seek, Write};
+use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
 
+/// Number of seeds per chunk when scanning uncompressed seeds in parallel
+const SEED_CHUNK_SIZE: usize = 1 << 16;
+
 fn ensure_parent_dir(path: &Path) -> std::io::Result<()> {
     if let Some(parent) = path.parent()
         && !parent.as_os_str().is_empty()
@@ -37,22 +47,90 @@ pub fn save_missing_seeds(
     seeds: &[u32],
 ) -> Result<(), MissingFormatError> {
     ensure_parent_dir(path.as_ref())?;
-    let header = MissingSeedsHeader::new(source_header, seeds.len() as u64);
-
-    let file = File::create(path)?;
-    let mut writer = BufWriter::new(file);
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_missing_seeds(&mut writer, source_header, seeds, false)?;
+    writer.flush()?;
+    Ok(())
+}
 
-    writer.write_all(&header.to_bytes())?;
+/// Save missing seeds in the compressed format
+///
+/// Each block of 2^16 seeds is stored as varint-coded deltas or as a bitmap,
+/// whichever is smaller (see `domain::missing_blocks`). The seeds are sorted
+/// and deduplicated if needed.
+pub fn save_missing_seeds_compressed(
+    path: impl AsRef<Path>,
+    source_header: &TableHeader,
+    seeds: &[u32],
+) -> Result<(), MissingFormatError> {
+    ensure_parent_dir(path.as_ref())?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_missing_seeds(&mut writer, source_header, seeds, true)?;
+    writer.flush()?;
+    Ok(())
+}
 
-    for &seed in seeds {
-        writer.write_u32::<LittleEndian>(seed)?;
+/// Write a missing seeds file to any seekable writer
+///
+/// Writes the raw u32 list, or the compressed format if `compressed` is set
+/// (the seeds are then sorted and deduplicated if needed).
+pub(crate) fn write_missing_seeds<W: Write + Seek>(
+    writer: &mut W,
+    source_header: &TableHeader,
+    seeds: &[u32],
+    compressed: bool,
+) -> std::io::Result<()> {
+    if !compressed {
+        let header = MissingSeedsHeader::new(source_header, seeds.len() as u64);
+        return write_with_checksum(
+            writer,
+            |checksum| {
+                MissingSeedsHeader {
+                    checksum: Some(checksum),
+                    ..header
+                }
+                .to_bytes()
+            },
+            |writer| {
+                for &seed in seeds {
+                    writer.write_u32::<LittleEndian>(seed)?;
+                }
+                Ok(())
+            },
+        );
     }
 
-    writer.flush()?;
-    Ok(())
+    let sorted;
+    let seeds = if seeds.windows(2).all(|w| w[0] < w[1]) {
+        seeds
+    } else {
+        let mut copy = seeds.to_vec();
+        copy.sort_unstable();
+        copy.dedup();
+        sorted = copy;
+        &sorted
+    };
+
+    let mut header = MissingSeedsHeader::new(source_header, seeds.len() as u64);
+    header.set_compressed(true);
+    let blocks = encode_missing_blocks(seeds);
+
+    write_with_checksum(
+        writer,
+        |checksum| {
+            MissingSeedsHeader {
+                checksum: Some(checksum),
+                ..header
+            }
+            .to_bytes()
+        },
+        |writer| writer.write_all(&blocks),
+    )
 }
 
 /// Load missing seeds with validation
+///
+/// Reads the whole list into memory; use `MissingSeeds` to query a file in place.
 pub fn load_missing_seeds(
     path: impl AsRef<Path>,
     expected_consumption: Option<i32>,
@@ -75,6 +153,17 @@ pub fn load_missing_seeds(
         });
     }
 
+    if header.is_compressed() {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let blocks = MissingBlocksRef::new(&bytes).ok_or(MissingFormatError::InvalidBlocks)?;
+        let seeds: Vec<u32> = blocks.iter().collect();
+        if seeds.len() as u64 != header.missing_count {
+            return Err(MissingFormatError::InvalidBlocks);
+        }
+        return Ok((header, seeds));
+    }
+
     let expected_size = expected_missing_file_size(&header);
     if metadata.len() != expected_size {
         return Err(MissingFormatError::InvalidFileSize {
@@ -91,6 +180,42 @@ pub fn load_missing_seeds(
     Ok((header, seeds))
 }
 
+/// Verify the checksum of a missing seeds file
+///
+/// Streams the seeds after the header and compares their checksum with the
+/// one recorded in the header.
+pub fn verify_missing_seeds_file(
+    path: impl AsRef<Path>,
+) -> Result<ChecksumStatus, MissingFormatError> {
+    let file = File::open(path.as_ref())?;
+    let metadata = file.metadata()?;
+
+    let mut reader = BufReader::new(file);
+    let mut header_buf = [0u8; FILE_HEADER_SIZE];
+    reader.read_exact(&mut header_buf)?;
+    let header = MissingSeedsHeader::from_bytes(&header_buf)?;
+
+    // Compressed files are sized by their block directory, which the checksum covers
+    if !header.is_compressed() {
+        let expected_size = expected_missing_file_size(&header);
+        if metadata.len() != expected_size {
+            return Err(MissingFormatError::InvalidFileSize {
+                expected: expected_size,
+                found: metadata.len(),
+            });
+        }
+    }
+
+    let Some(expected) = header.checksum else {
+        return Ok(ChecksumStatus::NotRecorded);
+    };
+    let found = ChecksumReader::new(reader).finish()?;
+    if found != expected {
+        return Err(MissingFormatError::ChecksumMismatch { expected, found });
+    }
+    Ok(ChecksumStatus::Verified(found))
+}
+
 /// Verify missing seeds file matches the given table
 pub fn verify_missing_seeds_source(
     missing_header: &MissingSeedsHeader,
@@ -99,10 +224,358 @@ pub fn verify_missing_seeds_source(
     missing_header.verify_source(table_header)
 }
 
+/// Load missing seeds for the given table
+///
+/// Validates the consumption value and verifies that the file was extracted
+/// from the table described by `table_header` (see `calculate_source_checksum`).
+pub fn load_missing_seeds_for_table(
+    path: impl AsRef<Path>,
+    table_header: &TableHeader,
+) -> Result<Vec<u32>, MissingFormatError> {
+    let (missing_header, seeds) = load_missing_seeds(path, Some(table_header.consumption))?;
+    verify_missing_seeds_source(&missing_header, table_header)?;
+    Ok(seeds)
+}
+
+/// Load missing seeds for a memory-mapped table, bound to its chains
+///
+/// Also accepts tables read from a byte buffer (see `SingleTableView`).
+/// The checksum of the chain entries is computed from the data (see
+/// `SingleTableView::bound_header`), so a missing seeds file is only
+/// accepted for a table whose chains match the ones it was extracted from,
+/// and is rejected for a corrupted table even if its header is intact.
+pub fn load_missing_seeds_for_mapped_table<B: AsRef<[u8]>>(
+    path: impl AsRef<Path>,
+    table: &SingleTableView<B>,
+) -> Result<Vec<u32>, MissingFormatError> {
+    load_missing_seeds_for_table(path, &table.bound_header())
+}
+
+// =============================================================================
+// Missing seeds views
+// =============================================================================
+
+/// Missing seeds file read in place from a byte buffer
+///
+/// Works with both the raw u32 list and the compressed format. `B` is the
+/// storage of the buffer: a memory-mapped file (`MissingSeeds`), a borrowed
+/// `&[u8]` or an owned `Vec<u8>`. Seeds are decoded on access, so a file can
+/// be queried and streamed without loading the whole list.
+///
+/// Lookups in the raw format use binary search and assume ascending seeds,
+/// as written from `extract_missing_seeds`.
+pub struct MissingSeedsView<B> {
+    header: MissingSeedsHeader,
+    bytes: B,
+}
+
+/// Memory-mapped missing seeds file
+pub type MissingSeeds = MissingSeedsView<Mmap>;
+
+/// Seeds after the header, in either format
+#[derive(Clone, Copy)]
+enum SeedData<'a> {
+    Raw(&'a [u8]),
+    Blocks(MissingBlocksRef<'a>),
+}
+
+impl MissingSeeds {
+    /// Open a missing seeds file as memory-mapped
+    pub fn open(
+        path: impl AsRef<Path>,
+        expected_consumption: Option<i32>,
+    ) -> Result<Self, MissingFormatError> {
+        let file = File::open(path.as_ref())?;
+
+        // Check the header before mapping the file
+        let mut header_buf = [0u8; FILE_HEADER_SIZE];
+        BufReader::new(&file).read_exact(&mut header_buf)?;
+        MissingSeedsHeader::from_bytes(&header_buf)?;
+
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::from_bytes(mmap, expected_consumption)
+    }
+
+    /// Open the missing seeds file of a table, bound to its chains
+    ///
+    /// See `load_missing_seeds_for_mapped_table`.
+    pub fn open_for_mapped_table<T: AsRef<[u8]>>(
+        path: impl AsRef<Path>,
+        table: &SingleTableView<T>,
+    ) -> Result<Self, MissingFormatError> {
+        let missing = Self::open(path, Some(table.header().consumption))?;
+        missing.verify_source(&table.bound_header())?;
+        Ok(missing)
+    }
+}
+
+impl<'a> MissingSeedsView<&'a [u8]> {
+    /// View the missing seeds stored in a sectioned table file
+    ///
+    /// Returns `None` if the table has no missing seeds section. The seeds are
+    /// bound to the checksum of the chain data section, computed from the
+    /// data (see `SingleTableView::bound_header`).
+    pub fn embedded<T: AsRef<[u8]>>(
+        table: &'a SingleTableView<T>,
+    ) -> Result<Option<Self>, MissingFormatError> {
+        let Some(bytes) = table.section(SectionKind::MissingSeeds) else {
+            return Ok(None);
+        };
+        let missing = Self::from_bytes(bytes, Some(table.header().consumption))?;
+        missing.verify_source(&TableHeader {
+            chains_checksum: Some(table.chains_data_checksum()),
+            ..*table.header()
+        })?;
+        Ok(Some(missing))
+    }
+}
+
+impl<B: AsRef<[u8]>> MissingSeedsView<B> {
+    /// Create a view of a missing seeds file held in `bytes`
+    ///
+    /// Runs the same header validation and size checks as loading a file.
+    pub fn from_bytes(
+        bytes: B,
+        expected_consumption: Option<i32>,
+    ) -> Result<Self, MissingFormatError> {
+        let data = bytes.as_ref();
+        let size = data.len() as u64;
+        let header_buf =
+            data.get(..FILE_HEADER_SIZE)
+                .ok_or(MissingFormatError::InvalidFileSize {
+                    expected: FILE_HEADER_SIZE as u64,
+                    found: size,
+                })?;
+        let header = MissingSeedsHeader::from_bytes(header_buf.try_into().unwrap())?;
+
+        if let Some(expected) = expected_consumption
+            && header.consumption != expected
+        {
+            return Err(MissingFormatError::ConsumptionMismatch {
+                expected,
+                found: header.consumption,
+            });
+        }
+
+        if header.is_compressed() {
+            MissingBlocksRef::new(&data[FILE_HEADER_SIZE..])
+                .ok_or(MissingFormatError::InvalidBlocks)?;
+        } else {
+            let expected_size = expected_missing_file_size(&header);
+            if size != expected_size {
+                return Err(MissingFormatError::InvalidFileSize {
+                    expected: expected_size,
+                    found: size,
+                });
+            }
+        }
+
+        Ok(Self { header, bytes })
+    }
+
+    /// Borrow the view (e.g. to handle files and embedded seeds alike)
+    pub fn as_view(&self) -> MissingSeedsView<&[u8]> {
+        MissingSeedsView {
+            header: self.header,
+            bytes: self.bytes.as_ref(),
+        }
+    }
+
+    /// Get the header
+    pub fn header(&self) -> &MissingSeedsHeader {
+        &self.header
+    }
+
+    /// Verify that the file was extracted from the given table (see `calculate_source_checksum`)
+    pub fn verify_source(&self, table_header: &TableHeader) -> Result<(), MissingFormatError> {
+        self.header.verify_source(table_header)
+    }
+
+    /// Number of missing seeds (as recorded in the header)
+    pub fn len(&self) -> usize {
+        self.header.missing_count as usize
+    }
+
+    /// Check if there are no missing seeds
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn seed_data(&self) -> SeedData<'_> {
+        let data = &self.bytes.as_ref()[FILE_HEADER_SIZE..];
+        if self.header.is_compressed() {
+            // Checked in from_bytes
+            SeedData::Blocks(MissingBlocksRef::new(data).unwrap())
+        } else {
+            SeedData::Raw(data)
+        }
+    }
+
+    /// Check if `seed` is listed
+    pub fn contains(&self, seed: u32) -> bool {
+        match self.seed_data() {
+            SeedData::Raw(data) => {
+                let index = raw_lower_bound(data, seed as u64);
+                index < data.len() / 4 && raw_seed(data, index) == seed
+            }
+            SeedData::Blocks(blocks) => blocks.contains(seed),
+        }
+    }
+
+    /// Iterate over all seeds in file order
+    pub fn iter(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        match self.seed_data() {
+            SeedData::Raw(data) => Box::new((0..data.len() / 4).map(move |i| raw_seed(data, i))),
+            SeedData::Blocks(blocks) => Box::new(blocks.iter()),
+        }
+    }
+
+    /// Iterate over the seeds within `range` in ascending order
+    pub fn range(&self, range: impl RangeBounds<u32>) -> Box<dyn Iterator<Item = u32> + '_> {
+        match self.seed_data() {
+            SeedData::Raw(data) => {
+                let start = match range.start_bound() {
+                    Bound::Included(&s) => s as u64,
+                    Bound::Excluded(&s) => s as u64 + 1,
+                    Bound::Unbounded => 0,
+                };
+                let end = match range.end_bound() {
+                    Bound::Included(&e) => e as u64 + 1,
+                    Bound::Excluded(&e) => e as u64,
+                    Bound::Unbounded => 1 << 32,
+                };
+                let first = raw_lower_bound(data, start);
+                let last = raw_lower_bound(data, end).max(first);
+                Box::new((first..last).map(move |i| raw_seed(data, i)))
+            }
+            SeedData::Blocks(blocks) => Box::new(blocks.range(range)),
+        }
+    }
+
+    /// Decode the seeds in chunks, in parallel
+    ///
+    /// Chunks are yielded in file order: blocks of 2^16 seeds of the seed
+    /// space for the compressed format, runs of 2^16 listed seeds otherwise.
+    pub fn par_chunks(&self) -> impl IndexedParallelIterator<Item = Vec<u32>> + '_ {
+        let data = self.seed_data();
+        let chunk_count = match data {
+            SeedData::Raw(raw) => (raw.len() / 4).div_ceil(SEED_CHUNK_SIZE),
+            SeedData::Blocks(_) => MISSING_BLOCK_COUNT,
+        };
+        (0..chunk_count)
+            .into_par_iter()
+            .map(move |chunk| match data {
+                SeedData::Raw(raw) => {
+                    let start = chunk * SEED_CHUNK_SIZE;
+                    let end = (start + SEED_CHUNK_SIZE).min(raw.len() / 4);
+                    (start..end).map(|i| raw_seed(raw, i)).collect()
+                }
+                SeedData::Blocks(blocks) => blocks.block_seeds(chunk as u32).collect(),
+            })
+    }
+
+    /// Decode all seeds into a vector
+    pub fn to_vec(&self) -> Vec<u32> {
+        self.par_chunks().flatten_iter().collect()
+    }
+}
+
+#[inline]
+fn raw_seed(data: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(data[index * 4..index * 4 + 4].try_into().unwrap())
+}
+
+/// Index of the first raw seed not below `value`
+fn raw_lower_bound(data: &[u8], value: u64) -> usize {
+    let (mut lo, mut hi) = (0, data.len() / 4);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if (raw_seed(data, mid) as u64) < value {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Missing seeds that can be scanned in parallel
+///
+/// Implemented by seed slices and by `MissingSeedsView`, so the missing
+/// seeds search can stream a memory-mapped file.
+pub trait MissingSeedSource: Sync {
+    /// Number of seeds
+    fn seed_count(&self) -> usize;
+
+    /// Apply `f` to consecutive chunks of seeds in parallel and concatenate
+    /// the results in seed order
+    fn flat_map_chunks<T, F>(&self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&[u32]) -> Vec<T> + Sync + Send;
+}
+
+impl MissingSeedSource for [u32] {
+    fn seed_count(&self) -> usize {
+        self.len()
+    }
+
+    fn flat_map_chunks<T, F>(&self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&[u32]) -> Vec<T> + Sync + Send,
+    {
+        self.par_chunks(SEED_CHUNK_SIZE).flat_map_iter(f).collect()
+    }
+}
+
+impl<const N: usize> MissingSeedSource for [u32; N] {
+    fn seed_count(&self) -> usize {
+        N
+    }
+
+    fn flat_map_chunks<T, F>(&self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&[u32]) -> Vec<T> + Sync + Send,
+    {
+        self.as_slice().flat_map_chunks(f)
+    }
+}
+
+impl MissingSeedSource for Vec<u32> {
+    fn seed_count(&self) -> usize {
+        self.len()
+    }
+
+    fn flat_map_chunks<T, F>(&self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&[u32]) -> Vec<T> + Sync + Send,
+    {
+        self.as_slice().flat_map_chunks(f)
+    }
+}
+
+impl<B: AsRef<[u8]> + Sync> MissingSeedSource for MissingSeedsView<B> {
+    fn seed_count(&self) -> usize {
+        self.len()
+    }
+
+    fn flat_map_chunks<T, F>(&self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&[u32]) -> Vec<T> + Sync + Send,
+    {
+        self.par_chunks().flat_map_iter(|chunk| f(&chunk)).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::table_format::TableHeader;
+    use crate::infra::table_io::MappedSingleTable;
     use std::fs;
 
     fn create_temp_file(name: &str) -> std::path::PathBuf {
@@ -143,6 +616,27 @@ mod tests {
         fs::remove_file(path).ok();
     }
 
+    #[test]
+    fn test_load_missing_seeds_for_table() {
+        let path = create_temp_file("test_missing_for_table.g7ms");
+        let table_header = TableHeader::new(417, true);
+        let seeds = vec![3u32, 5, 7];
+
+        save_missing_seeds(&path, &table_header, &seeds).unwrap();
+        let loaded = load_missing_seeds_for_table(&path, &table_header).unwrap();
+        assert_eq!(seeds, loaded);
+
+        let mut other_header = table_header;
+        other_header.created_at += 1;
+        let result = load_missing_seeds_for_table(&path, &other_header);
+        assert!(matches!(
+            result,
+            Err(MissingFormatError::SourceMismatch { .. })
+        ));
+
+        fs::remove_file(path).ok();
+    }
+
     #[test]
     fn test_get_missing_seeds_path() {
         assert_eq!(
@@ -154,4 +648,256 @@ mod tests {
             PathBuf::from("tables").join("100.g7ms")
         );
     }
+
+    #[test]
+    fn test_missing_seeds_checksum() {
+        let path = create_temp_file("test_missing_checksum.g7ms");
+        let table_header = TableHeader::new(417, true);
+        save_missing_seeds(&path, &table_header, &[5u32, 6, 7]).unwrap();
+
+        let (header, _) = load_missing_seeds(&path, Some(417)).unwrap();
+        let checksum = header.checksum.expect("checksum should be recorded");
+        assert_eq!(
+            verify_missing_seeds_file(&path),
+            Ok(ChecksumStatus::Verified(checksum))
+        );
+
+        let mut data = fs::read(&path).unwrap();
+        data[FILE_HEADER_SIZE + 4] ^= 0x10;
+        fs::write(&path, &data).unwrap();
+        assert!(matches!(
+            verify_missing_seeds_file(&path),
+            Err(MissingFormatError::ChecksumMismatch { .. })
+        ));
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_missing_seeds_bound_to_table_chains() {
+        use crate::domain::chain::ChainEntry;
+        use crate::domain::table_format::{TableParams, ValidationOptions};
+        use crate::infra::table_io::save_single_table_with_header;
+
+        let params = TableParams::new(32, 4, 2);
+        let tables: Vec<Vec<ChainEntry>> = (0..2)
+            .map(|table_id| (0..4).map(|seed| ChainEntry::new(seed, table_id)).collect())
+            .collect();
+        let table_path = create_temp_file("test_missing_binding.g7rt");
+        let missing_path = create_temp_file("test_missing_binding.g7ms");
+        let options = ValidationOptions::for_search(417);
+
+        let mut header = TableHeader::with_params(417, params, true);
+        header.created_at = 1;
+        save_single_table_with_header(&table_path, &header, &tables, None).unwrap();
+        let table = MappedSingleTable::open(&table_path, &options).unwrap();
+        save_missing_seeds(&missing_path, table.header(), &[9u32]).unwrap();
+        drop(table);
+
+        // The same tables created at another time accept the missing seeds file
+        header.created_at = 2;
+        save_single_table_with_header(&table_path, &header, &tables, None).unwrap();
+        let table = MappedSingleTable::open(&table_path, &options).unwrap();
+        assert_eq!(
+            load_missing_seeds_for_mapped_table(&missing_path, &table).unwrap(),
+            vec![9]
+        );
+        drop(table);
+
+        // So do the same chains saved with end hashes
+        let end_hashes = vec![vec![0u64; 4]; 2];
+        save_single_table_with_header(&table_path, &header, &tables, Some(&end_hashes)).unwrap();
+        let table = MappedSingleTable::open(&table_path, &options).unwrap();
+        assert_eq!(
+            load_missing_seeds_for_mapped_table(&missing_path, &table).unwrap(),
+            vec![9]
+        );
+        drop(table);
+
+        // Other chains do not
+        let mut other = tables.clone();
+        other[1][3].start_seed ^= 1;
+        save_single_table_with_header(&table_path, &header, &other, None).unwrap();
+        let table = MappedSingleTable::open(&table_path, &options).unwrap();
+        assert!(matches!(
+            load_missing_seeds_for_mapped_table(&missing_path, &table),
+            Err(MissingFormatError::SourceMismatch { .. })
+        ));
+        drop(table);
+
+        // Nor do corrupted chains with an intact header
+        save_single_table_with_header(&table_path, &header, &tables, None).unwrap();
+        let mut data = fs::read(&table_path).unwrap();
+        data[FILE_HEADER_SIZE] ^= 0x01;
+        fs::write(&table_path, &data).unwrap();
+        let table = MappedSingleTable::open(&table_path, &options).unwrap();
+        assert!(matches!(
+            load_missing_seeds_for_mapped_table(&missing_path, &table),
+            Err(MissingFormatError::SourceMismatch { .. })
+        ));
+        assert!(matches!(
+            MissingSeeds::open_for_mapped_table(&missing_path, &table),
+            Err(MissingFormatError::SourceMismatch { .. })
+        ));
+        drop(table);
+
+        fs::remove_file(table_path).ok();
+        fs::remove_file(missing_path).ok();
+    }
+
+    fn sample_seeds() -> Vec<u32> {
+        let mut seeds: Vec<u32> = (0..5000u32)
+            .map(|i| i.wrapping_mul(2_654_435_761))
+            .collect();
+        // A dense block stored as a bitmap
+        seeds.extend((0..30_000).map(|i| (9 << 16) | (i * 2)));
+        seeds.sort_unstable();
+        seeds.dedup();
+        seeds
+    }
+
+    #[test]
+    fn test_compressed_save_and_load() {
+        let path = create_temp_file("test_missing_compressed.g7ms");
+        let table_header = TableHeader::new(417, true);
+        let seeds = sample_seeds();
+
+        save_missing_seeds_compressed(&path, &table_header, &seeds).unwrap();
+        let (header, loaded) = load_missing_seeds(&path, Some(417)).unwrap();
+        assert!(header.is_compressed());
+        assert_eq!(header.missing_count, seeds.len() as u64);
+        assert_eq!(loaded, seeds);
+        assert!(matches!(
+            verify_missing_seeds_file(&path),
+            Ok(ChecksumStatus::Verified(_))
+        ));
+
+        // Unsorted input is sorted before encoding
+        let mut shuffled = seeds.clone();
+        shuffled.reverse();
+        save_missing_seeds_compressed(&path, &table_header, &shuffled).unwrap();
+        assert_eq!(load_missing_seeds(&path, Some(417)).unwrap().1, seeds);
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_missing_seeds_view_formats() {
+        let table_header = TableHeader::new(417, true);
+        let seeds = sample_seeds();
+        let raw_path = create_temp_file("test_missing_view_raw.g7ms");
+        let compressed_path = create_temp_file("test_missing_view_compressed.g7ms");
+        save_missing_seeds(&raw_path, &table_header, &seeds).unwrap();
+        save_missing_seeds_compressed(&compressed_path, &table_header, &seeds).unwrap();
+
+        for path in [&raw_path, &compressed_path] {
+            let view = MissingSeeds::open(path, Some(417)).unwrap();
+            assert_eq!(view.len(), seeds.len());
+            assert_eq!(view.to_vec(), seeds);
+            assert_eq!(view.iter().collect::<Vec<_>>(), seeds);
+
+            for &seed in seeds.iter().step_by(97) {
+                assert!(view.contains(seed));
+            }
+            assert!(!view.contains((9 << 16) | 1));
+
+            let range = (9 << 16) + 10..(10 << 16) + 5;
+            let expected: Vec<u32> = seeds
+                .iter()
+                .copied()
+                .filter(|s| range.contains(s))
+                .collect();
+            assert_eq!(view.range(range).collect::<Vec<_>>(), expected);
+
+            let chunks: Vec<Vec<u32>> = view.par_chunks().collect();
+            assert_eq!(chunks.concat(), seeds);
+        }
+
+        assert!(matches!(
+            MissingSeeds::open(&compressed_path, Some(477)),
+            Err(MissingFormatError::ConsumptionMismatch { .. })
+        ));
+
+        fs::remove_file(raw_path).ok();
+        fs::remove_file(compressed_path).ok();
+    }
+
+    #[test]
+    fn test_missing_seeds_view_from_bytes() {
+        let table_header = TableHeader::new(417, true);
+        let seeds = sample_seeds();
+        let mut header = MissingSeedsHeader::new(&table_header, seeds.len() as u64);
+        header.set_compressed(true);
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend(encode_missing_blocks(&seeds));
+
+        let view = MissingSeedsView::from_bytes(bytes.as_slice(), None).unwrap();
+        assert!(view.contains(seeds[10]));
+        assert!(view.verify_source(&table_header).is_ok());
+
+        assert!(matches!(
+            MissingSeedsView::from_bytes(&bytes[..bytes.len() - 1], None),
+            Err(MissingFormatError::InvalidBlocks)
+        ));
+        assert!(matches!(
+            MissingSeedsView::from_bytes(&bytes[..10], None),
+            Err(MissingFormatError::InvalidFileSize { .. })
+        ));
+    }
+
+    #[test]
+    fn test_embedded_missing_seeds() {
+        use crate::domain::chain::ChainEntry;
+        use crate::domain::table_format::{TableParams, ValidationOptions};
+        use crate::infra::table_io::{TableSections, write_sectioned_table};
+
+        let params = TableParams::new(16, 4, 2);
+        let tables: Vec<Vec<ChainEntry>> = (0..2)
+            .map(|table_id| (0..4).map(|seed| ChainEntry::new(seed, table_id)).collect())
+            .collect();
+        let header = TableHeader::with_params(417, params, true);
+        let seeds = sample_seeds();
+        let options = ValidationOptions::for_search(417);
+
+        for compressed in [false, true] {
+            let sections = TableSections::default().with_missing_seeds(&seeds, compressed);
+            let mut cursor = std::io::Cursor::new(Vec::new());
+            write_sectioned_table(&mut cursor, &header, &tables, &sections).unwrap();
+            let bytes = cursor.into_inner();
+
+            let table = SingleTableView::from_bytes(bytes.as_slice(), &options).unwrap();
+            let missing = MissingSeedsView::embedded(&table).unwrap().unwrap();
+            assert_eq!(missing.header().is_compressed(), compressed);
+            assert_eq!(missing.to_vec(), seeds);
+            assert!(missing.contains(seeds[100]));
+
+            // Corrupted chains reject the seeds although the header is intact
+            let mut corrupted = bytes.clone();
+            corrupted[table.sections()[0].offset as usize] ^= 1;
+            let corrupted_table =
+                SingleTableView::from_bytes(corrupted.as_slice(), &options).unwrap();
+            assert!(matches!(
+                MissingSeedsView::embedded(&corrupted_table),
+                Err(MissingFormatError::SourceMismatch { .. })
+            ));
+
+            // The seeds are bound to the chain data they were stored with
+            let mut other = tables.clone();
+            other[1][0].end_seed += 1;
+            let mut cursor = std::io::Cursor::new(Vec::new());
+            write_sectioned_table(&mut cursor, &header, &other, &TableSections::default()).unwrap();
+            let other_bytes = cursor.into_inner();
+            let other_table =
+                SingleTableView::from_bytes(other_bytes.as_slice(), &options).unwrap();
+            assert!(MissingSeedsView::embedded(&other_table).unwrap().is_none());
+            let other_header = TableHeader {
+                checksum: Some(other_table.sections()[0].checksum),
+                ..*other_table.header()
+            };
+            assert!(matches!(
+                missing.verify_source(&other_header),
+                Err(MissingFormatError::SourceMismatch { .. })
+            ));
+        }
+    }
 }
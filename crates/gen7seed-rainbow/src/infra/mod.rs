@@ -2,6 +2,8 @@
 //!
 //! This module handles file operations and other external dependencies.
 
+pub mod checkpoint_io;
 pub mod missing_seeds_io;
+pub mod original_io;
 pub mod table_io;
 pub mod table_sort;
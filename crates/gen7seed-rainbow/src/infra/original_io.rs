@@ -0,0 +1,70 @@
+//! Original table file I/O operations
+//!
+//! Reads and writes tables in the headerless layout of fujidig/sfmt-rainbow
+//! (see `domain::original_format`).
+
+use crate::constants::CHAIN_ENTRY_SIZE;
+use crate::domain::chain::ChainEntry;
+use crate::domain::table_format::TableFormatError;
+use crate::infra::table_io::{read_entries, write_atomic, write_entries};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Load a table file of the original implementation
+///
+/// The file must hold whole `(start_seed, end_seed)` pairs.
+pub fn load_original_table(path: impl AsRef<Path>) -> Result<Vec<ChainEntry>, TableFormatError> {
+    let file = File::open(path.as_ref())?;
+    let size = file.metadata()?.len();
+    if size % CHAIN_ENTRY_SIZE as u64 != 0 {
+        return Err(TableFormatError::InvalidFileSize {
+            expected: size - size % CHAIN_ENTRY_SIZE as u64,
+            found: size,
+        });
+    }
+
+    let mut reader = BufReader::new(file);
+    Ok(read_entries(
+        &mut reader,
+        (size / CHAIN_ENTRY_SIZE as u64) as usize,
+    )?)
+}
+
+/// Save a table in the layout of the original implementation
+///
+/// The file is written atomically (see `write_atomic`).
+pub fn save_original_table(
+    path: impl AsRef<Path>,
+    entries: &[ChainEntry],
+) -> Result<(), TableFormatError> {
+    write_atomic(path.as_ref(), |writer| write_entries(writer, entries))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_save_and_load_original_table() {
+        let path = std::env::temp_dir().join("test_original_table.bin");
+        let entries: Vec<ChainEntry> = (0..100).map(|i| ChainEntry::new(i, i * 7)).collect();
+
+        save_original_table(&path, &entries).expect("Failed to save");
+        assert_eq!(fs::metadata(&path).unwrap().len(), 800);
+        assert_eq!(load_original_table(&path).unwrap(), entries);
+
+        fs::write(&path, [0u8; 12]).unwrap();
+        assert!(matches!(
+            load_original_table(&path),
+            Err(TableFormatError::InvalidFileSize {
+                expected: 8,
+                found: 12
+            })
+        ));
+
+        fs::remove_file(path).ok();
+    }
+}
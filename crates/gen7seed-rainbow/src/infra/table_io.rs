@@ -2,14 +2,24 @@
 //!
 //! This module provides functions for reading and writing rainbow table files.
 
-use crate::constants::{CHAIN_ENTRY_SIZE, FILE_HEADER_SIZE, TABLE_FILE_EXTENSION};
-use crate::domain::chain::ChainEntry;
+use crate::constants::{CHAIN_ENTRY_SIZE, END_HASH_SIZE, FILE_HEADER_SIZE, TABLE_FILE_EXTENSION};
+use crate::domain::chain::{ChainEntry, EndHashKey, LeChainEntries, LeEndHashes, TableRef};
+use crate::domain::checksum::{Checksum, ChecksumStatus, data_checksum};
+use crate::domain::compact::{
+    COMPACT_DESCRIPTOR_SIZE, CompactTable, CompactTableDescriptor, CompactTableRef,
+};
+use crate::domain::section_format::{
+    SECTION_DIRECTORY_HEADER_SIZE, SECTION_ENTRY_SIZE, SECTION_LAYOUT_VERSION, SectionDirectory,
+    SectionEntry, SectionKind,
+};
 use crate::domain::table_format::{
-    TableFormatError, TableHeader, ValidationOptions, expected_file_size, validate_header,
+    TableFormatError, TableHeader, ValidationOptions, end_hash_section_offset,
+    expected_compact_file_size, expected_file_size, validate_header,
 };
+use crate::infra::missing_seeds_io::write_missing_seeds;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 use memmap2::Mmap;
@@ -24,6 +34,106 @@ fn ensure_parent_dir(path: &Path) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Write a file atomically
+///
+/// The contents are written to `{path}.tmp` and renamed over `path` once
+/// complete, so an interrupted write never leaves a truncated file behind.
+pub(crate) fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    ensure_parent_dir(path)?;
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let result = File::create(&tmp_path).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()
+    });
+    match result {
+        Ok(()) => fs::rename(&tmp_path, path),
+        Err(e) => {
+            fs::remove_file(&tmp_path).ok();
+            Err(e)
+        }
+    }
+}
+
+/// Writer that computes the checksum of everything written through it
+pub(crate) struct ChecksumWriter<'a> {
+    inner: &'a mut dyn Write,
+    checksum: Checksum,
+}
+
+impl Write for ChecksumWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.checksum.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Write a header followed by data, recording the data checksum in the header
+///
+/// A placeholder header is written first; once the data is written, the
+/// header returned by `header(checksum)` is written over it.
+pub(crate) fn write_with_checksum<W: Write + Seek>(
+    writer: &mut W,
+    header: impl FnOnce(u64) -> [u8; FILE_HEADER_SIZE],
+    write_data: impl FnOnce(&mut ChecksumWriter<'_>) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    writer.write_all(&[0u8; FILE_HEADER_SIZE])?;
+
+    let mut data_writer = ChecksumWriter {
+        inner: writer,
+        checksum: Checksum::new(),
+    };
+    write_data(&mut data_writer)?;
+    let checksum = data_writer.checksum.finish();
+
+    writer.seek(SeekFrom::Start(0))?;
+    writer.write_all(&header(checksum))?;
+    writer.seek(SeekFrom::End(0))?;
+    Ok(())
+}
+
+/// Reader that computes the checksum of everything read through it
+pub(crate) struct ChecksumReader<R> {
+    inner: R,
+    checksum: Checksum,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            checksum: Checksum::new(),
+        }
+    }
+
+    /// Read the rest of the data and return its checksum
+    pub(crate) fn finish(mut self) -> std::io::Result<u64> {
+        std::io::copy(&mut self, &mut std::io::sink())?;
+        Ok(self.checksum.finish())
+    }
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.checksum.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
 /// Get the file path for a single-file rainbow table
 ///
 /// Format: `{dir}/{consumption}.g7rt`
@@ -35,6 +145,10 @@ pub fn get_single_table_path(dir: impl AsRef<Path>, consumption: i32) -> PathBuf
 /// Load a single-file rainbow table with validation
 ///
 /// Returns the header and a vector of tables (each table is a Vec<ChainEntry>).
+/// The end hash section, if present, is not loaded. Compact tables have no
+/// end seeds and are rejected; open them with `MappedSingleTable`. Of
+/// sectioned files only the chain data section is loaded. If the header
+/// records a checksum, the data is verified against it while reading.
 pub fn load_single_table(
     path: impl AsRef<Path>,
     options: &ValidationOptions,
@@ -48,27 +162,37 @@ pub fn load_single_table(
 
     let header = TableHeader::from_bytes(&header_buf)?;
     validate_header(&header, options)?;
+    if header.is_compact() {
+        return Err(TableFormatError::CompactNotSupported);
+    }
 
-    let expected_size = expected_file_size(&header);
-    if metadata.len() != expected_size {
-        return Err(TableFormatError::InvalidFileSize {
-            expected: expected_size,
-            found: metadata.len(),
-        });
+    // The checksum covers everything read from the file, buffered or not
+    let mut reader = BufReader::new(ChecksumReader::new(reader));
+    if header.is_sectioned() {
+        let sections = read_section_directory(&mut reader, metadata.len())?;
+        let (chains_offset, _) = locate_table_sections(&header, &sections)?;
+        let skip = chains_offset as u64 - FILE_HEADER_SIZE as u64 - sections.byte_size();
+        std::io::copy(&mut (&mut reader).take(skip), &mut std::io::sink())?;
+    } else {
+        let expected_size = expected_file_size(&header);
+        if metadata.len() != expected_size {
+            return Err(TableFormatError::InvalidFileSize {
+                expected: expected_size,
+                found: metadata.len(),
+            });
+        }
     }
 
     let mut tables = Vec::with_capacity(header.num_tables as usize);
     for _ in 0..header.num_tables {
-        let mut entries = Vec::with_capacity(header.chains_per_table as usize);
-        for _ in 0..header.chains_per_table {
-            let start_seed = reader.read_u32::<LittleEndian>()?;
-            let end_seed = reader.read_u32::<LittleEndian>()?;
-            entries.push(ChainEntry {
-                start_seed,
-                end_seed,
-            });
+        tables.push(read_entries(&mut reader, header.chains_per_table as usize)?);
+    }
+
+    if let Some(expected) = header.checksum {
+        let found = reader.into_inner().finish()?;
+        if found != expected {
+            return Err(TableFormatError::ChecksumMismatch { expected, found });
         }
-        tables.push(entries);
     }
 
     Ok((header, tables))
@@ -76,6 +200,8 @@ pub fn load_single_table(
 
 /// Save tables to a single file with header
 ///
+/// The file is written atomically (see `write_atomic`).
+///
 /// # Arguments
 /// * `path` - Output file path
 /// * `consumption` - RNG consumption value
@@ -87,9 +213,75 @@ pub fn save_single_table(
     tables: &[Vec<ChainEntry>],
     sorted: bool,
 ) -> Result<(), TableFormatError> {
-    ensure_parent_dir(path.as_ref())?;
-
     let header = TableHeader::new(consumption, sorted);
+    save_single_table_with_header(path, &header, tables, None)
+}
+
+/// Save sorted tables together with the end hash of every chain
+///
+/// Search uses the stored end hashes instead of recomputing them, at the cost
+/// of 8 more bytes per chain.
+///
+/// # Arguments
+/// * `path` - Output file path
+/// * `consumption` - RNG consumption value
+/// * `tables` - Vector of sorted tables (see `sort_table_with_end_hashes`)
+/// * `end_hashes` - End hashes of each table, in the same order as its chains
+pub fn save_single_table_with_end_hashes(
+    path: impl AsRef<Path>,
+    consumption: i32,
+    tables: &[Vec<ChainEntry>],
+    end_hashes: &[Vec<u64>],
+) -> Result<(), TableFormatError> {
+    let mut header = TableHeader::new(consumption, true);
+    header.set_end_hashes(true);
+    save_single_table_with_header(path, &header, tables, Some(end_hashes))
+}
+
+/// Save tables with the given header
+///
+/// Use this for tables generated with non-default `TableParams`. The table
+/// and chain counts must match the header, and `end_hashes` must be given if
+/// and only if the header has the end hashes flag. The file is written
+/// atomically (see `write_atomic`).
+pub fn save_single_table_with_header(
+    path: impl AsRef<Path>,
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    end_hashes: Option<&[Vec<u64>]>,
+) -> Result<(), TableFormatError> {
+    let header = check_single_table(header, tables, end_hashes)?;
+    write_atomic(path.as_ref(), |writer| {
+        write_single_table_data(writer, &header, tables, end_hashes)
+    })?;
+    Ok(())
+}
+
+/// Write tables with the given header to any seekable writer
+///
+/// Produces the same bytes as `save_single_table_with_header`, e.g. into a
+/// `Cursor<Vec<u8>>` to be read back with `SingleTableView::from_bytes`.
+pub fn write_single_table<W: Write + Seek>(
+    writer: &mut W,
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    end_hashes: Option<&[Vec<u64>]>,
+) -> Result<(), TableFormatError> {
+    let header = check_single_table(header, tables, end_hashes)?;
+    write_single_table_data(writer, &header, tables, end_hashes)?;
+    Ok(())
+}
+
+/// Check the table and chain counts and return the header to write
+fn check_single_table(
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    end_hashes: Option<&[Vec<u64>]>,
+) -> Result<TableHeader, TableFormatError> {
+    header.params().validate()?;
+    let mut header = *header;
+    header.set_compact(false);
+    header.set_end_hashes(end_hashes.is_some());
 
     if tables.len() != header.num_tables as usize {
         return Err(TableFormatError::TableCountMismatch {
@@ -105,33 +297,545 @@ pub fn save_single_table(
             });
         }
     }
+    if let Some(end_hashes) = end_hashes {
+        if end_hashes.len() != tables.len() {
+            return Err(TableFormatError::TableCountMismatch {
+                expected: header.num_tables,
+                found: end_hashes.len() as u32,
+            });
+        }
+        for hashes in end_hashes {
+            if hashes.len() != header.chains_per_table as usize {
+                return Err(TableFormatError::ChainCountMismatch {
+                    expected: header.chains_per_table,
+                    found: hashes.len() as u32,
+                });
+            }
+        }
+    }
 
-    let file = File::create(path)?;
-    let mut writer = BufWriter::new(file);
+    Ok(header)
+}
 
-    writer.write_all(&header.to_bytes())?;
+fn write_single_table_data<W: Write + Seek>(
+    writer: &mut W,
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    end_hashes: Option<&[Vec<u64>]>,
+) -> std::io::Result<()> {
+    let chains_checksum = stream_checksum(|writer| {
+        tables
+            .iter()
+            .try_for_each(|table| write_entries(writer, table))
+    })?;
+    write_with_checksum(
+        writer,
+        |checksum| {
+            TableHeader {
+                checksum: Some(checksum),
+                chains_checksum: Some(chains_checksum),
+                ..*header
+            }
+            .to_bytes()
+        },
+        |writer| {
+            for table in tables {
+                write_entries(writer, table)?;
+            }
+            for hashes in end_hashes.into_iter().flatten() {
+                write_end_hashes(writer, hashes)?;
+            }
+            Ok(())
+        },
+    )
+}
 
-    for table in tables {
-        for entry in table {
-            writer.write_u32::<LittleEndian>(entry.start_seed)?;
-            writer.write_u32::<LittleEndian>(entry.end_seed)?;
+/// Optional sections of a sectioned table file
+///
+/// The chain data is always written; everything here is stored in sections
+/// of its own after it (see `domain::section_format`).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TableSections<'a> {
+    /// End hashes of each table, in the same order as its chains (default: None)
+    pub end_hashes: Option<&'a [Vec<u64>]>,
+    /// Missing seeds of the tables (default: None)
+    pub missing_seeds: Option<&'a [u32]>,
+    /// Store the missing seeds in the compressed format (default: false)
+    pub compress_missing_seeds: bool,
+    /// Free-form metadata (default: None)
+    pub metadata: Option<&'a [u8]>,
+    /// Generation checkpoint data (default: None)
+    pub checkpoint: Option<&'a [u8]>,
+}
+
+impl<'a> TableSections<'a> {
+    /// Store the end hashes of each table
+    pub fn with_end_hashes(mut self, end_hashes: &'a [Vec<u64>]) -> Self {
+        self.end_hashes = Some(end_hashes);
+        self
+    }
+
+    /// Store the missing seeds of the tables
+    ///
+    /// Read them back with `MissingSeedsView::embedded`.
+    pub fn with_missing_seeds(mut self, seeds: &'a [u32], compressed: bool) -> Self {
+        self.missing_seeds = Some(seeds);
+        self.compress_missing_seeds = compressed;
+        self
+    }
+
+    /// Store free-form metadata
+    pub fn with_metadata(mut self, metadata: &'a [u8]) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Store generation checkpoint data
+    pub fn with_checkpoint(mut self, checkpoint: &'a [u8]) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+}
+
+/// Save tables in the sectioned format
+///
+/// Writes the header, a section directory and one section each for the
+/// chains and the optional `sections`. The embedded missing seeds are bound
+/// to the checksum of the chain data section. Readers before the sectioned
+/// format reject the file with `UnsupportedVersion`. The file is written
+/// atomically (see `write_atomic`).
+pub fn save_sectioned_table(
+    path: impl AsRef<Path>,
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    sections: &TableSections<'_>,
+) -> Result<(), TableFormatError> {
+    let mut header = check_single_table(header, tables, sections.end_hashes)?;
+    header.set_sectioned(true);
+    write_atomic(path.as_ref(), |writer| {
+        write_sectioned_table_data(writer, &header, tables, sections)
+    })?;
+    Ok(())
+}
+
+/// Write tables in the sectioned format to any seekable writer
+///
+/// Produces the same bytes as `save_sectioned_table`.
+pub fn write_sectioned_table<W: Write + Seek>(
+    writer: &mut W,
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    sections: &TableSections<'_>,
+) -> Result<(), TableFormatError> {
+    let mut header = check_single_table(header, tables, sections.end_hashes)?;
+    header.set_sectioned(true);
+    write_sectioned_table_data(writer, &header, tables, sections)?;
+    Ok(())
+}
+
+fn write_sectioned_table_data<W: Write + Seek>(
+    writer: &mut W,
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    sections: &TableSections<'_>,
+) -> std::io::Result<()> {
+    let write_chains = |mut writer: &mut dyn Write| {
+        tables
+            .iter()
+            .try_for_each(|table| write_entries(&mut writer, table))
+    };
+    let write_hashes = |mut writer: &mut dyn Write| {
+        sections
+            .end_hashes
+            .into_iter()
+            .flatten()
+            .try_for_each(|hashes| write_end_hashes(&mut writer, hashes))
+    };
+
+    // Checksums of the sections are recorded in the directory before them
+    let num_chains = header.params().total_chains();
+    let chains_checksum = stream_checksum(|writer| write_chains(writer))?;
+    let mut layout = vec![(
+        SectionKind::ChainData,
+        SECTION_LAYOUT_VERSION,
+        num_chains * CHAIN_ENTRY_SIZE as u64,
+        chains_checksum,
+    )];
+    if sections.end_hashes.is_some() {
+        layout.push((
+            SectionKind::KeyCache,
+            SECTION_LAYOUT_VERSION,
+            num_chains * END_HASH_SIZE as u64,
+            stream_checksum(|writer| write_hashes(writer))?,
+        ));
+    }
+
+    let mut missing = Vec::new();
+    if let Some(seeds) = sections.missing_seeds {
+        let source = TableHeader {
+            chains_checksum: Some(chains_checksum),
+            ..*header
+        };
+        let mut cursor = Cursor::new(Vec::new());
+        write_missing_seeds(&mut cursor, &source, seeds, sections.compress_missing_seeds)?;
+        missing = cursor.into_inner();
+    }
+    let mut blobs: Vec<(SectionKind, &[u8])> = Vec::new();
+    if sections.missing_seeds.is_some() {
+        blobs.push((SectionKind::MissingSeeds, &missing));
+    }
+    blobs.extend(sections.metadata.map(|data| (SectionKind::Metadata, data)));
+    blobs.extend(
+        sections
+            .checkpoint
+            .map(|data| (SectionKind::Checkpoint, data)),
+    );
+    for &(kind, data) in &blobs {
+        layout.push((
+            kind,
+            SECTION_LAYOUT_VERSION,
+            data.len() as u64,
+            data_checksum(data),
+        ));
+    }
+
+    let directory = SectionDirectory::layout(&layout);
+    write_with_checksum(
+        writer,
+        |checksum| {
+            TableHeader {
+                checksum: Some(checksum),
+                chains_checksum: Some(chains_checksum),
+                ..*header
+            }
+            .to_bytes()
+        },
+        |writer| {
+            writer.write_all(&directory.to_bytes())?;
+            let mut position = FILE_HEADER_SIZE as u64 + directory.byte_size();
+            for entry in directory.entries() {
+                let padding = entry.offset - position;
+                writer.write_all(&vec![0u8; padding as usize])?;
+                match entry.kind {
+                    SectionKind::ChainData => write_chains(writer)?,
+                    SectionKind::KeyCache => write_hashes(writer)?,
+                    kind => {
+                        let &(_, data) = blobs.iter().find(|&&(k, _)| k == kind).unwrap();
+                        writer.write_all(data)?;
+                    }
+                }
+                position = entry.end();
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Compute the checksum of the data written by `write`, discarding the data
+fn stream_checksum(
+    write: impl FnOnce(&mut ChecksumWriter<'_>) -> std::io::Result<()>,
+) -> std::io::Result<u64> {
+    let mut sink = std::io::sink();
+    let mut writer = ChecksumWriter {
+        inner: &mut sink,
+        checksum: Checksum::new(),
+    };
+    write(&mut writer)?;
+    Ok(writer.checksum.finish())
+}
+
+/// Save sorted tables in the compact format
+///
+/// Stores the bit-packed start seeds and the Elias–Fano coded end hashes of
+/// every table (see `domain::compact`) instead of chain entries. Compact
+/// tables can only be searched through `SingleTableView`. The table and
+/// chain counts must match the header, and `end_hashes` are the full end
+/// hashes of each table in ascending order (see `sort_table_with_end_hashes`).
+/// The file is written atomically (see `write_atomic`).
+pub fn save_compact_table(
+    path: impl AsRef<Path>,
+    header: &TableHeader,
+    tables: &[Vec<ChainEntry>],
+    end_hashes: &[Vec<u64>],
+) -> Result<(), TableFormatError> {
+    header.params().validate()?;
+    let mut header = *header;
+    header.set_compact(true);
+    header.set_sorted(true);
+    header.set_end_hashes(false);
+
+    if tables.len() != header.num_tables as usize {
+        return Err(TableFormatError::TableCountMismatch {
+            expected: header.num_tables,
+            found: tables.len() as u32,
+        });
+    }
+    if end_hashes.len() != tables.len() {
+        return Err(TableFormatError::TableCountMismatch {
+            expected: header.num_tables,
+            found: end_hashes.len() as u32,
+        });
+    }
+    for (table, hashes) in tables.iter().zip(end_hashes) {
+        for count in [table.len(), hashes.len()] {
+            if count != header.chains_per_table as usize {
+                return Err(TableFormatError::ChainCountMismatch {
+                    expected: header.chains_per_table,
+                    found: count as u32,
+                });
+            }
+        }
+    }
+
+    let compact: Vec<CompactTable> = tables
+        .iter()
+        .zip(end_hashes)
+        .map(|(table, hashes)| CompactTable::encode(table, hashes))
+        .collect();
+
+    write_atomic(path.as_ref(), |writer| {
+        write_with_checksum(
+            writer,
+            |checksum| {
+                TableHeader {
+                    checksum: Some(checksum),
+                    chains_checksum: None,
+                    ..header
+                }
+                .to_bytes()
+            },
+            |writer| {
+                for table in &compact {
+                    writer.write_all(&table.descriptor().to_bytes())?;
+                }
+                for table in &compact {
+                    writer.write_all(&table.to_le_bytes())?;
+                }
+                Ok(())
+            },
+        )
+    })?;
+    Ok(())
+}
+
+/// Verify the checksum of a table file
+///
+/// Streams the data after the header and compares its checksum with the one
+/// recorded in the header. The header itself is not validated against any
+/// options; use this to check a downloaded file before relying on it.
+pub fn verify_table_file(path: impl AsRef<Path>) -> Result<ChecksumStatus, TableFormatError> {
+    let file = File::open(path.as_ref())?;
+    let metadata = file.metadata()?;
+
+    let mut reader = BufReader::new(file);
+    let mut header_buf = [0u8; FILE_HEADER_SIZE];
+    reader.read_exact(&mut header_buf)?;
+    let header = TableHeader::from_bytes(&header_buf)?;
+
+    // Compact and sectioned files are sized by their descriptors or section
+    // directory, which the checksum covers
+    if !header.is_compact() && !header.is_sectioned() {
+        let expected_size = expected_file_size(&header);
+        if metadata.len() != expected_size {
+            return Err(TableFormatError::InvalidFileSize {
+                expected: expected_size,
+                found: metadata.len(),
+            });
         }
     }
 
-    writer.flush()?;
+    let Some(expected) = header.checksum else {
+        return Ok(ChecksumStatus::NotRecorded);
+    };
+    let found = ChecksumReader::new(reader).finish()?;
+    if found != expected {
+        return Err(TableFormatError::ChecksumMismatch { expected, found });
+    }
+    Ok(ChecksumStatus::Verified(found))
+}
+
+/// Write chain entries as little-endian (start, end) pairs
+pub(crate) fn write_entries(
+    writer: &mut impl Write,
+    entries: &[ChainEntry],
+) -> std::io::Result<()> {
+    for entry in entries {
+        writer.write_u32::<LittleEndian>(entry.start_seed)?;
+        writer.write_u32::<LittleEndian>(entry.end_seed)?;
+    }
+    Ok(())
+}
+
+/// Write end hashes as little-endian u64 values
+pub(crate) fn write_end_hashes(writer: &mut impl Write, hashes: &[u64]) -> std::io::Result<()> {
+    for &hash in hashes {
+        writer.write_u64::<LittleEndian>(hash)?;
+    }
     Ok(())
 }
 
+/// Read `count` chain entries stored as little-endian (start, end) pairs
+pub(crate) fn read_entries(
+    reader: &mut impl Read,
+    count: usize,
+) -> std::io::Result<Vec<ChainEntry>> {
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        let start_seed = reader.read_u32::<LittleEndian>()?;
+        let end_seed = reader.read_u32::<LittleEndian>()?;
+        entries.push(ChainEntry {
+            start_seed,
+            end_seed,
+        });
+    }
+    Ok(entries)
+}
+
+/// Read and check the section directory following the header
+fn read_section_directory(
+    reader: &mut impl Read,
+    file_size: u64,
+) -> Result<SectionDirectory, TableFormatError> {
+    let mut buf = vec![0u8; SECTION_DIRECTORY_HEADER_SIZE];
+    reader.read_exact(&mut buf)?;
+
+    let count = SectionDirectory::section_count(buf.as_slice().try_into().unwrap());
+    let size = (SECTION_DIRECTORY_HEADER_SIZE + count as usize * SECTION_ENTRY_SIZE) as u64;
+    if FILE_HEADER_SIZE as u64 + size > file_size {
+        return Err(TableFormatError::InvalidFileSize {
+            expected: FILE_HEADER_SIZE as u64 + size,
+            found: file_size,
+        });
+    }
+
+    buf.resize(size as usize, 0);
+    reader.read_exact(&mut buf[SECTION_DIRECTORY_HEADER_SIZE..])?;
+    SectionDirectory::from_bytes(&buf, file_size)
+}
+
+/// Find the chain data and end hash sections of a sectioned file
+///
+/// Returns their byte offsets; the end hash section is required if and only
+/// if the header has the end hashes flag.
+fn locate_table_sections(
+    header: &TableHeader,
+    sections: &SectionDirectory,
+) -> Result<(usize, Option<usize>), TableFormatError> {
+    let num_chains = header.params().total_chains();
+    let locate = |kind: SectionKind, entry_size: usize| {
+        let entry = sections
+            .find(kind)
+            .ok_or(TableFormatError::MissingSection(kind))?;
+        if entry.version != SECTION_LAYOUT_VERSION || entry.length != num_chains * entry_size as u64
+        {
+            return Err(TableFormatError::UnsupportedSection {
+                kind,
+                version: entry.version,
+            });
+        }
+        Ok(entry.offset as usize)
+    };
+
+    let chains = locate(SectionKind::ChainData, CHAIN_ENTRY_SIZE)?;
+    let end_hashes = if header.has_end_hashes() {
+        Some(locate(SectionKind::KeyCache, END_HASH_SIZE)?)
+    } else {
+        None
+    };
+    Ok((chains, end_hashes))
+}
+
+/// Read and check the descriptors of a compact table file held in `data`
+///
+/// Returns the descriptor and byte offset of each table.
+fn read_compact_descriptors(
+    header: &TableHeader,
+    data: &[u8],
+) -> Result<Vec<(CompactTableDescriptor, usize)>, TableFormatError> {
+    let size = data.len() as u64;
+    let descriptors_size = header.num_tables as u64 * COMPACT_DESCRIPTOR_SIZE as u64;
+    let min_size = FILE_HEADER_SIZE as u64 + descriptors_size;
+    if size < min_size {
+        return Err(TableFormatError::InvalidFileSize {
+            expected: min_size,
+            found: size,
+        });
+    }
+
+    let mut descriptors = Vec::with_capacity(header.num_tables as usize);
+    for (table_id, buf) in data[FILE_HEADER_SIZE..min_size as usize]
+        .chunks_exact(COMPACT_DESCRIPTOR_SIZE)
+        .enumerate()
+    {
+        let descriptor = CompactTableDescriptor::from_bytes(buf.try_into().unwrap());
+        if !header.is_sorted()
+            || header.end_hash_key() != EndHashKey::Full
+            || descriptor.len != header.chains_per_table as u64
+            || !descriptor.is_consistent()
+        {
+            return Err(TableFormatError::InvalidCompactTable {
+                table_id: table_id as u32,
+            });
+        }
+        descriptors.push(descriptor);
+    }
+
+    let expected_size = expected_compact_file_size(&descriptors);
+    if size != expected_size {
+        return Err(TableFormatError::InvalidFileSize {
+            expected: expected_size,
+            found: size,
+        });
+    }
+
+    // Check that every table can be searched without reading out of bounds
+    let mut offset = min_size as usize;
+    let mut tables = Vec::with_capacity(descriptors.len());
+    for (table_id, descriptor) in descriptors.into_iter().enumerate() {
+        let end = offset + descriptor.byte_size() as usize;
+        if CompactTableRef::new(descriptor, &data[offset..end]).is_none() {
+            return Err(TableFormatError::InvalidCompactTable {
+                table_id: table_id as u32,
+            });
+        }
+        tables.push((descriptor, offset));
+        offset = end;
+    }
+    Ok(tables)
+}
+
 // =============================================================================
-// Memory-mapped single-file table
+// Single-file table views
 // =============================================================================
 
-/// Memory-mapped single-file rainbow table
-pub struct MappedSingleTable {
+/// Single-file rainbow table read in place from a byte buffer
+///
+/// The buffer is validated like a table file: the header is parsed and
+/// checked against the validation options, and the buffer size must match
+/// the header. `B` is the storage of the buffer: a memory-mapped file
+/// (`MappedSingleTable`), a borrowed `&[u8]` such as an embedded resource,
+/// or an owned `Vec<u8>`.
+///
+/// Entries and end hashes are read in place as little-endian views (see
+/// `entries` and `table_ref`). Compact tables are read through their
+/// descriptors; they have no chain entries, so `entries` returns `None` for them.
+/// Sectioned files are read through their section directory (see `sections`).
+pub struct SingleTableView<B> {
     header: TableHeader,
-    mmap: Mmap,
+    bytes: B,
+    /// Descriptor and byte offset of each compact table
+    compact: Vec<(CompactTableDescriptor, usize)>,
+    /// Section directory (empty unless the file is sectioned)
+    sections: SectionDirectory,
+    /// Byte offset of the chain entries of the first table
+    chains_offset: usize,
+    /// Byte offset of the end hashes of the first table
+    end_hashes_offset: Option<usize>,
 }
 
+/// Memory-mapped single-file rainbow table
+pub type MappedSingleTable = SingleTableView<Mmap>;
+
 impl MappedSingleTable {
     /// Open a single-file table as memory-mapped
     pub fn open(
@@ -139,28 +843,81 @@ impl MappedSingleTable {
         options: &ValidationOptions,
     ) -> Result<Self, TableFormatError> {
         let file = File::open(path.as_ref())?;
-        let metadata = file.metadata()?;
 
+        // Check the header before mapping the file
         let mut header_buf = [0u8; FILE_HEADER_SIZE];
         {
             let mut reader = BufReader::new(&file);
             reader.read_exact(&mut header_buf)?;
         }
+        validate_header(&TableHeader::from_bytes(&header_buf)?, options)?;
+
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::from_bytes(mmap, options)
+    }
+}
 
-        let header = TableHeader::from_bytes(&header_buf)?;
+impl<B: AsRef<[u8]>> SingleTableView<B> {
+    /// Create a view of a table file held in `bytes`
+    ///
+    /// Runs the same header validation and size checks as opening a file.
+    pub fn from_bytes(bytes: B, options: &ValidationOptions) -> Result<Self, TableFormatError> {
+        let data = bytes.as_ref();
+        let size = data.len() as u64;
+        let header_buf = data
+            .get(..FILE_HEADER_SIZE)
+            .ok_or(TableFormatError::InvalidFileSize {
+                expected: FILE_HEADER_SIZE as u64,
+                found: size,
+            })?;
+
+        let header = TableHeader::from_bytes(header_buf.try_into().unwrap())?;
         validate_header(&header, options)?;
 
+        if header.is_compact() {
+            let compact = read_compact_descriptors(&header, data)?;
+            return Ok(Self {
+                header,
+                bytes,
+                compact,
+                sections: SectionDirectory::default(),
+                chains_offset: 0,
+                end_hashes_offset: None,
+            });
+        }
+
+        if header.is_sectioned() {
+            let sections = SectionDirectory::from_bytes(&data[FILE_HEADER_SIZE..], size)?;
+            let (chains_offset, end_hashes_offset) = locate_table_sections(&header, &sections)?;
+            return Ok(Self {
+                header,
+                bytes,
+                compact: Vec::new(),
+                sections,
+                chains_offset,
+                end_hashes_offset,
+            });
+        }
+
         let expected_size = expected_file_size(&header);
-        if metadata.len() != expected_size {
+        if size != expected_size {
             return Err(TableFormatError::InvalidFileSize {
                 expected: expected_size,
-                found: metadata.len(),
+                found: size,
             });
         }
 
-        let mmap = unsafe { Mmap::map(&file)? };
-
-        Ok(Self { header, mmap })
+        let end_hashes_offset = header
+            .has_end_hashes()
+            .then(|| end_hash_section_offset(&header) as usize);
+        Ok(Self {
+            header,
+            bytes,
+            compact: Vec::new(),
+            sections: SectionDirectory::default(),
+            chains_offset: FILE_HEADER_SIZE,
+            end_hashes_offset,
+        })
     }
 
     /// Get the header
@@ -168,28 +925,222 @@ impl MappedSingleTable {
         &self.header
     }
 
+    /// Byte size of the file described by the header
+    ///
+    /// Compact files are sized by their table descriptors and sectioned
+    /// files by their section directory.
+    pub fn expected_size(&self) -> u64 {
+        if self.header.is_compact() {
+            let descriptors: Vec<_> = self
+                .compact
+                .iter()
+                .map(|&(descriptor, _)| descriptor)
+                .collect();
+            expected_compact_file_size(&descriptors)
+        } else if self.header.is_sectioned() {
+            self.sections.file_size()
+        } else {
+            expected_file_size(&self.header)
+        }
+    }
+
+    /// Get the underlying buffer
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    /// Consume the view and return the underlying buffer
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+
+    /// Verify the data against the checksum recorded in the header
+    ///
+    /// Reads the whole buffer once, which also pages a mapped file in before
+    /// searching.
+    pub fn verify_checksum(&self) -> Result<ChecksumStatus, TableFormatError> {
+        let Some(expected) = self.header.checksum else {
+            return Ok(ChecksumStatus::NotRecorded);
+        };
+        let found = self.data_checksum();
+        if found != expected {
+            return Err(TableFormatError::ChecksumMismatch { expected, found });
+        }
+        Ok(ChecksumStatus::Verified(found))
+    }
+
+    /// Compute the checksum of the data (the content digest of the table)
+    pub fn data_checksum(&self) -> u64 {
+        data_checksum(&self.as_bytes()[FILE_HEADER_SIZE..])
+    }
+
+    /// Compute the checksum of the chain entries (see `TableHeader::chains_checksum`)
+    ///
+    /// Compact tables have no chain entries; their data checksum is returned.
+    pub fn chains_data_checksum(&self) -> u64 {
+        if self.header.is_compact() {
+            return self.data_checksum();
+        }
+        let size = self.header.params().total_chains() as usize * CHAIN_ENTRY_SIZE;
+        data_checksum(&self.as_bytes()[self.chains_offset..self.chains_offset + size])
+    }
+
+    /// Get the header with the chain checksum computed from the data
+    ///
+    /// Missing seeds files are bound to this header (see
+    /// `calculate_source_checksum`), so they are only accepted for a table
+    /// whose chains are intact. Compact tables are bound to the checksum of
+    /// their data. Headers without any recorded checksum are returned unchanged.
+    pub fn bound_header(&self) -> TableHeader {
+        let mut header = self.header;
+        if header.is_compact() {
+            if header.checksum.is_some() {
+                header.checksum = Some(self.data_checksum());
+            }
+        } else if header.chains_checksum.or(header.checksum).is_some() {
+            header.chains_checksum = Some(self.chains_data_checksum());
+        }
+        header
+    }
+
+    /// Get the section directory entries
+    ///
+    /// Empty unless the file is sectioned.
+    pub fn sections(&self) -> &[SectionEntry] {
+        self.sections.entries()
+    }
+
+    /// Get the data of the first section of the given type
+    pub fn section(&self, kind: SectionKind) -> Option<&[u8]> {
+        let entry = self.sections.find(kind)?;
+        Some(&self.as_bytes()[entry.offset as usize..entry.end() as usize])
+    }
+
+    /// Verify every section against the checksum recorded in the directory
+    ///
+    /// Unlike `verify_checksum`, reports which section is corrupted.
+    pub fn verify_sections(&self) -> Result<(), TableFormatError> {
+        for entry in self.sections() {
+            let found =
+                data_checksum(&self.as_bytes()[entry.offset as usize..entry.end() as usize]);
+            if found != entry.checksum {
+                return Err(TableFormatError::SectionChecksumMismatch {
+                    kind: entry.kind,
+                    expected: entry.checksum,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Get a specific table as a slice
+    ///
+    /// Returns `None` for compact tables and for buffers that are not aligned
+    /// for `ChainEntry` (memory-mapped files always are). Only available on
+    /// little-endian targets; use `entries` for an endian-independent view.
     #[cfg(target_endian = "little")]
     pub fn table(&self, table_id: u32) -> Option<&[ChainEntry]> {
-        if table_id >= self.header.num_tables {
+        let bytes = self.entry_bytes(table_id)?;
+        let ptr = bytes.as_ptr() as *const ChainEntry;
+        if !ptr.is_aligned() {
             return None;
         }
 
-        let table_size = self.header.chains_per_table as usize * CHAIN_ENTRY_SIZE;
-        let offset = FILE_HEADER_SIZE + table_id as usize * table_size;
-        let end = offset + table_size;
+        Some(unsafe { std::slice::from_raw_parts(ptr, self.header.chains_per_table as usize) })
+    }
 
-        let data = &self.mmap[offset..end];
-        let ptr = data.as_ptr() as *const ChainEntry;
+    /// Get the precomputed end hashes of a specific table
+    ///
+    /// Returns `None` if the file has no end hash section or the buffer is
+    /// not aligned for `u64` (memory-mapped files always are). Only available
+    /// on little-endian targets; use `le_end_hashes` for an endian-independent view.
+    #[cfg(target_endian = "little")]
+    pub fn end_hashes(&self, table_id: u32) -> Option<&[u64]> {
+        let bytes = self.end_hash_bytes(table_id)?;
+        let ptr = bytes.as_ptr() as *const u64;
+        // The section starts at a multiple of 8 bytes and a mapping is page-aligned
+        if !ptr.is_aligned() {
+            return None;
+        }
 
         Some(unsafe { std::slice::from_raw_parts(ptr, self.header.chains_per_table as usize) })
     }
 
-    #[cfg(target_endian = "big")]
-    pub fn table(&self, _table_id: u32) -> Option<&[ChainEntry]> {
-        panic!(
-            "Big-endian platforms are not supported for memory-mapped tables. Use load_single_table() instead for non-memory-mapped access."
-        );
+    /// Get the entries of a specific table, decoded on access
+    ///
+    /// Returns `None` for compact tables.
+    pub fn entries(&self, table_id: u32) -> Option<LeChainEntries<'_>> {
+        LeChainEntries::new(self.entry_bytes(table_id)?)
+    }
+
+    /// Get the precomputed end hashes of a specific table, decoded on access
+    ///
+    /// Returns `None` if the file has no end hash section.
+    pub fn le_end_hashes(&self, table_id: u32) -> Option<LeEndHashes<'_>> {
+        LeEndHashes::new(self.end_hash_bytes(table_id)?)
+    }
+
+    fn entry_bytes(&self, table_id: u32) -> Option<&[u8]> {
+        if table_id >= self.header.num_tables || self.header.is_compact() {
+            return None;
+        }
+
+        let table_size = self.header.chains_per_table as usize * CHAIN_ENTRY_SIZE;
+        let offset = self.chains_offset + table_id as usize * table_size;
+        Some(&self.as_bytes()[offset..offset + table_size])
+    }
+
+    fn end_hash_bytes(&self, table_id: u32) -> Option<&[u8]> {
+        let section_offset = self.end_hashes_offset?;
+        if table_id >= self.header.num_tables {
+            return None;
+        }
+
+        let count = self.header.chains_per_table as usize;
+        let offset = section_offset + table_id as usize * count * END_HASH_SIZE;
+        Some(&self.as_bytes()[offset..offset + count * END_HASH_SIZE])
+    }
+
+    /// Get a specific compact table
+    ///
+    /// Returns `None` if the file is not compact.
+    pub fn compact_table(&self, table_id: u32) -> Option<CompactTableRef<'_>> {
+        let &(descriptor, offset) = self.compact.get(table_id as usize)?;
+        let bytes = &self.as_bytes()[offset..offset + descriptor.byte_size() as usize];
+        CompactTableRef::new(descriptor, bytes)
+    }
+
+    /// Get a specific table prepared for search
+    ///
+    /// Uses the sort key and chain length recorded in the header and the
+    /// stored end hashes, if any. Entries are decoded on access, so this
+    /// works on any target endianness.
+    pub fn table_ref(&self, table_id: u32) -> Option<TableRef<'_>> {
+        if self.header.is_compact() {
+            let compact = self.compact_table(table_id)?;
+            return Some(
+                TableRef::from_compact(compact).with_chain_length(self.header.chain_length),
+            );
+        }
+
+        let entries = self.entries(table_id)?;
+        let table = TableRef::from_le(entries)
+            .with_sort_key(self.header.end_hash_key())
+            .with_chain_length(self.header.chain_length);
+        Some(match self.le_end_hashes(table_id) {
+            Some(end_hashes) => table.with_le_end_hashes(end_hashes),
+            None => table,
+        })
+    }
+
+    /// Get views of all tables, indexed by table_id
+    ///
+    /// Pass these to `search_seeds_multi_table` to search the whole file.
+    pub fn table_refs(&self) -> Vec<TableRef<'_>> {
+        (0..self.num_tables())
+            .filter_map(|table_id| self.table_ref(table_id))
+            .collect()
     }
 
     /// Get the number of tables
@@ -239,6 +1190,41 @@ mod tests {
         fs::remove_file(path).ok();
     }
 
+    #[test]
+    fn test_save_with_custom_params() {
+        use crate::domain::table_format::TableParams;
+
+        let path = create_temp_file("test_table_params.g7rt");
+        let params = TableParams::new(32, 4, 3);
+        let tables: Vec<Vec<ChainEntry>> = (0..3)
+            .map(|table_id| (0..4).map(|seed| ChainEntry::new(seed, table_id)).collect())
+            .collect();
+
+        let header = TableHeader::with_params(417, params, true);
+        save_single_table_with_header(&path, &header, &tables, None).expect("Failed to save");
+
+        let options = ValidationOptions::for_search(417);
+        let mapped = MappedSingleTable::open(&path, &options).expect("Failed to open");
+        assert_eq!(mapped.header().params(), params);
+        assert_eq!(mapped.table_ref(2).unwrap().chain_length, 32);
+
+        let result = load_single_table(&path, &options.with_params(TableParams::default()));
+        assert!(matches!(
+            result,
+            Err(TableFormatError::ChainLengthMismatch { .. })
+        ));
+
+        // The header must describe the tables
+        let wrong = TableHeader::with_params(417, TableParams::new(32, 5, 3), true);
+        let result = save_single_table_with_header(&path, &wrong, &tables, None);
+        assert!(matches!(
+            result,
+            Err(TableFormatError::ChainCountMismatch { .. })
+        ));
+
+        fs::remove_file(path).ok();
+    }
+
     #[test]
     fn test_table_file_size_validation() {
         let path = create_temp_file("test_table_size.g7rt");
@@ -282,8 +1268,61 @@ mod tests {
 
         assert_eq!(table.num_tables(), NUM_TABLES);
         assert_eq!(table.chains_per_table(), NUM_CHAINS);
-        assert!(table.table(0).is_some());
-        assert!(table.table(NUM_TABLES).is_none());
+        assert_eq!(table.entries(0).unwrap().to_vec(), tables[0]);
+        assert!(table.entries(NUM_TABLES).is_none());
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_mapped_table_end_hashes() {
+        use crate::domain::chain::EndHashKey;
+        use crate::infra::table_sort::sort_table_with_end_hashes;
+
+        let path = create_temp_file("test_mmap_end_hashes.g7rt");
+        let mut tables = create_tables();
+        let end_hashes: Vec<Vec<u64>> = tables
+            .iter_mut()
+            .map(|table| sort_table_with_end_hashes(table, 417))
+            .collect();
+
+        save_single_table_with_end_hashes(&path, 417, &tables, &end_hashes)
+            .expect("Failed to save");
+
+        let options = ValidationOptions::for_search(417);
+        let mapped = MappedSingleTable::open(&path, &options).expect("Failed to open");
+        assert!(mapped.header().has_end_hashes());
+
+        for table_id in [0, NUM_TABLES - 1] {
+            let table = mapped.table_ref(table_id).unwrap();
+            assert_eq!(table.sort_key, EndHashKey::Full);
+            assert!(table.has_end_hashes());
+            for (i, entry) in tables[table_id as usize].iter().enumerate() {
+                assert_eq!(table.start_seed(i), entry.start_seed);
+                assert_eq!(table.end_hash(i, 417), end_hashes[table_id as usize][i]);
+            }
+            assert_eq!(
+                mapped.le_end_hashes(table_id).unwrap().to_vec(),
+                end_hashes[table_id as usize]
+            );
+        }
+
+        // load_single_table skips the end hash section
+        let (_, loaded) = load_single_table(&path, &options).expect("Failed to load");
+        assert_eq!(loaded, tables);
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_mapped_table_without_end_hashes() {
+        let path = create_temp_file("test_mmap_no_end_hashes.g7rt");
+        save_single_table(&path, 417, &create_tables(), true).expect("Failed to save");
+
+        let options = ValidationOptions::for_search(417);
+        let mapped = MappedSingleTable::open(&path, &options).expect("Failed to open");
+        assert!(mapped.le_end_hashes(0).is_none());
+        assert!(!mapped.table_ref(0).unwrap().has_end_hashes());
 
         fs::remove_file(path).ok();
     }
@@ -305,4 +1344,411 @@ mod tests {
 
         fs::remove_file(path).ok();
     }
+
+    fn table_bytes(
+        header: &TableHeader,
+        tables: &[Vec<ChainEntry>],
+        end_hashes: Option<&[Vec<u64>]>,
+    ) -> Vec<u8> {
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        write_single_table(&mut cursor, header, tables, end_hashes).expect("Failed to write");
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn test_table_from_bytes_search() {
+        use crate::app::searcher::search_seeds_multi_table;
+        use crate::constants::NEEDLE_COUNT;
+        use crate::domain::chain::compute_chain;
+        use crate::domain::needle::gen_needle_values;
+        use crate::domain::table_format::TableParams;
+        use crate::infra::table_sort::sort_table_parallel;
+
+        let params = TableParams::new(16, 64, 2);
+        let tables: Vec<Vec<ChainEntry>> = (0..2)
+            .map(|table_id| {
+                let mut table: Vec<ChainEntry> = (0..64)
+                    .map(|seed| compute_chain(seed, 417, table_id, 16))
+                    .collect();
+                sort_table_parallel(&mut table, 417);
+                table
+            })
+            .collect();
+        let header = TableHeader::with_params(417, params, true);
+        let bytes = table_bytes(&header, &tables, None);
+
+        let options = ValidationOptions::for_search(417);
+        let view = SingleTableView::from_bytes(bytes.as_slice(), &options).expect("Failed to read");
+        assert_eq!(view.header().params(), params);
+        assert_eq!(view.entries(1).unwrap().to_vec(), tables[1]);
+
+        let needle = gen_needle_values(5, 417, NEEDLE_COUNT).try_into().unwrap();
+        let results = search_seeds_multi_table(needle, 417, &view.table_refs());
+        assert!(results.contains(&(0, 5)));
+
+        // An owned buffer behaves the same
+        let owned = SingleTableView::from_bytes(bytes.clone(), &options).expect("Failed to read");
+        assert_eq!(
+            owned.verify_checksum(),
+            Ok(ChecksumStatus::Verified(view.data_checksum()))
+        );
+        assert_eq!(owned.into_inner(), bytes);
+    }
+
+    #[test]
+    fn test_table_from_bytes_validation() {
+        let header = TableHeader::new(417, true);
+        let bytes = table_bytes(&header, &create_tables(), None);
+
+        let options = ValidationOptions::for_search(417);
+        assert!(matches!(
+            SingleTableView::from_bytes(&bytes[..FILE_HEADER_SIZE - 1], &options),
+            Err(TableFormatError::InvalidFileSize { .. })
+        ));
+        assert!(matches!(
+            SingleTableView::from_bytes(&bytes[..bytes.len() - 1], &options),
+            Err(TableFormatError::InvalidFileSize { .. })
+        ));
+        assert!(matches!(
+            SingleTableView::from_bytes(bytes.as_slice(), &ValidationOptions::for_search(477)),
+            Err(TableFormatError::ConsumptionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_table_from_unaligned_bytes() {
+        let tables = create_tables();
+        let header = TableHeader::new(417, true);
+        let mut bytes = vec![0u8];
+        bytes.extend(table_bytes(&header, &tables, None));
+
+        let options = ValidationOptions::for_search(417);
+        let view = SingleTableView::from_bytes(&bytes[1..], &options).expect("Failed to read");
+        #[cfg(target_endian = "little")]
+        assert!(view.table(0).is_none());
+        assert_eq!(view.entries(0).unwrap().to_vec(), tables[0]);
+        assert_eq!(
+            view.table_ref(0).unwrap().start_seed(1),
+            tables[0][1].start_seed
+        );
+    }
+
+    #[test]
+    fn test_little_endian_view_search() {
+        use crate::app::searcher::search_seeds_multi_table;
+        use crate::constants::NEEDLE_COUNT;
+        use crate::domain::chain::{TableData, compute_chain, enumerate_chain_seeds};
+        use crate::domain::needle::gen_needle_values;
+        use crate::domain::table_format::TableParams;
+        use crate::infra::table_sort::sort_table_with_end_hashes;
+
+        let params = TableParams::new(16, 64, 2);
+        let mut tables: Vec<Vec<ChainEntry>> = (0..2)
+            .map(|table_id| {
+                (0..64)
+                    .map(|seed| compute_chain(seed, 417, table_id, 16))
+                    .collect()
+            })
+            .collect();
+        let end_hashes: Vec<Vec<u64>> = tables
+            .iter_mut()
+            .map(|table| sort_table_with_end_hashes(table, 417))
+            .collect();
+        let mut header = TableHeader::with_params(417, params, true);
+        header.set_end_hashes(true);
+
+        // Unaligned, so the tables can only be read through the little-endian view
+        let mut bytes = vec![0u8];
+        bytes.extend(table_bytes(&header, &tables, Some(&end_hashes)));
+        let options = ValidationOptions::for_search(417);
+        let view = SingleTableView::from_bytes(&bytes[1..], &options).expect("Failed to read");
+        let refs = view.table_refs();
+        assert!(refs.iter().all(|table| matches!(
+            table.data,
+            TableData::LittleEndian {
+                end_hashes: Some(_),
+                ..
+            }
+        )));
+
+        let chain = tables[1][10];
+        for column in [0, 7, 15] {
+            let seed = enumerate_chain_seeds(chain.start_seed, 417, 1, 16)[column];
+            let needle = gen_needle_values(seed, 417, NEEDLE_COUNT)
+                .try_into()
+                .unwrap();
+            assert!(search_seeds_multi_table(needle, 417, &refs).contains(&(1, seed)));
+        }
+    }
+
+    #[test]
+    fn test_compact_table_save_and_open() {
+        use crate::infra::table_sort::sort_table_with_end_hashes;
+
+        let path = create_temp_file("test_mmap_compact.g7rt");
+        let mut tables = create_tables();
+        let end_hashes: Vec<Vec<u64>> = tables
+            .iter_mut()
+            .map(|table| sort_table_with_end_hashes(table, 417))
+            .collect();
+
+        let header = TableHeader::new(417, true);
+        save_compact_table(&path, &header, &tables, &end_hashes).expect("Failed to save");
+
+        let options = ValidationOptions::for_search(417);
+        let mapped = MappedSingleTable::open(&path, &options).expect("Failed to open");
+        assert!(mapped.header().is_compact());
+        assert_eq!(mapped.expected_size(), fs::metadata(&path).unwrap().len());
+        assert!(mapped.entries(0).is_none());
+        assert!(mapped.le_end_hashes(0).is_none());
+
+        for table_id in [0, NUM_TABLES - 1] {
+            let table = mapped.table_ref(table_id).unwrap();
+            let expected = &tables[table_id as usize];
+            assert_eq!(table.len(), expected.len());
+            for (i, entry) in expected.iter().enumerate() {
+                assert_eq!(table.start_seed(i), entry.start_seed);
+                assert_eq!(table.end_hash(i, 417), end_hashes[table_id as usize][i]);
+            }
+        }
+
+        // The same file read from a buffer
+        let view = SingleTableView::from_bytes(fs::read(&path).unwrap(), &options)
+            .expect("Failed to read");
+        assert_eq!(
+            view.table_ref(0).unwrap().end_hash(1, 417),
+            end_hashes[0][1]
+        );
+
+        // Compact tables have no end seeds to load
+        assert_eq!(
+            load_single_table(&path, &options).unwrap_err(),
+            TableFormatError::CompactNotSupported
+        );
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_compact_table_truncated() {
+        use crate::infra::table_sort::sort_table_with_end_hashes;
+
+        let path = create_temp_file("test_compact_truncated.g7rt");
+        let mut tables = create_tables();
+        let end_hashes: Vec<Vec<u64>> = tables
+            .iter_mut()
+            .map(|table| sort_table_with_end_hashes(table, 417))
+            .collect();
+        save_compact_table(&path, &TableHeader::new(417, true), &tables, &end_hashes)
+            .expect("Failed to save");
+
+        let data = fs::read(&path).unwrap();
+        fs::write(&path, &data[..data.len() - 8]).unwrap();
+
+        let result = MappedSingleTable::open(&path, &ValidationOptions::for_search(417));
+        assert!(matches!(
+            result,
+            Err(TableFormatError::InvalidFileSize { .. })
+        ));
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_compact_table_corrupted_index() {
+        use crate::infra::table_sort::sort_table_with_end_hashes;
+
+        let path = create_temp_file("test_compact_corrupted.g7rt");
+        let mut tables = create_tables();
+        let end_hashes: Vec<Vec<u64>> = tables
+            .iter_mut()
+            .map(|table| sort_table_with_end_hashes(table, 417))
+            .collect();
+        save_compact_table(&path, &TableHeader::new(417, true), &tables, &end_hashes)
+            .expect("Failed to save");
+
+        // Point the first sample of table 0 past its high bit vector
+        let mut data = fs::read(&path).unwrap();
+        let descriptor = CompactTableDescriptor::from_bytes(
+            data[FILE_HEADER_SIZE..FILE_HEADER_SIZE + COMPACT_DESCRIPTOR_SIZE]
+                .try_into()
+                .unwrap(),
+        );
+        let tables_start = FILE_HEADER_SIZE + NUM_TABLES as usize * COMPACT_DESCRIPTOR_SIZE;
+        let samples_start =
+            tables_start + (descriptor.byte_size() - descriptor.sample_words * 8) as usize;
+        data[samples_start..samples_start + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        fs::write(&path, &data).unwrap();
+
+        let result = MappedSingleTable::open(&path, &ValidationOptions::for_search(417));
+        assert!(matches!(
+            result,
+            Err(TableFormatError::InvalidCompactTable { table_id: 0 })
+        ));
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_checksum_recorded_and_verified() {
+        let path = create_temp_file("test_table_checksum.g7rt");
+        save_single_table(&path, 417, &create_tables(), true).expect("Failed to save");
+
+        let options = ValidationOptions::for_search(417);
+        let mapped = MappedSingleTable::open(&path, &options).expect("Failed to open");
+        let checksum = mapped
+            .header()
+            .checksum
+            .expect("checksum should be recorded");
+        assert_eq!(
+            mapped.verify_checksum(),
+            Ok(ChecksumStatus::Verified(checksum))
+        );
+        drop(mapped);
+        assert_eq!(
+            verify_table_file(&path),
+            Ok(ChecksumStatus::Verified(checksum))
+        );
+
+        // Flip one bit of the chain data: the size check passes, the checksum does not
+        let mut data = fs::read(&path).unwrap();
+        data[FILE_HEADER_SIZE + 100] ^= 0x01;
+        fs::write(&path, &data).unwrap();
+
+        assert!(MappedSingleTable::open(&path, &options).is_ok());
+        assert!(matches!(
+            verify_table_file(&path),
+            Err(TableFormatError::ChecksumMismatch { .. })
+        ));
+        let mapped = MappedSingleTable::open(&path, &options).unwrap();
+        assert!(matches!(
+            mapped.verify_checksum(),
+            Err(TableFormatError::ChecksumMismatch { .. })
+        ));
+        drop(mapped);
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_verify_without_checksum() {
+        let path = create_temp_file("test_table_no_checksum.g7rt");
+        save_single_table(&path, 417, &create_tables(), true).expect("Failed to save");
+
+        // Clear the checksum as in files written before checksums were recorded
+        let mut data = fs::read(&path).unwrap();
+        data[40..48].fill(0);
+        fs::write(&path, &data).unwrap();
+
+        assert_eq!(verify_table_file(&path), Ok(ChecksumStatus::NotRecorded));
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_sectioned_table_from_bytes() {
+        use crate::domain::table_format::TableParams;
+
+        let params = TableParams::new(16, 8, 2);
+        let tables: Vec<Vec<ChainEntry>> = (0..2)
+            .map(|table_id| (0..8).map(|seed| ChainEntry::new(seed, table_id)).collect())
+            .collect();
+        let end_hashes: Vec<Vec<u64>> = (0..2)
+            .map(|table_id| (0..8).map(|i| i * 10 + table_id).collect())
+            .collect();
+        let header = TableHeader::with_params(417, params, true);
+        let sections = TableSections::default()
+            .with_end_hashes(&end_hashes)
+            .with_metadata(b"name=test");
+
+        let mut cursor = Cursor::new(Vec::new());
+        write_sectioned_table(&mut cursor, &header, &tables, &sections).expect("Failed to write");
+        let mut bytes = cursor.into_inner();
+
+        let options = ValidationOptions::for_search(417);
+        let view = SingleTableView::from_bytes(bytes.as_slice(), &options).expect("Failed to read");
+        assert!(view.header().is_sectioned());
+        assert!(view.header().has_end_hashes());
+        assert_eq!(view.expected_size(), bytes.len() as u64);
+        let kinds: Vec<SectionKind> = view.sections().iter().map(|entry| entry.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SectionKind::ChainData,
+                SectionKind::KeyCache,
+                SectionKind::Metadata
+            ]
+        );
+        assert_eq!(view.entries(1).unwrap().to_vec(), tables[1]);
+        assert_eq!(view.le_end_hashes(1).unwrap().to_vec(), end_hashes[1]);
+        assert_eq!(view.table_ref(0).unwrap().end_hash(3, 417), 30);
+        assert_eq!(view.section(SectionKind::Metadata), Some(&b"name=test"[..]));
+        assert!(view.section(SectionKind::MissingSeeds).is_none());
+        assert!(matches!(
+            view.verify_checksum(),
+            Ok(ChecksumStatus::Verified(_))
+        ));
+        view.verify_sections().expect("Sections should match");
+
+        // A corrupted section is reported by its type
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let view = SingleTableView::from_bytes(bytes.as_slice(), &options).unwrap();
+        assert!(matches!(
+            view.verify_sections(),
+            Err(TableFormatError::SectionChecksumMismatch {
+                kind: SectionKind::Metadata,
+                ..
+            })
+        ));
+        assert!(matches!(
+            SingleTableView::from_bytes(&bytes[..last], &options),
+            Err(TableFormatError::InvalidFileSize { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sectioned_table_save_and_load() {
+        let path = create_temp_file("test_sectioned.g7rt");
+        let tables = create_tables();
+        let header = TableHeader::new(417, true);
+
+        save_sectioned_table(&path, &header, &tables, &TableSections::default())
+            .expect("Failed to save");
+        assert!(matches!(
+            verify_table_file(&path),
+            Ok(ChecksumStatus::Verified(_))
+        ));
+
+        let options = ValidationOptions::for_search(417);
+        let (loaded_header, loaded) = load_single_table(&path, &options).expect("Failed to load");
+        assert!(loaded_header.is_sectioned());
+        assert_eq!(loaded, tables);
+
+        let mapped = MappedSingleTable::open(&path, &options).expect("Failed to open");
+        assert!(!mapped.header().has_end_hashes());
+        assert!(mapped.le_end_hashes(0).is_none());
+        assert_eq!(mapped.entries(NUM_TABLES - 1).unwrap().to_vec(), tables[15]);
+        assert_eq!(mapped.entries(0).unwrap().to_vec(), tables[0]);
+        #[cfg(target_endian = "little")]
+        assert_eq!(mapped.table(0).unwrap(), tables[0].as_slice());
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_sectioned_table_requires_chain_data() {
+        let header = TableHeader {
+            version: crate::constants::SECTIONED_FORMAT_VERSION,
+            ..TableHeader::new(417, true)
+        };
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend(SectionDirectory::layout(&[(SectionKind::Metadata, 1, 0, 0)]).to_bytes());
+
+        let result = SingleTableView::from_bytes(bytes.as_slice(), &ValidationOptions::default());
+        assert!(matches!(
+            result,
+            Err(TableFormatError::MissingSection(SectionKind::ChainData))
+        ));
+    }
 }
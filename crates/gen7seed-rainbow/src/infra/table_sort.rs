@@ -12,29 +12,48 @@ use rayon::prelude::*;
 /// 2. Create (key, entry) pairs and parallel sort
 /// 3. Extract sorted entries
 ///
+/// The sort key is the full 33-bit end hash (`EndHashKey::Full`); chains
+/// with the same end hash are ordered by start seed, so the result does not
+/// depend on the input order.
 /// This is the recommended function for production use with large tables.
 /// Memory usage: O(n) for pairs (key + entry combined)
 pub fn sort_table_parallel(entries: &mut [ChainEntry], consumption: i32) {
+    sort_table_with_end_hashes(entries, consumption);
+}
+
+/// Sort table entries and return the end hash of each sorted entry
+///
+/// Same as `sort_table_parallel`, but keeps the computed keys so they can be
+/// saved alongside the table (see `save_single_table_with_end_hashes`).
+pub fn sort_table_with_end_hashes(entries: &mut [ChainEntry], consumption: i32) -> Vec<u64> {
     if entries.is_empty() {
-        return;
+        return Vec::new();
     }
 
     // Step 1 & 2: Calculate keys and create pairs simultaneously
-    let mut pairs: Vec<(u32, ChainEntry)> = entries
+    let mut pairs: Vec<(u64, ChainEntry)> = entries
         .par_iter()
-        .map(|entry| {
-            let key = gen_hash_from_seed(entry.end_seed, consumption) as u32;
-            (key, *entry)
-        })
+        .map(|entry| (gen_hash_from_seed(entry.end_seed, consumption), *entry))
         .collect();
 
     // Step 3: Parallel sort
-    pairs.par_sort_unstable_by_key(|(key, _)| *key);
+    pairs.par_sort_unstable_by_key(|(key, entry)| (*key, entry.start_seed));
 
     // Step 4: Extract sorted entries
-    for (i, (_, entry)) in pairs.into_iter().enumerate() {
+    let mut end_hashes = Vec::with_capacity(pairs.len());
+    for (i, (key, entry)) in pairs.into_iter().enumerate() {
         entries[i] = entry;
+        end_hashes.push(key);
     }
+    end_hashes
+}
+
+/// Compute the end hash of every entry in parallel
+pub fn compute_end_hashes(entries: &[ChainEntry], consumption: i32) -> Vec<u64> {
+    entries
+        .par_iter()
+        .map(|entry| gen_hash_from_seed(entry.end_seed, consumption))
+        .collect()
 }
 
 /// Deduplicate sorted table (original version)
@@ -46,10 +65,10 @@ pub fn deduplicate_table(entries: &mut Vec<ChainEntry>, consumption: i32) {
     }
 
     let mut write_idx = 1;
-    let mut prev_hash = gen_hash_from_seed(entries[0].end_seed, consumption) as u32;
+    let mut prev_hash = gen_hash_from_seed(entries[0].end_seed, consumption);
 
     for read_idx in 1..entries.len() {
-        let current_hash = gen_hash_from_seed(entries[read_idx].end_seed, consumption) as u32;
+        let current_hash = gen_hash_from_seed(entries[read_idx].end_seed, consumption);
         if current_hash != prev_hash {
             entries[write_idx] = entries[read_idx];
             write_idx += 1;
@@ -60,6 +79,38 @@ pub fn deduplicate_table(entries: &mut Vec<ChainEntry>, consumption: i32) {
     entries.truncate(write_idx);
 }
 
+/// Deduplicate a sorted table together with its end hashes
+///
+/// Keeps only the first entry among those with the same end hash (see
+/// `sort_table_with_end_hashes`) and returns the number of entries removed.
+pub fn deduplicate_table_with_end_hashes(
+    entries: &mut Vec<ChainEntry>,
+    end_hashes: &mut Vec<u64>,
+) -> usize {
+    assert_eq!(
+        entries.len(),
+        end_hashes.len(),
+        "end hash count must match entry count"
+    );
+    let len = entries.len();
+    if len == 0 {
+        return 0;
+    }
+
+    let mut write_idx = 1;
+    for read_idx in 1..len {
+        if end_hashes[read_idx] != end_hashes[write_idx - 1] {
+            entries[write_idx] = entries[read_idx];
+            end_hashes[write_idx] = end_hashes[read_idx];
+            write_idx += 1;
+        }
+    }
+
+    entries.truncate(write_idx);
+    end_hashes.truncate(write_idx);
+    len - write_idx
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,8 +145,8 @@ mod tests {
 
         // Verify ordering by hash
         for i in 1..entries.len() {
-            let prev_hash = gen_hash_from_seed(entries[i - 1].end_seed, 417) as u32;
-            let curr_hash = gen_hash_from_seed(entries[i].end_seed, 417) as u32;
+            let prev_hash = gen_hash_from_seed(entries[i - 1].end_seed, 417);
+            let curr_hash = gen_hash_from_seed(entries[i].end_seed, 417);
             assert!(prev_hash <= curr_hash);
         }
     }
@@ -114,12 +165,49 @@ mod tests {
 
         // Verify ordering by hash
         for i in 1..entries.len() {
-            let prev_hash = gen_hash_from_seed(entries[i - 1].end_seed, 417) as u32;
-            let curr_hash = gen_hash_from_seed(entries[i].end_seed, 417) as u32;
+            let prev_hash = gen_hash_from_seed(entries[i - 1].end_seed, 417);
+            let curr_hash = gen_hash_from_seed(entries[i].end_seed, 417);
             assert!(prev_hash <= curr_hash);
         }
     }
 
+    #[test]
+    fn test_sort_table_with_end_hashes() {
+        let mut entries: Vec<ChainEntry> = (0..64).map(|i| ChainEntry::new(i, i * 7919)).collect();
+
+        let end_hashes = sort_table_with_end_hashes(&mut entries, 417);
+
+        assert_eq!(end_hashes, compute_end_hashes(&entries, 417));
+        assert!(end_hashes.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_sort_uses_full_hash() {
+        // Find end seeds whose hashes differ only above bit 32
+        let end_seeds: Vec<u32> = (0..4096).collect();
+        let hashes = compute_end_hashes(
+            &end_seeds
+                .iter()
+                .map(|&s| ChainEntry::new(s, s))
+                .collect::<Vec<_>>(),
+            417,
+        );
+        let high = (0..hashes.len()).find(|&i| hashes[i] >> 32 != 0).unwrap();
+        let low = (0..hashes.len())
+            .find(|&i| hashes[i] >> 32 == 0 && (hashes[i] as u32) > (hashes[high] as u32))
+            .unwrap();
+
+        let mut entries = vec![
+            ChainEntry::new(0, end_seeds[high]),
+            ChainEntry::new(1, end_seeds[low]),
+        ];
+        sort_table_parallel(&mut entries, 417);
+
+        // Truncated to u32, `high` would sort first
+        assert_eq!(entries[0].end_seed, end_seeds[low]);
+        assert_eq!(entries[1].end_seed, end_seeds[high]);
+    }
+
     // =========================================================================
     // deduplicate tests
     // =========================================================================
@@ -158,9 +246,29 @@ mod tests {
 
         // Verify ordering remains non-decreasing by hash
         for i in 1..entries.len() {
-            let prev_hash = gen_hash_from_seed(entries[i - 1].end_seed, 417) as u32;
-            let curr_hash = gen_hash_from_seed(entries[i].end_seed, 417) as u32;
+            let prev_hash = gen_hash_from_seed(entries[i - 1].end_seed, 417);
+            let curr_hash = gen_hash_from_seed(entries[i].end_seed, 417);
             assert!(prev_hash <= curr_hash);
         }
     }
+
+    #[test]
+    fn test_deduplicate_with_end_hashes_keeps_lowest_start_seed() {
+        let mut entries = vec![
+            ChainEntry::new(4, 200),
+            ChainEntry::new(2, 100),
+            ChainEntry::new(1, 100),
+            ChainEntry::new(3, 200),
+            ChainEntry::new(5, 300),
+        ];
+
+        let mut end_hashes = sort_table_with_end_hashes(&mut entries, 417);
+        let removed = deduplicate_table_with_end_hashes(&mut entries, &mut end_hashes);
+
+        assert_eq!(removed, 2);
+        assert_eq!(end_hashes, compute_end_hashes(&entries, 417));
+        let mut start_seeds: Vec<u32> = entries.iter().map(|e| e.start_seed).collect();
+        start_seeds.sort_unstable();
+        assert_eq!(start_seeds, vec![1, 3, 5]);
+    }
 }
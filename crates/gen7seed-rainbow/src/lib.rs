@@ -21,40 +21,83 @@ pub mod infra;
 
 // Re-export commonly used types
 pub use constants::*;
-pub use domain::chain::ChainEntry;
+pub use domain::chain::{
+    ChainEntry, ChainTable, EndHashKey, LeChainEntries, LeEndHashes, TableData, TableRef,
+};
+pub use domain::checksum::ChecksumStatus;
 pub use domain::coverage::SeedBitmap;
 pub use domain::hash::{gen_hash, gen_hash_from_seed, reduce_hash_with_salt};
 pub use domain::missing_format::{MissingFormatError, MissingSeedsHeader};
+pub use domain::needle::{NeedleCorrection, NeedleTolerance};
+pub use domain::section_format::{SectionEntry, SectionKind};
 pub use domain::sfmt::Sfmt;
-pub use domain::table_format::{TableFormatError, TableHeader, ValidationOptions};
+pub use domain::table_format::{
+    StartPoints, TableFormatError, TableHeader, TableParams, ValidationOptions,
+};
+
+// Re-export cancellation token
+pub use app::cancel::CancellationToken;
 
 // Re-export generator types and functions
-pub use app::generator::{GenerateOptions, generate_all_tables, generate_table};
+pub use app::generator::{
+    GenerateOptions, MergeStats, generate_all_tables, generate_all_tables_guided, generate_table,
+    generate_table_guided, make_table_perfect, mark_chain_seeds,
+};
+
+// Re-export checkpointed generation types
+pub use app::checkpoint::{
+    CheckpointError, CheckpointEvent, CheckpointOptions, CheckpointSummary,
+    generate_tables_with_checkpoints,
+};
 
 // Re-export searcher function
-pub use app::searcher::{search_seeds, search_seeds_with_validation};
+pub use app::searcher::{
+    ExtraNeedleFilter, SearchHit, SearchOptions, SearchReport, SearchStatus, TableSearchStats,
+    TolerantHit, filter_by_extra_needles, search_missing_seeds, search_missing_seeds_tolerant,
+    search_seeds, search_seeds_multi_table, search_seeds_multi_table_with_options,
+    search_seeds_multi_table_with_report, search_seeds_tolerant, search_seeds_tolerant_multi_table,
+    search_seeds_with_options, search_seeds_with_report, search_seeds_with_validation,
+};
 
 // Re-export 16-table parallel search (multi-sfmt feature)
 #[cfg(feature = "multi-sfmt")]
-pub use app::searcher::search_seeds_x16;
+pub use app::searcher::{
+    search_seeds_tolerant_x16, search_seeds_x16, search_seeds_x16_with_options,
+    search_seeds_x16_with_report,
+};
 
-// Re-export coverage analysis types
-pub use app::coverage::{
-    BitmapOptions, MissingSeedsResult, build_seed_bitmap, extract_missing_seeds,
-    extract_missing_seeds_with_header,
+// Re-export brute-force search types
+pub use app::brute_force::{
+    BruteForceOptions, BruteForceProgress, BruteForceResult, BruteForceStatus, brute_force_search,
 };
 
-// Re-export multi-table coverage analysis types (multi-sfmt feature)
-#[cfg(feature = "multi-sfmt")]
+// Re-export original table conversion
+pub use app::convert::{
+    ConvertError, ImportOptions, export_original_tables, import_original_tables,
+};
+
+// Re-export table inspection
+pub use app::inspect::{ChainTrace, EndKeyStats, end_key_stats, trace_chain};
+
+// Re-export table verification
+pub use app::verify::{
+    RoundTripReport, TableVerifyReport, VerifyFailure, VerifyOptions, VerifyReport,
+    verify_round_trips, verify_table, verify_tables,
+};
+
+// Re-export coverage analysis types
 pub use app::coverage::{
-    build_seed_bitmap_multi_table, extract_missing_seeds_multi_table,
-    extract_missing_seeds_multi_table_with_header,
+    BitmapOptions, MissingSeedsResult, build_seed_bitmap, build_seed_bitmap_multi_table,
+    extract_missing_seeds, extract_missing_seeds_multi_table,
+    extract_missing_seeds_multi_table_with_header, extract_missing_seeds_with_header,
 };
 
 // Re-export missing seeds I/O
 pub use infra::missing_seeds_io::{
-    get_missing_seeds_path, load_missing_seeds, save_missing_seeds, verify_missing_seeds_source,
+    MissingSeedSource, MissingSeeds, MissingSeedsView, get_missing_seeds_path, load_missing_seeds,
+    load_missing_seeds_for_mapped_table, load_missing_seeds_for_table, save_missing_seeds,
+    save_missing_seeds_compressed, verify_missing_seeds_file, verify_missing_seeds_source,
 };
 
 // Re-export mmap functionality
-pub use infra::table_io::MappedSingleTable;
+pub use infra::table_io::{MappedSingleTable, SingleTableView, TableSections, verify_table_file};
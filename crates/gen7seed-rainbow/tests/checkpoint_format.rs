@@ -0,0 +1,40 @@
+use gen7seed_rainbow::constants::{CHAIN_ENTRY_SIZE, END_HASH_SIZE, FILE_HEADER_SIZE};
+use gen7seed_rainbow::domain::checkpoint_format::{
+    CheckpointHeader, expected_checkpoint_file_size,
+};
+use gen7seed_rainbow::domain::table_format::{TableFormatError, TableHeader, TableParams};
+
+#[test]
+fn test_checkpoint_header_serialization() {
+    let mut header = CheckpointHeader::new(417, TableParams::new(64, 131072, 8), 7, 65536..131072);
+    header.set_sorted(true);
+
+    let bytes = header.to_bytes();
+    let restored = CheckpointHeader::from_bytes(&bytes).unwrap();
+
+    assert_eq!(header, restored);
+    assert_eq!(restored.range(), 65536..131072);
+    assert!(restored.is_sorted());
+    assert_eq!(restored.params(), TableParams::new(64, 131072, 8));
+}
+
+#[test]
+fn test_checkpoint_header_rejects_table_file() {
+    let bytes = TableHeader::new(417, true).to_bytes();
+
+    let result = CheckpointHeader::from_bytes(&bytes);
+    assert!(matches!(result, Err(TableFormatError::InvalidMagic)));
+}
+
+#[test]
+fn test_expected_checkpoint_file_size() {
+    let mut header = CheckpointHeader::new(417, TableParams::default(), 0, 100..200);
+    let chains_size = FILE_HEADER_SIZE as u64 + 100 * CHAIN_ENTRY_SIZE as u64;
+    assert_eq!(expected_checkpoint_file_size(&header), chains_size);
+
+    header.set_end_hashes(true);
+    assert_eq!(
+        expected_checkpoint_file_size(&header),
+        chains_size + 100 * END_HASH_SIZE as u64
+    );
+}
@@ -46,3 +46,80 @@ fn test_source_verification() {
         Err(MissingFormatError::SourceMismatch { .. })
     ));
 }
+
+#[test]
+fn test_missing_header_checksum_serialization() {
+    let table_header = TableHeader::new(417, true);
+    let mut missing_header = MissingSeedsHeader::new(&table_header, 3);
+    assert_eq!(missing_header.checksum, None);
+
+    missing_header.checksum = Some(0x0123_4567_89ab_cdef);
+    let restored = MissingSeedsHeader::from_bytes(&missing_header.to_bytes()).unwrap();
+    assert_eq!(restored.checksum, Some(0x0123_4567_89ab_cdef));
+}
+
+#[test]
+fn test_source_binding_uses_data_checksum() {
+    let mut table_header = TableHeader::new(417, true);
+    table_header.checksum = Some(42);
+    let missing_header = MissingSeedsHeader::new(&table_header, 100);
+
+    // Same contents created at another time
+    let mut same_data = table_header;
+    same_data.created_at += 1000;
+    assert!(missing_header.verify_source(&same_data).is_ok());
+
+    // Different contents with the same header fields
+    let mut other_data = table_header;
+    other_data.checksum = Some(43);
+    assert!(matches!(
+        missing_header.verify_source(&other_data),
+        Err(MissingFormatError::SourceMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_source_binding_prefers_chains_checksum() {
+    let mut table_header = TableHeader::new(417, true);
+    table_header.checksum = Some(42);
+    table_header.chains_checksum = Some(7);
+    let missing_header = MissingSeedsHeader::new(&table_header, 100);
+
+    // Same chains, stored with end hashes (other data checksum and flags)
+    let mut with_end_hashes = table_header;
+    with_end_hashes.checksum = Some(43);
+    with_end_hashes.set_end_hashes(true);
+    assert!(missing_header.verify_source(&with_end_hashes).is_ok());
+
+    // Other chains
+    let mut other_chains = table_header;
+    other_chains.chains_checksum = Some(8);
+    assert!(matches!(
+        missing_header.verify_source(&other_chains),
+        Err(MissingFormatError::SourceMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_compressed_version_accepted() {
+    use gen7seed_rainbow::constants::{FILE_FORMAT_VERSION, MISSING_COMPRESSED_FORMAT_VERSION};
+
+    let table_header = TableHeader::new(417, true);
+    let mut missing_header = MissingSeedsHeader::new(&table_header, 3);
+    assert!(!missing_header.is_compressed());
+
+    missing_header.set_compressed(true);
+    assert_eq!(missing_header.version, MISSING_COMPRESSED_FORMAT_VERSION);
+    let restored = MissingSeedsHeader::from_bytes(&missing_header.to_bytes()).unwrap();
+    assert!(restored.is_compressed());
+
+    missing_header.set_compressed(false);
+    assert_eq!(missing_header.version, FILE_FORMAT_VERSION);
+
+    let mut bytes = missing_header.to_bytes();
+    bytes[8..10].copy_from_slice(&(MISSING_COMPRESSED_FORMAT_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        MissingSeedsHeader::from_bytes(&bytes),
+        Err(MissingFormatError::UnsupportedVersion(_))
+    ));
+}
@@ -1,7 +1,9 @@
 use byteorder::{LittleEndian, WriteBytesExt};
 use gen7seed_rainbow::ChainEntry;
 use gen7seed_rainbow::Sfmt;
-use gen7seed_rainbow::constants::{FILE_FORMAT_VERSION, FLAG_SORTED, NEEDLE_COUNT};
+use gen7seed_rainbow::constants::{
+    FILE_FORMAT_VERSION, FLAG_FULL_HASH_SORT, FLAG_SORTED, NEEDLE_COUNT,
+};
 use gen7seed_rainbow::domain::missing_format::MissingSeedsHeader;
 use gen7seed_rainbow::domain::table_format::{TableFormatError, TableHeader, ValidationOptions};
 use gen7seed_rainbow::infra::missing_seeds_io::{
@@ -23,7 +25,7 @@ fn validation_options() -> ValidationOptions {
     ValidationOptions {
         expected_consumption: Some(CONSUMPTION),
         require_sorted: true,
-        validate_constants: false,
+        expected_params: None,
     }
 }
 
@@ -34,8 +36,14 @@ fn build_header(consumption: i32, sorted: bool) -> TableHeader {
         chain_length: gen7seed_rainbow::MAX_CHAIN_LENGTH,
         chains_per_table: CHAINS_PER_TABLE,
         num_tables: TABLE_COUNT,
-        flags: if sorted { FLAG_SORTED } else { 0 },
+        flags: if sorted {
+            FLAG_SORTED | FLAG_FULL_HASH_SORT
+        } else {
+            0
+        },
         created_at: CREATED_AT,
+        checksum: None,
+        chains_checksum: None,
     }
 }
 
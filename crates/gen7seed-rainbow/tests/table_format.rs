@@ -1,6 +1,11 @@
-use gen7seed_rainbow::constants::{FILE_FORMAT_VERSION, FILE_HEADER_SIZE};
+use gen7seed_rainbow::constants::{
+    CHAIN_ENTRY_SIZE, COMPACT_FORMAT_VERSION, END_HASH_SIZE, FILE_FORMAT_VERSION, FILE_HEADER_SIZE,
+    FLAG_FULL_HASH_SORT, SECTIONED_FORMAT_VERSION,
+};
+use gen7seed_rainbow::domain::chain::EndHashKey;
 use gen7seed_rainbow::domain::table_format::{
-    TableFormatError, TableHeader, ValidationOptions, validate_header,
+    StartPoints, TableFormatError, TableHeader, TableParams, ValidationOptions,
+    end_hash_section_offset, expected_file_size, validate_header,
 };
 
 #[test]
@@ -24,7 +29,7 @@ fn test_table_header_magic_validation() {
 #[test]
 fn test_table_header_version_validation() {
     let mut header = TableHeader::new(417, true);
-    header.version = FILE_FORMAT_VERSION + 1;
+    header.version = SECTIONED_FORMAT_VERSION + 1;
     let bytes = header.to_bytes();
 
     let result = TableHeader::from_bytes(&bytes);
@@ -53,7 +58,7 @@ fn test_validate_consumption_mismatch() {
 fn test_validate_chain_length_mismatch() {
     let mut header = TableHeader::new(417, true);
     header.chain_length += 1;
-    let options = ValidationOptions::for_search(417);
+    let options = ValidationOptions::for_search(417).with_params(TableParams::default());
 
     let result = validate_header(&header, &options);
     assert!(matches!(
@@ -61,3 +66,123 @@ fn test_validate_chain_length_mismatch() {
         Err(TableFormatError::ChainLengthMismatch { .. })
     ));
 }
+
+#[test]
+fn test_validate_accepts_custom_params() {
+    let params = TableParams::new(256, 1024, 4);
+    let header = TableHeader::with_params(417, params, true);
+    assert_eq!(header.params(), params);
+
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert_eq!(restored.params(), params);
+    assert!(validate_header(&restored, &ValidationOptions::for_search(417)).is_ok());
+}
+
+#[test]
+fn test_validate_rejects_zero_params() {
+    let header = TableHeader::with_params(417, TableParams::new(0, 1024, 4), true);
+
+    let result = validate_header(&header, &ValidationOptions::for_search(417));
+    assert!(matches!(result, Err(TableFormatError::InvalidParams(_))));
+}
+
+#[test]
+fn test_legacy_header_uses_truncated_sort_key() {
+    let mut header = TableHeader::new(417, true);
+    assert_eq!(header.end_hash_key(), EndHashKey::Full);
+
+    header.flags &= !FLAG_FULL_HASH_SORT;
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert!(restored.is_sorted());
+    assert_eq!(restored.end_hash_key(), EndHashKey::Truncated);
+}
+
+#[test]
+fn test_expected_file_size_with_end_hashes() {
+    let mut header = TableHeader::new(417, true);
+    let num_chains = header.chains_per_table as u64 * header.num_tables as u64;
+    let chains_size = FILE_HEADER_SIZE as u64 + num_chains * CHAIN_ENTRY_SIZE as u64;
+    assert_eq!(expected_file_size(&header), chains_size);
+
+    header.set_end_hashes(true);
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert!(restored.has_end_hashes());
+    assert_eq!(end_hash_section_offset(&restored), chains_size);
+    assert_eq!(
+        expected_file_size(&restored),
+        chains_size + num_chains * END_HASH_SIZE as u64
+    );
+}
+
+#[test]
+fn test_start_points_recorded_in_header() {
+    let mut header = TableHeader::new(417, true);
+    assert_eq!(header.start_points(), StartPoints::Sequential);
+
+    header.set_start_points(StartPoints::CoverageGuided);
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert_eq!(restored.start_points(), StartPoints::CoverageGuided);
+    assert!(restored.is_sorted());
+
+    header.set_start_points(StartPoints::Sequential);
+    assert_eq!(header.flags, TableHeader::new(417, true).flags);
+}
+
+#[test]
+fn test_perfect_flag_recorded_in_header() {
+    let mut header = TableHeader::new(417, true);
+    assert!(!header.is_perfect());
+
+    header.set_perfect(true);
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert!(restored.is_perfect());
+    assert!(restored.is_sorted());
+
+    header.set_perfect(false);
+    assert_eq!(header.flags, TableHeader::new(417, true).flags);
+}
+
+#[test]
+fn test_compact_version_recorded_in_header() {
+    let mut header = TableHeader::new(417, true);
+    assert!(!header.is_compact());
+
+    header.set_compact(true);
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert!(restored.is_compact());
+    assert_eq!(restored.version, COMPACT_FORMAT_VERSION);
+
+    header.set_compact(false);
+    assert_eq!(header.version, FILE_FORMAT_VERSION);
+}
+
+#[test]
+fn test_sectioned_version_recorded_in_header() {
+    let mut header = TableHeader::new(417, true);
+    assert!(!header.is_sectioned());
+
+    header.set_sectioned(true);
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert!(restored.is_sectioned());
+    assert!(!restored.is_compact());
+    assert_eq!(restored.version, SECTIONED_FORMAT_VERSION);
+
+    header.set_sectioned(false);
+    assert_eq!(header.version, FILE_FORMAT_VERSION);
+}
+
+#[test]
+fn test_checksum_recorded_in_header() {
+    let mut header = TableHeader::new(417, true);
+    assert_eq!(header.checksum, None);
+    assert_eq!(
+        TableHeader::from_bytes(&header.to_bytes())
+            .unwrap()
+            .checksum,
+        None
+    );
+
+    header.checksum = Some(0xfedc_ba98_7654_3210);
+    let restored = TableHeader::from_bytes(&header.to_bytes()).unwrap();
+    assert_eq!(restored.checksum, Some(0xfedc_ba98_7654_3210));
+}
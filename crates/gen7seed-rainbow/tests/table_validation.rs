@@ -26,8 +26,8 @@ use std::time::Instant;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use gen7seed_rainbow::Sfmt;
-use gen7seed_rainbow::constants::{FILE_FORMAT_VERSION, FLAG_SORTED};
-use gen7seed_rainbow::domain::chain::ChainEntry;
+use gen7seed_rainbow::constants::{FILE_FORMAT_VERSION, FLAG_FULL_HASH_SORT, FLAG_SORTED};
+use gen7seed_rainbow::domain::chain::{ChainEntry, TableRef};
 use gen7seed_rainbow::domain::hash::gen_hash_from_seed;
 use gen7seed_rainbow::domain::table_format::TableHeader;
 use gen7seed_rainbow::infra::table_io::load_single_table;
@@ -70,7 +70,7 @@ fn validation_options() -> ValidationOptions {
     ValidationOptions {
         expected_consumption: Some(CONSUMPTION),
         require_sorted: true,
-        validate_constants: false,
+        expected_params: None,
     }
 }
 
@@ -81,8 +81,14 @@ fn build_header(sorted: bool) -> TableHeader {
         chain_length: gen7seed_rainbow::MAX_CHAIN_LENGTH,
         chains_per_table: CHAINS_PER_TABLE,
         num_tables: TABLE_COUNT,
-        flags: if sorted { FLAG_SORTED } else { 0 },
+        flags: if sorted {
+            FLAG_SORTED | FLAG_FULL_HASH_SORT
+        } else {
+            0
+        },
         created_at: CREATED_AT,
+        checksum: None,
+        chains_checksum: None,
     }
 }
 
@@ -184,8 +190,8 @@ fn generate_needle_from_seed(seed: u32, consumption: i32) -> [u64; 8] {
 /// Verify table is sorted correctly
 fn verify_sort_order(table: &[ChainEntry], consumption: i32) -> bool {
     table.windows(2).all(|w| {
-        let key0 = gen_hash_from_seed(w[0].end_seed, consumption) as u32;
-        let key1 = gen_hash_from_seed(w[1].end_seed, consumption) as u32;
+        let key0 = gen_hash_from_seed(w[0].end_seed, consumption);
+        let key1 = gen_hash_from_seed(w[1].end_seed, consumption);
         key0 <= key1
     })
 }
@@ -236,8 +242,8 @@ fn test_sorted_table_order() {
             .windows(2)
             .enumerate()
             .filter(|(_, w)| {
-                let key0 = gen_hash_from_seed(w[0].end_seed, CONSUMPTION) as u32;
-                let key1 = gen_hash_from_seed(w[1].end_seed, CONSUMPTION) as u32;
+                let key0 = gen_hash_from_seed(w[0].end_seed, CONSUMPTION);
+                let key1 = gen_hash_from_seed(w[1].end_seed, CONSUMPTION);
                 key0 > key1
             })
             .collect();
@@ -314,7 +320,8 @@ fn test_full_table_sort_order_sampling() {
     };
 
     let options = ValidationOptions::for_search(CONSUMPTION);
-    let (_header, tables) = load_single_table(&path, &options).expect("Failed to load table");
+    let (header, tables) = load_single_table(&path, &options).expect("Failed to load table");
+    let sort_key = header.end_hash_key();
     let table = &tables[0];
     println!("Loaded {} entries", table.len());
 
@@ -325,8 +332,8 @@ fn test_full_table_sort_order_sampling() {
     let mut violations = 0;
     for _ in 0..sample_count {
         let idx = rng.gen_range(0..table.len() - 1);
-        let key0 = gen_hash_from_seed(table[idx].end_seed, CONSUMPTION) as u32;
-        let key1 = gen_hash_from_seed(table[idx + 1].end_seed, CONSUMPTION) as u32;
+        let key0 = sort_key.key(gen_hash_from_seed(table[idx].end_seed, CONSUMPTION));
+        let key1 = sort_key.key(gen_hash_from_seed(table[idx + 1].end_seed, CONSUMPTION));
         if key0 > key1 {
             violations += 1;
         }
@@ -347,8 +354,8 @@ fn test_full_table_search_random_seeds() {
     };
 
     let options = ValidationOptions::for_search(CONSUMPTION);
-    let (_header, tables) = load_single_table(&path, &options).expect("Failed to load table");
-    let table = &tables[0];
+    let (header, tables) = load_single_table(&path, &options).expect("Failed to load table");
+    let table = TableRef::new(&tables[0]).with_sort_key(header.end_hash_key());
     let table_size = table.len() as u32;
 
     // Pick random seeds within the table range
@@ -394,12 +401,14 @@ fn test_search_seeds_x16_with_full_table() {
     };
 
     let options = ValidationOptions::for_search(CONSUMPTION);
-    let (_header, tables) = load_single_table(&path, &options).expect("Failed to load table");
+    let (header, tables) = load_single_table(&path, &options).expect("Failed to load table");
 
     assert_eq!(tables.len(), 16, "Full table should have 16 sub-tables");
 
     // Build table references for x16 search
-    let table_refs: [&[ChainEntry]; 16] = std::array::from_fn(|i| tables[i].as_slice());
+    let table_refs: [TableRef; 16] = std::array::from_fn(|i| {
+        TableRef::new(tables[i].as_slice()).with_sort_key(header.end_hash_key())
+    });
 
     // Test with seeds from different tables
     let mut found_count = 0;
@@ -417,7 +426,8 @@ fn test_search_seeds_x16_with_full_table() {
         let x16_results = search_seeds_x16(needle, CONSUMPTION, table_refs);
 
         // Also search using sequential
-        let seq_results = search_seeds(needle, CONSUMPTION, &tables[table_id as usize], table_id);
+        let seq_results =
+            search_seeds(needle, CONSUMPTION, table_refs[table_id as usize], table_id);
 
         total_tests += 1;
         if x16_results.iter().any(|(_, s)| *s == seed) {
@@ -451,9 +461,11 @@ fn test_search_x16_cross_table() {
     };
 
     let options = ValidationOptions::for_search(CONSUMPTION);
-    let (_header, tables) = load_single_table(&path, &options).expect("Failed to load table");
+    let (header, tables) = load_single_table(&path, &options).expect("Failed to load table");
 
-    let table_refs: [&[ChainEntry]; 16] = std::array::from_fn(|i| tables[i].as_slice());
+    let table_refs: [TableRef; 16] = std::array::from_fn(|i| {
+        TableRef::new(tables[i].as_slice()).with_sort_key(header.end_hash_key())
+    });
 
     // Random sampling from different tables
     let mut rng = rand::thread_rng();